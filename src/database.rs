@@ -0,0 +1,1188 @@
+use std::fmt::Display;
+
+use crate::{
+    error::Error,
+    executor::{
+        context::SimpleMemoryContext,
+        ddl, dml,
+        error::ExecuteError,
+        meta::{
+            column::Column,
+            row::{Row, SimpleMemoryRow},
+            schema::Schema,
+        },
+        plan::{logic::LogicPlan, physical::PhysicalPlan},
+        planner::{
+            logic::LogicPlanner,
+            optimizer::Optimizer,
+            physical::{JoinAlgorithm, PhysicalPlanner},
+        },
+        types::{DataType, OwnValue},
+    },
+    sql::{
+        parser::{
+            ast::{Explain, Select, Statement, Statements},
+            parser::Parser,
+        },
+        tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+    },
+};
+
+/// QueryResult 语句执行结果，结果中的行可以借用数据库中表的值
+#[derive(Debug)]
+pub struct QueryResult<'a> {
+    pub schema: Schema,
+    pub rows: Vec<Box<dyn Row + 'a>>,
+    pub affected_rows: Option<usize>, // INSERT 等修改数据的语句影响的行数
+}
+
+impl QueryResult<'_> {
+    /// 不返回数据的空结果，例如空语句
+    pub fn empty() -> Self {
+        Self {
+            schema: Schema {
+                name: "".into(),
+                columns: vec![].into_boxed_slice(),
+            },
+            rows: vec![],
+            affected_rows: None,
+        }
+    }
+
+    /// 修改数据的语句的结果，只包含影响的行数
+    pub fn affected(rows: usize) -> Self {
+        Self {
+            affected_rows: Some(rows),
+            ..Self::empty()
+        }
+    }
+}
+
+impl Display for QueryResult<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(affected_rows) = self.affected_rows {
+            return write!(f, "affected rows: {}", affected_rows);
+        }
+        // DDL 和空语句没有结果列
+        if self.schema.columns.is_empty() {
+            return write!(f, "OK");
+        }
+        write!(f, "{:?}", self.schema)?;
+        // 行与 schema 不符时输出错误信息，不中断整个结果的输出
+        for row in self.rows.iter() {
+            match row.to_string(&self.schema) {
+                Ok(row) => write!(f, "\n{}", row)?,
+                Err(error) => write!(f, "\n<{}>", error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Database 数据库入口
+/// 执行 SQL 语句：词法分析 -> 语法分析 -> 逻辑计划 -> 逻辑优化 -> 物理计划 -> 执行
+#[derive(Default)]
+pub struct Database {
+    context: SimpleMemoryContext,
+    optimizer: Optimizer,
+    physical_planner: PhysicalPlanner,
+}
+
+impl Database {
+    pub fn new() -> Self {
+        Self {
+            context: SimpleMemoryContext::new(),
+            optimizer: Optimizer::new(),
+            physical_planner: PhysicalPlanner::new(),
+        }
+    }
+
+    pub fn with_context(context: SimpleMemoryContext) -> Self {
+        Self {
+            context,
+            optimizer: Optimizer::new(),
+            physical_planner: PhysicalPlanner::new(),
+        }
+    }
+
+    /// 设置排序可以使用的内存（字节），超过后溢写到临时文件
+    pub fn set_sort_memory_budget(&mut self, budget: usize) {
+        self.physical_planner.sort_memory_budget = budget;
+    }
+
+    /// 指定连接算法，None 时由物理计划器自动选择
+    pub fn set_join_algorithm(&mut self, algorithm: Option<JoinAlgorithm>) {
+        self.physical_planner.join_algorithm = algorithm;
+    }
+
+    pub fn context(&self) -> &SimpleMemoryContext {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut SimpleMemoryContext {
+        &mut self.context
+    }
+
+    /// 执行 SQL，可以包含多条语句，返回最后一条语句的结果
+    pub fn execute(&mut self, sql: &str) -> Result<QueryResult<'_>, Error> {
+        self.execute_with_params(sql, &[])
+    }
+
+    /// 执行带参数占位符 ? 的 SQL，占位符按在整段 SQL 中出现的顺序取 params 中的值
+    pub fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[OwnValue],
+    ) -> Result<QueryResult<'_>, Error> {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize()?;
+        let statements: Statements = Parser::new(&tokens).parse()?;
+
+        // 结果借用数据库，前面语句的结果在执行下一条语句前丢弃
+        let Some((last, statements_before)) = statements.statements.split_last() else {
+            return Ok(QueryResult::empty());
+        };
+        for statement in statements_before {
+            self.execute_statement(statement, &statements.raw_sql, params)?;
+        }
+        Ok(self.execute_statement(last, &statements.raw_sql, params)?)
+    }
+
+    fn execute_statement(
+        &mut self,
+        statement: &Statement,
+        raw_sql: &str,
+        params: &[OwnValue],
+    ) -> Result<QueryResult<'_>, ExecuteError> {
+        match statement {
+            Statement::Select(select) => self.query(select, raw_sql, params),
+            Statement::CreateTable(create_table) => {
+                ddl::create_table(&mut self.context, create_table, raw_sql)?;
+                Ok(QueryResult::empty())
+            }
+            Statement::DropTable(drop_table) => {
+                ddl::drop_table(&mut self.context, drop_table, raw_sql)?;
+                Ok(QueryResult::empty())
+            }
+            Statement::AlterTable(alter_table) => {
+                ddl::alter_table(&mut self.context, alter_table, raw_sql, params)?;
+                Ok(QueryResult::empty())
+            }
+            Statement::Insert(insert) => Ok(QueryResult::affected(dml::insert(
+                &mut self.context,
+                &self.optimizer,
+                &self.physical_planner,
+                insert,
+                raw_sql,
+                params,
+            )?)),
+            Statement::Update(update) => Ok(QueryResult::affected(dml::update(
+                &mut self.context,
+                update,
+                raw_sql,
+                params,
+            )?)),
+            Statement::Delete(delete) => Ok(QueryResult::affected(dml::delete(
+                &mut self.context,
+                delete,
+                raw_sql,
+                params,
+            )?)),
+            Statement::Explain(explain) => self.explain(explain, raw_sql, params),
+            Statement::Empty(_) => Ok(QueryResult::empty()),
+        }
+    }
+
+    fn query(
+        &self,
+        select: &Select,
+        raw_sql: &str,
+        params: &[OwnValue],
+    ) -> Result<QueryResult<'_>, ExecuteError> {
+        let logic = LogicPlanner::new(&self.context, raw_sql)
+            .with_params(params)
+            .plan_select(select)?;
+        let logic = self.optimizer.optimize(logic);
+        let mut physical: Box<dyn PhysicalPlan<'_> + '_> = self.physical_planner.build(&logic)?;
+
+        // 按行执行，扫描输出的行借用表中的值，结果中不复制表中的字符串
+        let mut rows: Vec<Box<dyn Row + '_>> = vec![];
+        physical.open()?;
+        while let Some(row) = physical.next()? {
+            rows.push(row);
+        }
+        physical.close()?;
+
+        Ok(QueryResult {
+            schema: physical.schema().clone(),
+            rows,
+            affected_rows: None,
+        })
+    }
+
+    /// 输出优化后的逻辑计划和物理计划，每个计划一行。
+    /// ANALYZE 时执行查询并丢弃结果，物理计划的每个算子后面附上执行统计
+    fn explain(
+        &self,
+        explain: &Explain,
+        raw_sql: &str,
+        params: &[OwnValue],
+    ) -> Result<QueryResult<'_>, ExecuteError> {
+        let logic = LogicPlanner::new(&self.context, raw_sql)
+            .with_params(params)
+            .plan_select(&explain.select)?;
+        let logic = self.optimizer.optimize(logic);
+        let planner: PhysicalPlanner = PhysicalPlanner {
+            analyze: explain.analyze,
+            ..self.physical_planner.clone()
+        };
+        let mut physical: Box<dyn PhysicalPlan<'_> + '_> = planner.build(&logic)?;
+        if explain.analyze {
+            physical.open()?;
+            while physical.batch()?.is_some() {}
+            physical.close()?;
+        }
+
+        let column = |name: &str| Column {
+            name: name.into(),
+            data_type: DataType::String,
+            nullable: false,
+            default: None,
+        };
+        let row = |plan_type: &str, plan: String| -> Box<dyn Row> {
+            Box::new(SimpleMemoryRow::new(vec![
+                OwnValue::String(plan_type.to_string()),
+                OwnValue::String(plan),
+            ]))
+        };
+        Ok(QueryResult {
+            schema: Schema {
+                name: "explain".into(),
+                columns: vec![column("plan_type"), column("plan")].into_boxed_slice(),
+            },
+            rows: vec![
+                row("logical_plan", logic.to_string()),
+                row("physical_plan", physical.to_string()),
+            ],
+            affected_rows: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::executor::{
+        context::Context,
+        meta::{
+            column::Column,
+            data_source::{DataSource, SimpleMemoryDataSource},
+            row::SimpleMemoryRow,
+        },
+        types::{int32::Int32, DataType, OwnValue},
+    };
+
+    fn database() -> Database {
+        let schema: Schema = Schema {
+            name: "stu".into(),
+            columns: vec![
+                Column {
+                    name: "name".into(),
+                    data_type: DataType::Varchar(32),
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "age".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        source.push_row(SimpleMemoryRow::new(vec![
+            OwnValue::String("张三".into()),
+            OwnValue::Int32(Int32::new(18)),
+        ]));
+        source.push_row(SimpleMemoryRow::new(vec![
+            OwnValue::Null,
+            OwnValue::Int32(Int32::new(20)),
+        ]));
+
+        let mut database: Database = Database::new();
+        database.context_mut().add_table(Box::new(source));
+        database
+    }
+
+    fn rows(result: &QueryResult) -> Vec<String> {
+        result
+            .rows
+            .iter()
+            .map(|row| row.to_string(&result.schema).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn select_columns() {
+        let mut database: Database = database();
+        let result: QueryResult = database.execute("SELECT age, name FROM stu;").unwrap();
+        assert_eq!(result.schema.columns.len(), 2);
+        assert_eq!(rows(&result), vec!["[18, \"张三\"]", "[20, NULL]"]);
+    }
+
+    #[test]
+    fn select_wildcard() {
+        let mut database: Database = database();
+        let result: QueryResult = database.execute("SELECT * FROM stu").unwrap();
+        assert_eq!(rows(&result), vec!["[\"张三\", 18]", "[NULL, 20]"]);
+    }
+
+    #[test]
+    fn select_borrows_table_strings() {
+        let mut database: Database = database();
+        let table: &dyn DataSource = database.context().find_table("stu").unwrap();
+        let name: *const u8 = table.read().next().unwrap().get_string(0).unwrap().as_ptr();
+
+        // 扫描输出的行借用表中的字符串，结果中的值与表中的值是同一块内存
+        for sql in ["SELECT name FROM stu WHERE age < 20", "SELECT * FROM stu"] {
+            let result: QueryResult = database.execute(sql).unwrap();
+            assert_eq!(
+                result.rows[0].get_string(0).unwrap().as_ptr(),
+                name,
+                "{}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn select_projection_batches() {
+        // 超过一批的行，只读取宽表中的部分列
+        let values: Vec<String> = (0..2500)
+            .map(|i| match i % 3 {
+                0 => format!("('a{}', {}, 'c{}', NULL)", i, i, i),
+                _ => format!("('a{}', {}, 'c{}', 'd{}')", i, i, i, i),
+            })
+            .collect();
+        let mut database: Database = Database::new();
+        database
+            .execute("CREATE TABLE wide (a TEXT, b INT, c TEXT, d TEXT)")
+            .unwrap();
+        database
+            .execute(&format!("INSERT INTO wide VALUES {}", values.join(", ")))
+            .unwrap();
+
+        let result: QueryResult = database
+            .execute("SELECT d, b FROM wide WHERE b % 2 = 1")
+            .unwrap();
+        let rows: Vec<String> = rows(&result);
+        assert_eq!(rows.len(), 1250);
+        assert_eq!(rows[..2], ["[\"d1\", 1]", "[NULL, 3]"]);
+        assert_eq!(rows[1249], "[NULL, 2499]");
+    }
+
+    #[test]
+    fn select_expression_where() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT age + 1, name FROM stu WHERE age > 18")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[21, NULL]"]);
+        assert_eq!(result.schema.columns[0].name.as_ref(), "(age + 1)");
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT age % 7, age % 2.5 FROM stu WHERE age % 4 = 2")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[4, 0.5]"]);
+    }
+
+    #[test]
+    fn select_subquery() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT t.a * 2 FROM (SELECT age AS a FROM stu WHERE age < 20) AS t")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[36]"]);
+    }
+
+    #[test]
+    fn select_without_from() {
+        let mut database: Database = database();
+        for (sql, expected) in [
+            ("SELECT NULL", "[NULL]"),
+            ("SELECT NULL AND FALSE, NULL OR TRUE", "[false, true]"),
+            ("SELECT COALESCE(NULL, 1)", "[1]"),
+            ("SELECT 1 + 2 AS three, 7 % 4", "[3, 3]"),
+            ("SELECT COUNT(*)", "[1]"),
+        ] {
+            let result: QueryResult = database.execute(sql).unwrap();
+            assert_eq!(rows(&result), vec![expected], "{}", sql);
+        }
+
+        let result: QueryResult = database.execute("SELECT 1 WHERE FALSE").unwrap();
+        assert!(result.rows.is_empty());
+        drop(result);
+        let result: QueryResult = database.execute("SELECT 1 LIMIT 1 OFFSET 1").unwrap();
+        assert!(result.rows.is_empty());
+        drop(result);
+
+        let error = database.execute("SELECT *").unwrap_err();
+        assert!(
+            error.to_string().contains("wildcard without FROM clause"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn select_null() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT NULL, NULL + 1, NULL = NULL, NOT NULL, NULL IS NULL FROM stu WHERE age = 18")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[NULL, NULL, NULL, NULL, true]"]);
+        assert_eq!(result.schema.columns[0].data_type, DataType::Null);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT age FROM stu WHERE name IS NULL OR name = NULL")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[20]"]);
+        drop(result);
+        let result: QueryResult = database
+            .execute("SELECT age FROM stu WHERE NOT (name = '李四')")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[18]"]);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute(
+                "SELECT COALESCE(name, 'unknown'), IFNULL(name, NULL), NULLIF(age, 18),                  COALESCE(NULL, age, 1.5) FROM stu",
+            )
+            .unwrap();
+        assert_eq!(
+            rows(&result),
+            vec![
+                "[\"张三\", \"张三\", NULL, 18]",
+                "[\"unknown\", NULL, 20, 20]"
+            ]
+        );
+        assert!(!result.schema.columns[0].nullable);
+        assert_eq!(result.schema.columns[3].data_type, DataType::Float64);
+
+        drop(result);
+        for (sql, message) in [
+            ("SELECT NULLIF(age) FROM stu", "takes exactly two arguments"),
+            (
+                "SELECT COALESCE(age, name) FROM stu",
+                "incompatible argument types",
+            ),
+            ("SELECT name + NULL FROM stu", "invalid operand types"),
+        ] {
+            let error: Error = database.execute(sql).unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn insert_null() {
+        let mut database: Database = database();
+        database
+            .execute("INSERT INTO stu (name, age) VALUES (NULL, NULL)")
+            .unwrap();
+        let result: QueryResult = database
+            .execute("SELECT COUNT(*), COUNT(age), SUM(NULL), MAX(NULL) FROM stu WHERE age IS NULL")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[1, 0, NULL, NULL]"]);
+    }
+
+    #[test]
+    fn data_types() {
+        use crate::error::ErrorCode;
+
+        let mut database: Database = database();
+        database
+            .execute(
+                "CREATE TABLE item (ok BOOLEAN, id BIGINT, price DECIMAL(6, 2), born DATE,
+                    at TIME, updated TIMESTAMP, ttl INTERVAL, data BLOB)",
+            )
+            .unwrap();
+        database
+            .execute(
+                "INSERT INTO item VALUES
+                    (TRUE, 5000000000, 12.5, DATE '2024-02-29', TIME '08:30:00',
+                        TIMESTAMP '2024-03-01 12:00:00.25', INTERVAL '1 day 02:00:00', X'00ff'),
+                    (FALSE, 1, DECIMAL '-0.125', DATE '1999-12-31', TIME '23:59:59',
+                        DATE '2000-01-01', INTERVAL '1 year 2 months', X''),
+                    (NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL)",
+            )
+            .unwrap();
+        let result: QueryResult = database.execute("SELECT * FROM item").unwrap();
+        assert_eq!(
+            rows(&result),
+            vec![
+                "[true, 5000000000, 12.50, 2024-02-29, 08:30:00, 2024-03-01 12:00:00.25, 1 day 02:00:00, \\x00ff]",
+                "[false, 1, -0.13, 1999-12-31, 23:59:59, 2000-01-01 00:00:00, 1 year 2 months, \\x]",
+                "[NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL]",
+            ]
+        );
+        drop(result);
+
+        let result: QueryResult = database
+            .execute(
+                "SELECT id * 2, price * 2, price + 1.5, SUM(price), id > 1, born < DATE '2000-01-01'
+                    FROM item WHERE ok GROUP BY id, price, born",
+            )
+            .unwrap();
+        assert_eq!(
+            rows(&result),
+            vec!["[10000000000, 25.00, 14, 12.50, true, false]"]
+        );
+        assert_eq!(result.schema.columns[0].data_type, DataType::Int64);
+        assert_eq!(result.schema.columns[1].data_type, DataType::Decimal(38, 2));
+        assert_eq!(result.schema.columns[4].data_type, DataType::Boolean);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT id FROM item WHERE ttl > INTERVAL '1 day' AND NOT ok ORDER BY id")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[1]"]);
+
+        drop(result);
+        for (sql, code) in [
+            (
+                "INSERT INTO item (price) VALUES (10000)",
+                ErrorCode::NumericValueOutOfRange,
+            ),
+            (
+                "SELECT DATE '2023-02-29' FROM item",
+                ErrorCode::DatetimeFieldOverflow,
+            ),
+            (
+                "SELECT TIMESTAMP 'yesterday' FROM item",
+                ErrorCode::InvalidDatetimeFormat,
+            ),
+            (
+                "SELECT X'0g' FROM item",
+                ErrorCode::InvalidTextRepresentation,
+            ),
+            (
+                "SELECT id + 9223372036854775807 FROM item",
+                ErrorCode::NumericValueOutOfRange,
+            ),
+            ("SELECT born + 1 FROM item", ErrorCode::DatatypeMismatch),
+            // 条件必须是 Boolean，数值不会隐式转换为真值
+            ("SELECT ok FROM item WHERE id", ErrorCode::DatatypeMismatch),
+            (
+                "SELECT ok FROM item WHERE NOT price",
+                ErrorCode::DatatypeMismatch,
+            ),
+            ("DELETE FROM item WHERE data", ErrorCode::DatatypeMismatch),
+            (
+                "SELECT COUNT(*) FROM item HAVING COUNT(*)",
+                ErrorCode::DatatypeMismatch,
+            ),
+        ] {
+            let error: Error = database.execute(sql).unwrap_err();
+            assert_eq!(error.code(), code, "{}: {}", sql, error);
+        }
+    }
+
+    #[test]
+    fn error_codes() {
+        use crate::error::{ErrorCode, ErrorKind};
+
+        for (sql, code, kind) in [
+            ("SELECT FROM", ErrorCode::SyntaxError, ErrorKind::Syntax),
+            ("SELECT @", ErrorCode::SyntaxError, ErrorKind::Syntax),
+            (
+                "SELECT a FROM t",
+                ErrorCode::UndefinedTable,
+                ErrorKind::Binding,
+            ),
+            (
+                "SELECT x FROM stu",
+                ErrorCode::UndefinedColumn,
+                ErrorKind::Binding,
+            ),
+            (
+                "SELECT name + 1 FROM stu",
+                ErrorCode::DatatypeMismatch,
+                ErrorKind::Type,
+            ),
+            (
+                "INSERT INTO stu VALUES ('a', 'b')",
+                ErrorCode::DatatypeMismatch,
+                ErrorKind::Type,
+            ),
+            (
+                "SELECT age / 0 FROM stu",
+                ErrorCode::DivisionByZero,
+                ErrorKind::Runtime,
+            ),
+            (
+                "SELECT age / 0.0 FROM stu",
+                ErrorCode::DivisionByZero,
+                ErrorKind::Runtime,
+            ),
+            (
+                "SELECT age % 0 FROM stu",
+                ErrorCode::DivisionByZero,
+                ErrorKind::Runtime,
+            ),
+            (
+                "SELECT -age * 2147483647 FROM stu",
+                ErrorCode::NumericValueOutOfRange,
+                ErrorKind::Runtime,
+            ),
+        ] {
+            let error: Error = database().execute(sql).unwrap_err();
+            assert_eq!((error.code(), error.kind()), (code, kind), "{}", error);
+        }
+
+        let mut database: Database = database();
+        database.execute("CREATE TABLE t (a INT NOT NULL)").unwrap();
+        let error: Error = database
+            .execute("INSERT INTO t VALUES (1), (NULL)")
+            .unwrap_err();
+        assert_eq!(error.code().sqlstate(), "23502");
+        assert_eq!(error.kind(), ErrorKind::Constraint);
+        // 错误定位到出错的值
+        let location = error.location().unwrap();
+        assert_eq!((location.line_number, location.column_number), (1, 28));
+        // 约束错误没有更底层的原因
+        assert!(std::error::Error::source(&error).is_none());
+
+        // I/O 错误的原因穿过 ExecuteError 和 Error 保留下来
+        let error: Error = ExecuteError::new(ErrorCode::IoError, "spill file error")
+            .with_source(std::io::Error::other("disk full"))
+            .into();
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().to_string(),
+            "disk full"
+        );
+
+        let error: Error = database.execute("SELECT a FROM\n  x").unwrap_err();
+        let location = error.location().unwrap();
+        assert_eq!((location.line_number, location.column_number), (2, 3));
+    }
+
+    #[test]
+    fn select_empty_statement() {
+        let mut database: Database = database();
+        let result: QueryResult = database.execute(";").unwrap();
+        assert!(result.rows.is_empty());
+        assert_eq!(result.to_string(), "OK");
+        drop(result);
+        let result: QueryResult = database.execute("CREATE TABLE t (a INT)").unwrap();
+        assert_eq!(result.to_string(), "OK");
+    }
+
+    #[test]
+    fn select_unknown_table() {
+        let error: Error = database().execute("SELECT a FROM tea").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "table tea not found in Ln 1, Col 15 near \"SELECT a FROM tea\""
+        );
+    }
+
+    #[test]
+    fn select_unknown_column() {
+        let error: Error = database().execute("SELECT a FROM stu").unwrap_err();
+        assert!(matches!(error, Error::Execute(_)));
+    }
+
+    #[test]
+    fn select_parse_error() {
+        let error: Error = database().execute("SELECT a FROM stu stu").unwrap_err();
+        assert!(matches!(error, Error::Parse(_)));
+    }
+
+    #[test]
+    fn select_aggregate() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute(
+                "SELECT count(*), count(name), sum(age), avg(age), min(age), max(name) FROM stu",
+            )
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[2, 1, 38, 19, 18, \"张三\"]"]);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT count(*), sum(age) FROM stu WHERE age > 100")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[0, NULL]"]);
+        drop(result);
+
+        // Int32 的和按 Int64 计算，超过 Int32 的范围不溢出
+        database
+            .execute("CREATE TABLE big (v INT); INSERT INTO big VALUES (2147483647), (2147483647)")
+            .unwrap();
+        let result: QueryResult = database
+            .execute("SELECT count(*), sum(v), sum(v) + 1 FROM big")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[2, 4294967294, 4294967295]"]);
+        assert_eq!(result.schema.columns[0].data_type, DataType::Int64);
+        assert_eq!(result.schema.columns[1].data_type, DataType::Int64);
+    }
+
+    #[test]
+    fn select_group_by_having() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT age, count(*) AS c, age + sum(age) FROM stu GROUP BY age HAVING c > 0 AND age > 18")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[20, 1, 40]"]);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT count(*) FROM stu GROUP BY age HAVING max(age) < 10")
+            .unwrap();
+        assert!(result.rows.is_empty());
+    }
+
+    #[test]
+    fn select_order_by() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT name, age FROM stu ORDER BY name")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[\"张三\", 18]", "[NULL, 20]"]);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT name, age FROM stu ORDER BY name NULLS FIRST")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[NULL, 20]", "[\"张三\", 18]"]);
+        drop(result);
+
+        // 内存不足时溢写到临时文件，结果相同
+        database.set_sort_memory_budget(0);
+        let result: QueryResult = database
+            .execute("SELECT age + 1 AS a FROM stu ORDER BY a DESC")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[21]", "[19]"]);
+    }
+
+    #[test]
+    fn select_limit_offset() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("SELECT age FROM stu ORDER BY age DESC LIMIT 1 OFFSET 1")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[18]"]);
+        drop(result);
+
+        let result: QueryResult = database
+            .execute_with_params(
+                "SELECT age FROM stu WHERE age > ? LIMIT ?",
+                &[
+                    OwnValue::Int32(Int32::new(0)),
+                    OwnValue::Int32(Int32::new(1)),
+                ],
+            )
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[18]"]);
+        drop(result);
+
+        let result: QueryResult = database.execute("SELECT age FROM stu LIMIT 0").unwrap();
+        assert!(result.rows.is_empty());
+    }
+
+    #[test]
+    fn create_table() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute(
+                "CREATE TABLE tea (name VARCHAR(16) NOT NULL, salary DOUBLE); SELECT * FROM tea",
+            )
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", result.schema),
+            "tea(name Varchar(16) NOT NULL, salary Float64 NULLABLE)"
+        );
+        assert!(result.rows.is_empty());
+        drop(result);
+
+        let error: Error = database.execute("CREATE TABLE stu (a INT)").unwrap_err();
+        assert!(matches!(error, Error::Execute(_)));
+    }
+
+    #[test]
+    fn insert() {
+        let mut database: Database = database();
+        let sql: &str = "INSERT INTO stu (age, name) VALUES (21, '王五'), (?, ?)";
+        let error: Error = database.execute(sql).unwrap_err();
+        assert!(error.to_string().contains("missing value for parameter"));
+
+        let result: QueryResult = database
+            .execute_with_params(sql, &[OwnValue::Int32(Int32::new(22)), OwnValue::Null])
+            .unwrap();
+        assert_eq!(result.affected_rows, Some(2));
+        assert_eq!(result.to_string(), "affected rows: 2");
+        drop(result);
+
+        let result: QueryResult = database
+            .execute("SELECT name, age FROM stu WHERE age > 20")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[\"王五\", 21]", "[NULL, 22]"]);
+    }
+
+    #[test]
+    fn update_delete() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute("UPDATE stu SET age = age + 1 WHERE name = '张三'")
+            .unwrap();
+        assert_eq!(result.affected_rows, Some(1));
+        drop(result);
+        let result: QueryResult = database.execute("SELECT age FROM stu").unwrap();
+        assert_eq!(rows(&result), vec!["[19]", "[20]"]);
+        drop(result);
+
+        let result: QueryResult = database.execute("DELETE FROM stu WHERE age > 19").unwrap();
+        assert_eq!(result.to_string(), "affected rows: 1");
+        drop(result);
+        let result: QueryResult = database.execute("SELECT * FROM stu").unwrap();
+        assert_eq!(rows(&result), vec!["[\"张三\", 19]"]);
+    }
+
+    #[test]
+    fn drop_alter_table() {
+        let mut database: Database = database();
+        let result: QueryResult = database
+            .execute(
+                "ALTER TABLE stu ADD score INT DEFAULT 60; \
+                 ALTER TABLE stu RENAME age TO years; \
+                 SELECT name, years, score FROM stu",
+            )
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[\"张三\", 18, 60]", "[NULL, 20, 60]"]);
+        drop(result);
+
+        // 之后插入时没有指定的列使用 ADD COLUMN 的默认值
+        let result: QueryResult = database
+            .execute(
+                "INSERT INTO stu (name, years) VALUES ('李四', 21); \
+                 INSERT INTO stu (name) SELECT 'x'; \
+                 SELECT name, years, score FROM stu WHERE years > 20 OR years IS NULL",
+            )
+            .unwrap();
+        assert_eq!(
+            rows(&result),
+            vec!["[\"李四\", 21, 60]", "[\"x\", NULL, 60]"]
+        );
+        drop(result);
+
+        database.execute("DROP TABLE stu").unwrap();
+        let error: Error = database.execute("SELECT * FROM stu").unwrap_err();
+        assert!(error.to_string().contains("table stu not found"));
+    }
+
+    #[test]
+    fn namespace() {
+        let mut database: Database = database();
+        database.context_mut().create_namespace("db").unwrap();
+        database.context_mut().set_current_namespace("db").unwrap();
+        database
+            .execute("CREATE TABLE stu (name TEXT); INSERT INTO stu VALUES ('李四')")
+            .unwrap();
+        database
+            .context_mut()
+            .set_current_namespace("main")
+            .unwrap();
+
+        let result: QueryResult = database
+            .execute("SELECT main.stu.name FROM stu WHERE main.stu.age = 18")
+            .unwrap();
+        assert_eq!(rows(&result), vec!["[\"张三\"]"]);
+        drop(result);
+        let result: QueryResult = database.execute("SELECT db.stu.* FROM db.stu").unwrap();
+        assert_eq!(rows(&result), vec!["[\"李四\"]"]);
+    }
+
+    fn join_database() -> Database {
+        let mut database: Database = database();
+        database
+            .execute(
+                "CREATE TABLE score (name TEXT, course TEXT, score INT);
+                INSERT INTO score VALUES ('张三', 'math', 90), ('张三', 'art', 80), ('王五', 'math', 70)",
+            )
+            .unwrap();
+        database
+            .execute_with_params(
+                "INSERT INTO score VALUES (?, 'math', 60)",
+                &[OwnValue::Null],
+            )
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn select_join() {
+        let cases: [(&str, Vec<&str>); 9] = [
+            (
+                "SELECT stu.name, course, score FROM stu JOIN score ON stu.name = score.name ORDER BY score",
+                vec!["[\"张三\", \"art\", 80]", "[\"张三\", \"math\", 90]"],
+            ),
+            (
+                "SELECT age, course, score FROM stu LEFT JOIN score ON stu.name = score.name ORDER BY age, score",
+                vec!["[18, \"art\", 80]", "[18, \"math\", 90]", "[20, NULL, NULL]"],
+            ),
+            (
+                "SELECT score.name, age, score FROM stu RIGHT OUTER JOIN score ON score.name = stu.name ORDER BY score",
+                vec![
+                    "[NULL, NULL, 60]",
+                    "[\"王五\", NULL, 70]",
+                    "[\"张三\", 18, 80]",
+                    "[\"张三\", 18, 90]",
+                ],
+            ),
+            (
+                "SELECT age, score FROM stu FULL JOIN score ON stu.name = score.name ORDER BY score, age",
+                vec!["[NULL, 60]", "[NULL, 70]", "[18, 80]", "[18, 90]", "[20, NULL]"],
+            ),
+            (
+                "SELECT * FROM stu JOIN score USING (name) ORDER BY score DESC",
+                vec!["[\"张三\", 18, \"math\", 90]", "[\"张三\", 18, \"art\", 80]"],
+            ),
+            (
+                "SELECT * FROM stu FULL JOIN score USING (name) ORDER BY score",
+                vec![
+                    "[NULL, NULL, \"math\", 60]",
+                    "[\"王五\", NULL, \"math\", 70]",
+                    "[\"张三\", 18, \"art\", 80]",
+                    "[\"张三\", 18, \"math\", 90]",
+                    "[NULL, 20, NULL, NULL]",
+                ],
+            ),
+            (
+                "SELECT age, course FROM stu LEFT JOIN score ON stu.name = score.name AND score > 85 ORDER BY age",
+                vec!["[18, \"math\"]", "[20, NULL]"],
+            ),
+            (
+                "SELECT count(*), sum(score) FROM stu, score",
+                vec!["[8, 600]"],
+            ),
+            (
+                "SELECT a.course, b.course FROM score AS a JOIN score AS b ON a.name = b.name AND a.score > b.score",
+                vec!["[\"math\", \"art\"]"],
+            ),
+        ];
+        for algorithm in [
+            None,
+            Some(JoinAlgorithm::NestedLoop),
+            Some(JoinAlgorithm::Hash),
+            Some(JoinAlgorithm::SortMerge),
+        ] {
+            let mut database: Database = join_database();
+            database.set_join_algorithm(algorithm);
+            for (sql, expected) in cases.iter() {
+                let result: QueryResult = database.execute(sql).unwrap();
+                assert_eq!(&rows(&result), expected, "{} with {:?}", sql, algorithm);
+            }
+        }
+    }
+
+    #[test]
+    fn select_optimized() {
+        // 谓词下推、列裁剪、常量折叠和排序分页的删除不改变查询结果
+        let mut database: Database = join_database();
+        for (sql, expected) in [
+            (
+                "SELECT age, course FROM stu LEFT JOIN score ON stu.name = score.name WHERE score > 85",
+                vec!["[18, \"math\"]"],
+            ),
+            (
+                "SELECT s.course FROM score AS s, stu WHERE s.name = stu.name AND stu.age < 19 AND s.score < 85",
+                vec!["[\"art\"]"],
+            ),
+            (
+                "SELECT course, total FROM (SELECT course, sum(score) AS total FROM score GROUP BY course) AS t \
+                 WHERE course = 'math' AND total > 200",
+                vec!["[\"math\", 220]"],
+            ),
+            (
+                "SELECT n FROM (SELECT name AS n, score FROM score ORDER BY score DESC LIMIT 3) AS t LIMIT 1 OFFSET 1",
+                vec!["[\"张三\"]"],
+            ),
+            (
+                "SELECT course FROM score WHERE score > 85",
+                vec!["[\"math\"]"],
+            ),
+            (
+                "SELECT count(*) FROM score WHERE 1 = 2 - 1 AND score > 60 + 5",
+                vec!["[3]"],
+            ),
+        ] {
+            let result: QueryResult = database.execute(sql).unwrap();
+            assert_eq!(rows(&result), expected, "{}", sql);
+        }
+    }
+
+    /// EXPLAIN 的结果，每个计划的类型和计划树，去掉不确定的耗时
+    fn plans(result: &QueryResult) -> Vec<(String, String)> {
+        result
+            .rows
+            .iter()
+            .map(|row| match (row.get(0).unwrap(), row.get(1).unwrap()) {
+                (OwnValue::String(plan_type), OwnValue::String(plan)) => (
+                    plan_type.clone(),
+                    plan.split(' ')
+                        .filter(|word| !word.starts_with("time="))
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                ),
+                _ => panic!("expect strings"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn explain() {
+        let mut database: Database = join_database();
+        let logical_plan: String = [
+            "Projection: stu.name, score.score",
+            "  Sort: score.score ASC NULLS LAST",
+            "    Join: INNER ON (stu.name = score.name)",
+            "      Scan: stu [name, age] filter=(stu.age > 2)",
+            "      Scan: score [name, score]",
+        ]
+        .join("\n");
+        let sql: &str = "SELECT stu.name, score FROM stu JOIN score ON stu.name = score.name \
+                         WHERE age > 1 + 1 ORDER BY score";
+
+        let result: QueryResult = database.execute(&format!("EXPLAIN {}", sql)).unwrap();
+        assert_eq!(
+            plans(&result),
+            vec![
+                ("logical_plan".to_string(), logical_plan.clone()),
+                (
+                    "physical_plan".to_string(),
+                    [
+                        "Projection: stu.name, score.score",
+                        "  Sort: score.score ASC NULLS LAST",
+                        "    Projection: stu.name, stu.age, score.name, score.score",
+                        "      HashJoin: INNER keys=[score.name = stu.name]",
+                        "        SeqScan: score [name, score]",
+                        "        SeqScan: stu [name, age] filter=(stu.age > 2)",
+                    ]
+                    .join("\n")
+                ),
+            ]
+        );
+        drop(result);
+
+        let result: QueryResult = database
+            .execute(&format!("EXPLAIN ANALYZE {}", sql))
+            .unwrap();
+        assert_eq!(
+            plans(&result),
+            vec![
+                ("logical_plan".to_string(), logical_plan),
+                (
+                    "physical_plan".to_string(),
+                    [
+                        "Projection: stu.name, score.score (rows=2 opens=1 closes=1)",
+                        "  Sort: score.score ASC NULLS LAST (rows=2 opens=1 closes=1)",
+                        "    Projection: stu.name, stu.age, score.name, score.score (rows=2 opens=1 closes=1)",
+                        "      HashJoin: INNER keys=[score.name = stu.name] (rows=2 opens=1 closes=1)",
+                        "        SeqScan: score [name, score] (rows=4 opens=1 closes=1)",
+                        "        SeqScan: stu [name, age] filter=(stu.age > 2) (rows=2 opens=1 closes=1)",
+                    ]
+                    .join("\n")
+                ),
+            ]
+        );
+        assert!(result.rows[1]
+            .to_string(&result.schema)
+            .unwrap()
+            .contains("time="));
+        drop(result);
+
+        let error: Error = database
+            .execute("EXPLAIN SELECT nothing FROM stu")
+            .unwrap_err();
+        assert!(error.to_string().contains("nothing"), "{}", error);
+    }
+
+    #[test]
+    fn explain_join_reorder() {
+        let mut database: Database = Database::new();
+        let values = |rows: i32| -> String {
+            (0..rows)
+                .map(|i| format!("({}, {})", i, i))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        for (name, column, rows) in [("a", "x", 2), ("b", "y", 10), ("c", "z", 100)] {
+            database
+                .execute(&format!(
+                    "CREATE TABLE {} (id INT, {} INT); INSERT INTO {} VALUES {}",
+                    name,
+                    column,
+                    name,
+                    values(rows)
+                ))
+                .unwrap();
+        }
+
+        // 连接顺序改变后，SELECT * 的投影直接恢复列顺序，不再叠加一个相同的投影
+        let sql: &str = "SELECT * FROM a JOIN b ON a.id = b.id JOIN c ON b.id = c.id";
+        let result: QueryResult = database.execute(&format!("EXPLAIN {}", sql)).unwrap();
+        assert_eq!(
+            plans(&result)[1].1,
+            [
+                "Projection: a.id, a.x, b.id, b.y, c.id, c.z",
+                "  HashJoin: INNER keys=[b.id = a.id]",
+                "    HashJoin: INNER keys=[c.id = b.id]",
+                "      SeqScan: c [id, z]",
+                "      SeqScan: b [id, y]",
+                "    SeqScan: a [id, x]",
+            ]
+            .join("\n")
+        );
+        drop(result);
+
+        let result: QueryResult = database.execute(sql).unwrap();
+        assert_eq!(
+            rows(&result),
+            vec!["[0, 0, 0, 0, 0, 0]", "[1, 1, 1, 1, 1, 1]"]
+        );
+    }
+
+    #[test]
+    fn select_join_error() {
+        let mut database: Database = join_database();
+        for (sql, message) in [
+            (
+                "SELECT name FROM stu JOIN score ON stu.name = score.name",
+                "ambiguous column name",
+            ),
+            (
+                "SELECT * FROM stu JOIN score ON stu.age = score.age",
+                "unknown column score.age",
+            ),
+            (
+                "SELECT * FROM stu JOIN stu ON 1",
+                "duplicate table name stu",
+            ),
+            (
+                "SELECT * FROM stu JOIN score USING (age)",
+                "column age in USING not found in right table",
+            ),
+            (
+                "SELECT * FROM stu JOIN score AS a ON a.score = count(*)",
+                "aggregate functions are not allowed in JOIN conditions",
+            ),
+        ] {
+            let error: Error = database.execute(sql).unwrap_err();
+            assert!(error.to_string().contains(message), "{}: {}", sql, error);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use crate::{
+    executor::error::ExecuteError,
+    sql::{
+        parser::{ast::leaf::Location, error::ParseError},
+        tokenizer::error::TokenizeError,
+    },
+};
+
+/// Error 执行一条 SQL 语句时可能出现的所有错误
+/// code 是稳定的错误码，kind 是错误的大类，location 是出错的位置
+#[derive(Debug)]
+pub enum Error {
+    Tokenize(TokenizeError),
+    Parse(ParseError),
+    Execute(ExecuteError),
+}
+
+impl Error {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Tokenize(_) | Error::Parse(_) => ErrorCode::SyntaxError,
+            Error::Execute(e) => e.code,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.code().kind()
+    }
+
+    /// 出错的位置，执行时的错误可能没有位置
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::Tokenize(e) => Some(e.location()),
+            Error::Parse(e) => Some(e.location()),
+            Error::Execute(e) => e.location.clone(),
+        }
+    }
+}
+
+/// 与 Display 一样对内层错误透明，source 直接是内层错误的原因
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Tokenize(e) => e.source(),
+            Error::Parse(e) => e.source(),
+            Error::Execute(e) => e.source(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Tokenize(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Execute(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<TokenizeError> for Error {
+    fn from(e: TokenizeError) -> Self {
+        Error::Tokenize(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<ExecuteError> for Error {
+    fn from(e: ExecuteError) -> Self {
+        Error::Execute(e)
+    }
+}
+
+/// ErrorCode 错误码，sqlstate 的取值参照 SQL 标准和 PostgreSQL 的 SQLSTATE，不会随版本改变
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorCode {
+    /// 词法、语法错误，以及语句结构不合法，例如 INSERT 的值的个数与列数不同
+    SyntaxError,
+    /// 不支持的语法或功能
+    FeatureNotSupported,
+    UndefinedTable,
+    UndefinedColumn,
+    /// 不存在的函数，或者函数的参数个数不对
+    UndefinedFunction,
+    /// 不存在的 namespace
+    UndefinedObject,
+    /// 缺少参数占位符的值
+    UndefinedParameter,
+    AmbiguousColumn,
+    DuplicateTable,
+    DuplicateColumn,
+    /// 已存在的 namespace、同一个 FROM 中重复的表名
+    DuplicateObject,
+    /// 聚合函数出现在不允许的位置，或者非分组列出现在聚合之上
+    GroupingError,
+    /// 表定义不合法，例如删除表的唯一一列
+    InvalidTableDefinition,
+    /// 被视图等其他对象依赖，不能删除或修改
+    DependentObjectsStillExist,
+    /// 对象正在使用，例如删除当前的 namespace
+    ObjectInUse,
+    /// 操作数、参数或者值的类型不符
+    DatatypeMismatch,
+    NotNullViolation,
+    /// 字符串超过 VARCHAR(n) 的长度
+    StringDataRightTruncation,
+    /// 整数溢出或者超出类型的范围
+    NumericValueOutOfRange,
+    DivisionByZero,
+    /// 字符串不能转换为目标类型，例如格式不对的十进制数、十六进制串或时间间隔
+    InvalidTextRepresentation,
+    /// 日期、时间的格式不对
+    InvalidDatetimeFormat,
+    /// 日期、时间的字段超出范围，例如 2 月 30 日
+    DatetimeFieldOverflow,
+    /// LIMIT、OFFSET 等参数的值不合法
+    InvalidParameterValue,
+    /// 读写外部文件出错
+    IoError,
+    /// 溢写文件等内部数据损坏
+    DataCorrupted,
+    /// 执行器的内部错误，例如计划与数据不一致
+    InternalError,
+}
+
+impl ErrorCode {
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            ErrorCode::SyntaxError => "42601",
+            ErrorCode::FeatureNotSupported => "0A000",
+            ErrorCode::UndefinedTable => "42P01",
+            ErrorCode::UndefinedColumn => "42703",
+            ErrorCode::UndefinedFunction => "42883",
+            ErrorCode::UndefinedObject => "42704",
+            ErrorCode::UndefinedParameter => "42P02",
+            ErrorCode::AmbiguousColumn => "42702",
+            ErrorCode::DuplicateTable => "42P07",
+            ErrorCode::DuplicateColumn => "42701",
+            ErrorCode::DuplicateObject => "42710",
+            ErrorCode::GroupingError => "42803",
+            ErrorCode::InvalidTableDefinition => "42P16",
+            ErrorCode::DependentObjectsStillExist => "2BP01",
+            ErrorCode::ObjectInUse => "55006",
+            ErrorCode::DatatypeMismatch => "42804",
+            ErrorCode::NotNullViolation => "23502",
+            ErrorCode::StringDataRightTruncation => "22001",
+            ErrorCode::NumericValueOutOfRange => "22003",
+            ErrorCode::DivisionByZero => "22012",
+            ErrorCode::InvalidTextRepresentation => "22P02",
+            ErrorCode::InvalidDatetimeFormat => "22007",
+            ErrorCode::DatetimeFieldOverflow => "22008",
+            ErrorCode::InvalidParameterValue => "22023",
+            ErrorCode::IoError => "58030",
+            ErrorCode::DataCorrupted => "XX001",
+            ErrorCode::InternalError => "XX000",
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            ErrorCode::SyntaxError => ErrorKind::Syntax,
+            ErrorCode::FeatureNotSupported
+            | ErrorCode::UndefinedTable
+            | ErrorCode::UndefinedColumn
+            | ErrorCode::UndefinedFunction
+            | ErrorCode::UndefinedObject
+            | ErrorCode::UndefinedParameter
+            | ErrorCode::AmbiguousColumn
+            | ErrorCode::DuplicateTable
+            | ErrorCode::DuplicateColumn
+            | ErrorCode::DuplicateObject
+            | ErrorCode::GroupingError
+            | ErrorCode::InvalidTableDefinition
+            | ErrorCode::DependentObjectsStillExist
+            | ErrorCode::ObjectInUse => ErrorKind::Binding,
+            ErrorCode::DatatypeMismatch => ErrorKind::Type,
+            ErrorCode::NotNullViolation | ErrorCode::StringDataRightTruncation => {
+                ErrorKind::Constraint
+            }
+            ErrorCode::NumericValueOutOfRange
+            | ErrorCode::DivisionByZero
+            | ErrorCode::InvalidTextRepresentation
+            | ErrorCode::InvalidDatetimeFormat
+            | ErrorCode::DatetimeFieldOverflow
+            | ErrorCode::InvalidParameterValue
+            | ErrorCode::IoError => ErrorKind::Runtime,
+            ErrorCode::DataCorrupted | ErrorCode::InternalError => ErrorKind::Internal,
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.sqlstate())
+    }
+}
+
+/// ErrorKind 错误的大类
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorKind {
+    /// 词法、语法错误
+    Syntax,
+    /// 名字解析、对象定义等语义错误
+    Binding,
+    /// 类型错误
+    Type,
+    /// 违反列的约束
+    Constraint,
+    /// 执行时数据导致的错误
+    Runtime,
+    /// 内部错误
+    Internal,
+}
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+use crate::{
+    executor::{
+        expression::BoundExpression,
+        meta::{column::Column, data_source::DataSource, schema::Schema},
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+/// BoundSelect 绑定后的 SELECT 语句
+/// wheres、group_by、having、order_by 中的列引用指向 FROM 子句拼接而成的输入行
+#[derive(Debug)]
+pub struct BoundSelect<'a> {
+    pub from: Box<[BoundFrom<'a>]>,
+    pub items: Box<[BoundExpression]>,
+    pub wheres: Option<BoundExpression>,
+    pub group_by: Box<[BoundExpression]>,
+    pub having: Option<BoundExpression>,
+    pub order_by: Box<[BoundOrderBy]>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+
+    /// 输出的列，和 items 一一对应
+    pub schema: Schema,
+}
+
+/// BoundFrom FROM 子句中的一项，schema.name 为表的别名
+#[derive(Debug)]
+pub enum BoundFrom<'a> {
+    Table {
+        data_source: &'a dyn DataSource,
+        schema: Schema,
+        namespace: Option<Box<str>>, // 表所在的命名空间，带别名时为 None
+    },
+    SubQuery {
+        select: Box<BoundSelect<'a>>,
+        schema: Schema,
+    },
+    /// 两项的连接，condition 中的列引用指向左右两项拼接而成的行
+    Join {
+        left: Box<BoundFrom<'a>>,
+        right: Box<BoundFrom<'a>>,
+        join_type: JoinType,
+        condition: Option<BoundExpression>,
+        schema: Schema,
+    },
+}
+
+impl<'a> BoundFrom<'a> {
+    /// 连接两项，输出的列为左右两项的列依次拼接，外连接中补 NULL 一侧的列可能为 NULL
+    pub fn join(
+        left: BoundFrom<'a>,
+        right: BoundFrom<'a>,
+        join_type: JoinType,
+        condition: Option<BoundExpression>,
+    ) -> Self {
+        let nullable = |column: &Column, nullable: bool| Column {
+            nullable: column.nullable || nullable,
+            default: None,
+            ..column.clone()
+        };
+        let columns: Vec<Column> = left
+            .schema()
+            .columns
+            .iter()
+            .map(|c| nullable(c, join_type.keeps_right()))
+            .chain(
+                right
+                    .schema()
+                    .columns
+                    .iter()
+                    .map(|c| nullable(c, join_type.keeps_left())),
+            )
+            .collect();
+        let schema: Schema = Schema {
+            name: left.schema().name.clone(),
+            columns: columns.into_boxed_slice(),
+        };
+        BoundFrom::Join {
+            left: Box::new(left),
+            right: Box::new(right),
+            join_type,
+            condition,
+            schema,
+        }
+    }
+
+    pub fn schema(&self) -> &Schema {
+        match self {
+            BoundFrom::Table { schema, .. } => schema,
+            BoundFrom::SubQuery { schema, .. } => schema,
+            BoundFrom::Join { schema, .. } => schema,
+        }
+    }
+
+    /// 可以用于限定列名的命名空间
+    pub fn namespace(&self) -> Option<&str> {
+        match self {
+            BoundFrom::Table { namespace, .. } => namespace.as_deref(),
+            BoundFrom::SubQuery { .. } | BoundFrom::Join { .. } => None,
+        }
+    }
+}
+
+/// BoundOrderBy 排序键
+/// 未指定 NULLS FIRST / NULLS LAST 时，NULL 视为比任何值都大，
+/// 即 ASC 时 NULL 在最后，DESC 时 NULL 在最前
+#[derive(Debug, Clone)]
+pub struct BoundOrderBy {
+    pub expression: BoundExpression,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl Display for BoundOrderBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)?;
+        if self.asc {
+            write!(f, " ASC")?;
+        } else {
+            write!(f, " DESC")?;
+        }
+        if self.nulls_first {
+            write!(f, " NULLS FIRST")
+        } else {
+            write!(f, " NULLS LAST")
+        }
+    }
+}
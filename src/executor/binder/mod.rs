@@ -0,0 +1,1479 @@
+use crate::{
+    error::ErrorCode,
+    executor::{
+        context::Context,
+        error::ExecuteError,
+        expression::{
+            AggregateCall, AggregateFunction, BoundBinary, BoundExpression, BoundLiteral,
+            BoundUnary, ColumnRef, FunctionCall, ScalarFunction,
+        },
+        meta::{column::Column, row::SimpleMemoryRow, schema::Schema},
+        types::{
+            blob,
+            datetime::{Date, Interval, Time, Timestamp},
+            decimal::{self, Decimal},
+            flout64::Float64,
+            int32::Int32,
+            int64::Int64,
+            DataType, OwnValue,
+        },
+    },
+    sql::parser::ast::{
+        expression::{
+            BinaryExpression, BinaryOperator, Expression, Function, Placeholder, UnaryExpression,
+            UnaryOperator,
+        },
+        identifier::{Identifier, SingleIdentifier},
+        leaf::WithLocation,
+        literal::{Literal, Value},
+        select::{FromItem, JoinConstraint, JoinType, OrderBy, SelectItem},
+        Select,
+    },
+};
+
+use bound::{BoundFrom, BoundOrderBy, BoundSelect};
+use scope::{ResolveError, Scope};
+
+/// 绑定后的语句
+pub mod bound;
+
+/// 名字解析作用域
+pub mod scope;
+
+/// Binder 语义分析
+/// 使用 Context 中的表信息，将 AST 中的标识符解析为（表，列下标，数据类型），
+/// 并推导每个表达式的数据类型
+/// 参数占位符 ? 按出现顺序绑定为 params 中的值
+pub struct Binder<'a, 's> {
+    context: &'a dyn Context, // 计划借用 context 中的表，SQL 文本和参数只在绑定时使用
+    raw_sql: &'s str,
+    params: &'s [OwnValue],
+}
+
+impl<'a, 's> Binder<'a, 's> {
+    pub fn new(context: &'a dyn Context, raw_sql: &'s str) -> Self {
+        Self {
+            context,
+            raw_sql,
+            params: &[],
+        }
+    }
+
+    /// 设置参数占位符的值
+    pub fn with_params(mut self, params: &'s [OwnValue]) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn bind_select(&self, select: &Select) -> Result<BoundSelect<'a>, ExecuteError> {
+        let mut scope: Scope = Scope::new();
+        let mut from: Vec<BoundFrom<'a>> = Vec::with_capacity(select.from.len());
+        for item in select.from.iter() {
+            from.push(self.bind_from_item(item, &mut scope)?);
+        }
+
+        let wheres: Option<BoundExpression> = match &select.wheres {
+            Some(wheres) => {
+                let bound: BoundExpression = self.bind_predicate(wheres, &scope, "WHERE")?;
+                if bound.contains_aggregate() {
+                    return self.error(
+                        ErrorCode::GroupingError,
+                        "aggregate functions are not allowed in WHERE",
+                        wheres,
+                    );
+                }
+                Some(bound)
+            }
+            None => None,
+        };
+
+        let mut items: Vec<BoundExpression> = vec![];
+        let mut columns: Vec<Column> = vec![];
+        for item in select.items.iter() {
+            match item {
+                SelectItem::Expression(Expression::Identifier(
+                    identifier @ (Identifier::Wildcard(_) | Identifier::WithWildcard(_)),
+                )) => {
+                    for column in self.expand_wildcard(identifier, &scope)? {
+                        let merged: Option<&BoundExpression> = match identifier {
+                            Identifier::Wildcard(_) => scope.merged(column.index),
+                            _ => None,
+                        };
+                        let bound: BoundExpression = merged
+                            .cloned()
+                            .unwrap_or_else(|| BoundExpression::Column(column.clone()));
+                        columns.push(Self::output_column(column.name, &bound));
+                        items.push(bound);
+                    }
+                }
+                SelectItem::Expression(expression) => {
+                    let bound: BoundExpression = self.bind_expression(expression, &scope)?;
+                    let name: Box<str> = match &bound {
+                        BoundExpression::Column(column) => column.name.clone(),
+                        _ => expression.to_string().into_boxed_str(),
+                    };
+                    columns.push(Self::output_column(name, &bound));
+                    items.push(bound);
+                }
+                SelectItem::Alias(alias) => {
+                    let bound: BoundExpression = self.bind_expression(&alias.expression, &scope)?;
+                    let name: Box<str> = self.single_name(&alias.alias)?.value.as_ref().into();
+                    columns.push(Self::output_column(name, &bound));
+                    items.push(bound);
+                }
+            }
+        }
+
+        // GROUP BY 和 HAVING 中无法解析的列名再尝试匹配 SELECT 中的别名
+        for (column, item) in columns.iter().zip(items.iter()) {
+            scope.push_alias(column.name.clone(), item.clone());
+        }
+
+        let mut group_by: Vec<BoundExpression> = Vec::with_capacity(select.group_by.len());
+        for identifier in select.group_by.iter() {
+            let bound: BoundExpression = self.bind_identifier(identifier, &scope)?;
+            if bound.contains_aggregate() {
+                return self.error(
+                    ErrorCode::GroupingError,
+                    "aggregate functions are not allowed in GROUP BY",
+                    identifier,
+                );
+            }
+            group_by.push(bound);
+        }
+
+        let having: Option<BoundExpression> = match &select.having {
+            Some(having) => Some(self.bind_predicate(having, &scope, "HAVING")?),
+            None => None,
+        };
+
+        let mut order_by: Vec<BoundOrderBy> = Vec::with_capacity(select.order_by.len());
+        for item in select.order_by.iter() {
+            order_by.push(self.bind_order_by(item, &items, &columns, &scope)?);
+        }
+
+        let name: Box<str> = match from.first() {
+            Some(first) => first.schema().name.clone(),
+            None => "".into(),
+        };
+        Ok(BoundSelect {
+            from: from.into_boxed_slice(),
+            items: items.into_boxed_slice(),
+            wheres,
+            group_by: group_by.into_boxed_slice(),
+            having,
+            order_by: order_by.into_boxed_slice(),
+            limit: match &select.limit {
+                Some(limit) => self.bind_limit(&limit.expression, "LIMIT")?,
+                None => None,
+            },
+            offset: match &select.offset {
+                Some(offset) => self.bind_limit(&offset.expression, "OFFSET")?,
+                None => None,
+            },
+            schema: Schema {
+                name,
+                columns: columns.into_boxed_slice(),
+            },
+        })
+    }
+
+    /// 绑定 FROM 子句中的一项及其后依次连接的表，并把其中的表加入 scope
+    /// 连接条件只能引用本项中已经出现的表，使用下标从本项开始计数的 local 作用域绑定
+    fn bind_from_item(
+        &self,
+        item: &FromItem,
+        scope: &mut Scope,
+    ) -> Result<BoundFrom<'a>, ExecuteError> {
+        let offset: usize = scope.width();
+        let mut local: Scope = Scope::new();
+        let mut bound: BoundFrom<'a> = self.bind_from(item)?;
+        self.push_from(&bound, item, scope, &mut local)?;
+        for join in item.joins.iter() {
+            let right: BoundFrom<'a> = self.bind_from(&join.item)?;
+            let left_width: usize = local.width();
+            let right_width: usize = right.schema().columns.len();
+
+            // USING 的左侧列在右表加入作用域之前解析，避免与右表的同名列产生歧义
+            let using: Option<Vec<(ColumnRef, ColumnRef)>> = match &join.constraint {
+                Some(JoinConstraint::Using(columns)) => {
+                    Some(self.bind_using(columns, &local, &right, left_width)?)
+                }
+                _ => None,
+            };
+            self.push_from(&right, &join.item, scope, &mut local)?;
+
+            let condition: Option<BoundExpression> = match &join.constraint {
+                Some(JoinConstraint::On(expression)) => {
+                    let bound: BoundExpression = self.bind_predicate(expression, &local, "JOIN")?;
+                    if bound.contains_aggregate() {
+                        return self.error(
+                            ErrorCode::GroupingError,
+                            "aggregate functions are not allowed in JOIN conditions",
+                            expression,
+                        );
+                    }
+                    Some(bound)
+                }
+                Some(JoinConstraint::Using(columns)) => {
+                    let mut conjuncts: Vec<BoundExpression> = vec![];
+                    for ((left, right), column) in using.unwrap().into_iter().zip(columns.iter()) {
+                        // 不带表名引用时，RIGHT JOIN 取右表的列，其余取左表的列
+                        let hidden: usize = match join.join_type {
+                            JoinType::Right => left.index,
+                            _ => right.index,
+                        };
+                        local.hide(hidden);
+                        scope.hide(offset + hidden);
+                        // 左侧的列可能已经在之前的 FULL JOIN 中合并
+                        let index: usize = left.index;
+                        let left: BoundExpression = local
+                            .merged(left.index)
+                            .cloned()
+                            .unwrap_or(BoundExpression::Column(left));
+                        // FULL JOIN 两侧都可能补 NULL，留下的列取两侧列的 COALESCE
+                        if join.join_type == JoinType::Full {
+                            let merged: BoundExpression = Self::merge_using(&left, &right);
+                            scope.merge(
+                                offset + index,
+                                merged.map_columns(&|c| ColumnRef {
+                                    index: offset + c.index,
+                                    ..c.clone()
+                                }),
+                            );
+                            local.merge(index, merged);
+                        }
+                        conjuncts.push(BoundExpression::Binary(BoundBinary {
+                            left: Box::new(left),
+                            operator: BinaryOperator::Equal(column.location().clone()),
+                            right: Box::new(BoundExpression::Column(right)),
+                            data_type: DataType::Boolean,
+                        }));
+                    }
+                    BoundExpression::conjunction(conjuncts)
+                }
+                None if join.join_type == JoinType::Cross => None,
+                None => {
+                    return self.error(
+                        ErrorCode::SyntaxError,
+                        format!("{} JOIN requires a condition", join.join_type),
+                        join,
+                    )
+                }
+            };
+
+            if join.join_type.keeps_right() {
+                local.make_nullable(0..left_width);
+                scope.make_nullable(offset..offset + left_width);
+            }
+            if join.join_type.keeps_left() {
+                local.make_nullable(left_width..left_width + right_width);
+                scope.make_nullable(offset + left_width..offset + left_width + right_width);
+            }
+            bound = BoundFrom::join(bound, right, join.join_type, condition);
+        }
+        Ok(bound)
+    }
+
+    /// FULL JOIN ... USING 中合并后的列，两侧都可能为 NULL
+    fn merge_using(left: &BoundExpression, right: &ColumnRef) -> BoundExpression {
+        let right: ColumnRef = ColumnRef {
+            nullable: true,
+            ..right.clone()
+        };
+        let left: BoundExpression = left.map_columns(&|c| ColumnRef {
+            nullable: true,
+            ..c.clone()
+        });
+        let data_type: DataType = Self::common_type(&[left.data_type(), &right.data_type])
+            .unwrap_or_else(|| left.data_type().clone());
+        BoundExpression::Function(FunctionCall {
+            function: ScalarFunction::Coalesce,
+            arguments: Box::new([left, BoundExpression::Column(right)]),
+            data_type,
+        })
+    }
+
+    /// 把 FROM 子句中的一项加入作用域，同一个 SELECT 中的表名不能重复
+    fn push_from(
+        &self,
+        bound: &BoundFrom<'a>,
+        item: &FromItem,
+        scope: &mut Scope,
+        local: &mut Scope,
+    ) -> Result<(), ExecuteError> {
+        if scope.contains_table(&bound.schema().name) {
+            return self.error(
+                ErrorCode::DuplicateObject,
+                format!("duplicate table name {}", bound.schema().name),
+                item,
+            );
+        }
+        scope.push_table(bound.namespace(), bound.schema());
+        local.push_table(bound.namespace(), bound.schema());
+        Ok(())
+    }
+
+    /// 解析 USING 中的每一列在左侧和右表中的列引用，右表的列下标从 left_width 开始
+    fn bind_using(
+        &self,
+        columns: &[SingleIdentifier],
+        left: &Scope,
+        right: &BoundFrom<'a>,
+        left_width: usize,
+    ) -> Result<Vec<(ColumnRef, ColumnRef)>, ExecuteError> {
+        let mut pairs: Vec<(ColumnRef, ColumnRef)> = Vec::with_capacity(columns.len());
+        for (position, column) in columns.iter().enumerate() {
+            if columns[..position].iter().any(|c| c.value == column.value) {
+                return self.error(
+                    ErrorCode::DuplicateColumn,
+                    format!("column {} appears more than once in USING", column.value),
+                    column,
+                );
+            }
+            let left_ref: ColumnRef = match left.resolve(None, &column.value) {
+                Ok(column) => column,
+                Err(ResolveError::Ambiguous) => {
+                    return self.error(
+                        ErrorCode::AmbiguousColumn,
+                        format!("ambiguous column {} in USING", column.value),
+                        column,
+                    )
+                }
+                Err(_) => {
+                    return self.error(
+                        ErrorCode::UndefinedColumn,
+                        format!("column {} in USING not found in left table", column.value),
+                        column,
+                    )
+                }
+            };
+            let schema: &Schema = right.schema();
+            let Some(index) = schema.column_index(&column.value) else {
+                return self.error(
+                    ErrorCode::UndefinedColumn,
+                    format!("column {} in USING not found in right table", column.value),
+                    column,
+                );
+            };
+            let right_column: &Column = &schema.columns[index];
+            let right_ref: ColumnRef = ColumnRef {
+                table: schema.name.clone(),
+                name: right_column.name.clone(),
+                index: left_width + index,
+                data_type: right_column.data_type.clone(),
+                nullable: right_column.nullable,
+            };
+            let (l, r) = (&left_ref.data_type, &right_ref.data_type);
+            if !Self::comparable(l, r) {
+                return self.error(
+                    ErrorCode::DatatypeMismatch,
+                    format!(
+                        "invalid operand types {} = {} for USING column {}",
+                        l, r, column.value
+                    ),
+                    column,
+                );
+            }
+            pairs.push((left_ref, right_ref));
+        }
+        Ok(pairs)
+    }
+
+    /// 绑定 FROM 子句中的一项，表、namespace.table 或者带别名的子查询
+    fn bind_from(&self, item: &FromItem) -> Result<BoundFrom<'a>, ExecuteError> {
+        let alias: Option<&SingleIdentifier> = match &item.alias {
+            Some(alias) => Some(self.single_name(alias)?),
+            None => None,
+        };
+        let (namespace, ident): (&str, &SingleIdentifier) = match &item.expression {
+            Expression::Identifier(Identifier::Single(ident)) => {
+                (self.context.current_namespace(), ident)
+            }
+            Expression::Identifier(Identifier::Combined(identifiers)) if identifiers.len() == 2 => {
+                (&identifiers[0].value, &identifiers[1])
+            }
+            Expression::SubQuery(select) => {
+                return match alias {
+                    Some(alias) => {
+                        let select: BoundSelect<'a> = self.bind_select(select)?;
+                        let schema: Schema = Schema {
+                            name: alias.value.as_ref().into(),
+                            columns: select.schema.columns.clone(),
+                        };
+                        Ok(BoundFrom::SubQuery {
+                            select: Box::new(select),
+                            schema,
+                        })
+                    }
+                    None => self.error(
+                        ErrorCode::SyntaxError,
+                        "subquery in FROM must have an alias",
+                        item,
+                    ),
+                }
+            }
+            _ => {
+                return self.error(
+                    ErrorCode::SyntaxError,
+                    format!("invalid FROM item {}", item.expression),
+                    item,
+                )
+            }
+        };
+        match self.context.find_table_in(namespace, &ident.value) {
+            Some(data_source) => {
+                let mut schema: Schema = data_source.schema().clone();
+                let namespace: Option<Box<str>> = match alias {
+                    Some(alias) => {
+                        schema.name = alias.value.as_ref().into();
+                        None
+                    }
+                    None => Some(namespace.into()),
+                };
+                Ok(BoundFrom::Table {
+                    data_source,
+                    schema,
+                    namespace,
+                })
+            }
+            None => self.error(
+                ErrorCode::UndefinedTable,
+                format!("table {} not found", item.expression),
+                &item.expression,
+            ),
+        }
+    }
+
+    /// ORDER BY 优先匹配 SELECT 中的别名，其次是输入的列
+    fn bind_order_by(
+        &self,
+        order_by: &OrderBy,
+        items: &[BoundExpression],
+        columns: &[Column],
+        scope: &Scope,
+    ) -> Result<BoundOrderBy, ExecuteError> {
+        let nulls_first: bool = order_by.nulls_first.unwrap_or(!order_by.asc);
+        if let Identifier::Single(ident) = &order_by.identifier {
+            let matched: Vec<&BoundExpression> = columns
+                .iter()
+                .zip(items.iter())
+                .filter(|(column, _)| column.name.as_ref() == ident.value.as_ref())
+                .map(|(_, item)| item)
+                .collect();
+            if let [first, rest @ ..] = matched.as_slice() {
+                if rest.iter().any(|item| item != first) {
+                    return self.error(
+                        ErrorCode::AmbiguousColumn,
+                        format!("ambiguous column {}", ident.value),
+                        ident,
+                    );
+                }
+                return Ok(BoundOrderBy {
+                    expression: (*first).clone(),
+                    asc: order_by.asc,
+                    nulls_first,
+                });
+            }
+        }
+        Ok(BoundOrderBy {
+            expression: self.bind_identifier(&order_by.identifier, scope)?,
+            asc: order_by.asc,
+            nulls_first,
+        })
+    }
+
+    /// 绑定 WHERE、HAVING、JOIN ON 的条件，条件的类型必须是 Boolean
+    pub fn bind_predicate(
+        &self,
+        expression: &Expression,
+        scope: &Scope,
+        clause: &str,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let bound: BoundExpression = self.bind_expression(expression, scope)?;
+        if !Self::logical(bound.data_type()) {
+            return self.error(
+                ErrorCode::DatatypeMismatch,
+                format!(
+                    "argument of {} must be type Boolean, not type {}",
+                    clause,
+                    bound.data_type()
+                ),
+                expression,
+            );
+        }
+        Ok(bound)
+    }
+
+    pub fn bind_expression(
+        &self,
+        expression: &Expression,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        match expression {
+            Expression::Literal(literal) => self.bind_literal(literal),
+            Expression::Identifier(identifier) => self.bind_identifier(identifier, scope),
+            Expression::BinaryExpression(binary) => self.bind_binary(binary, scope),
+            Expression::UnaryExpression(unary) => self.bind_unary(unary, scope),
+            Expression::Function(function) => self.bind_function(function, scope),
+            Expression::SubQuery(select) => self.error(
+                ErrorCode::FeatureNotSupported,
+                "unsupported subquery expression",
+                select.as_ref(),
+            ),
+            Expression::Placeholder(placeholder) => self.bind_placeholder(placeholder),
+        }
+    }
+
+    /// 绑定函数调用，支持聚合函数 COUNT、SUM、AVG、MIN、MAX
+    /// 和标量函数 COALESCE、NULLIF、IFNULL
+    fn bind_function(
+        &self,
+        function: &Function,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let name: Option<&str> = match &function.name {
+            Identifier::Single(name) => Some(&name.value),
+            _ => None,
+        };
+        if let Some(scalar) = name.and_then(ScalarFunction::from_name) {
+            return self.bind_scalar_function(scalar, function, scope);
+        }
+        let Some(aggregate) = name.and_then(AggregateFunction::from_name) else {
+            return self.error(
+                ErrorCode::UndefinedFunction,
+                format!("unknown function {}", function.name),
+                function,
+            );
+        };
+        let [argument] = function.args.as_ref() else {
+            return self.error(
+                ErrorCode::UndefinedFunction,
+                format!("function {} takes exactly one argument", function.name),
+                function,
+            );
+        };
+
+        if let Expression::Identifier(Identifier::Wildcard(_)) = argument {
+            if aggregate != AggregateFunction::Count {
+                return self.error(
+                    ErrorCode::DatatypeMismatch,
+                    format!("invalid argument * for {}", function.name),
+                    function,
+                );
+            }
+            return Ok(BoundExpression::Aggregate(AggregateCall {
+                function: aggregate,
+                argument: None,
+                data_type: DataType::Int64,
+            }));
+        }
+
+        let argument: BoundExpression = self.bind_expression(argument, scope)?;
+        if argument.contains_aggregate() {
+            return self.error(
+                ErrorCode::GroupingError,
+                "aggregate function calls cannot be nested",
+                function,
+            );
+        }
+        let data_type: DataType = match aggregate {
+            AggregateFunction::Count => DataType::Int64,
+            // Int32 的和按 Int64 累加，Decimal 的和可能超出参数的精度，取最大精度
+            AggregateFunction::Sum if argument.data_type().is_numeric() => {
+                match argument.data_type() {
+                    DataType::Int32 => DataType::Int64,
+                    DataType::Decimal(_, scale) => {
+                        DataType::Decimal(decimal::MAX_PRECISION, *scale)
+                    }
+                    t => t.clone(),
+                }
+            }
+            AggregateFunction::Sum if argument.data_type().is_null() => DataType::Null,
+            AggregateFunction::Avg
+                if argument.data_type().is_numeric() || argument.data_type().is_null() =>
+            {
+                DataType::Float64
+            }
+            AggregateFunction::Min | AggregateFunction::Max => match argument.data_type() {
+                t if Self::comparable(t, t) => t.clone(),
+                t => {
+                    return self.error(
+                        ErrorCode::DatatypeMismatch,
+                        format!("invalid argument type {:?} for {}", t, function.name),
+                        function,
+                    )
+                }
+            },
+            _ => {
+                return self.error(
+                    ErrorCode::DatatypeMismatch,
+                    format!(
+                        "invalid argument type {:?} for {}",
+                        argument.data_type(),
+                        function.name
+                    ),
+                    function,
+                )
+            }
+        };
+        Ok(BoundExpression::Aggregate(AggregateCall {
+            function: aggregate,
+            argument: Some(Box::new(argument)),
+            data_type,
+        }))
+    }
+
+    /// 绑定 COALESCE、NULLIF、IFNULL，参数的类型必须兼容
+    /// COALESCE、IFNULL 的类型是参数的公共类型，NULLIF 的类型是第一个参数的类型
+    fn bind_scalar_function(
+        &self,
+        scalar: ScalarFunction,
+        function: &Function,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let arity_error: Option<&str> = match (scalar, function.args.len()) {
+            (ScalarFunction::Coalesce, 0) => Some("at least one argument"),
+            (ScalarFunction::NullIf | ScalarFunction::IfNull, n) if n != 2 => {
+                Some("exactly two arguments")
+            }
+            _ => None,
+        };
+        if let Some(arity) = arity_error {
+            return self.error(
+                ErrorCode::UndefinedFunction,
+                format!("function {} takes {}", function.name, arity),
+                function,
+            );
+        }
+        let arguments: Box<[BoundExpression]> = function
+            .args
+            .iter()
+            .map(|argument| self.bind_expression(argument, scope))
+            .collect::<Result<_, _>>()?;
+        let types: Vec<&DataType> = arguments.iter().map(|a| a.data_type()).collect();
+        let Some(common) = Self::common_type(&types) else {
+            let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            return self.error(
+                ErrorCode::DatatypeMismatch,
+                format!(
+                    "incompatible argument types {} for {}",
+                    types.join(", "),
+                    function.name
+                ),
+                function,
+            );
+        };
+        let data_type: DataType = match scalar {
+            ScalarFunction::NullIf => arguments[0].data_type().clone(),
+            ScalarFunction::Coalesce | ScalarFunction::IfNull => common,
+        };
+        Ok(BoundExpression::Function(FunctionCall {
+            function: scalar,
+            arguments,
+            data_type,
+        }))
+    }
+
+    /// 多个值的公共类型，NULL 可以作为任何类型
+    /// 数值类型按 Int32 < Int64 < Decimal < Float64 提升，字符串类型不同时为 String，类型不兼容时返回 None
+    fn common_type(types: &[&DataType]) -> Option<DataType> {
+        let mut common: DataType = DataType::Null;
+        for t in types.iter().filter(|t| !t.is_null()) {
+            common = match (&common, *t) {
+                (DataType::Null, t) => t.clone(),
+                (c, t) if c == t => t.clone(),
+                (c, t) if c.is_numeric() && t.is_numeric() => {
+                    Self::numeric_type(c, t, |l, r| l.max(r))
+                }
+                (c, t) if c.is_string() && t.is_string() => DataType::String,
+                _ => return None,
+            };
+        }
+        Some(common)
+    }
+
+    /// 两个数值类型提升后的类型，结果为 Decimal 时由 scale 计算小数位数
+    fn numeric_type(l: &DataType, r: &DataType, scale: fn(u8, u8) -> u8) -> DataType {
+        let rank = |t: &DataType| t.numeric_rank().unwrap_or(0);
+        match if rank(l) >= rank(r) { l } else { r } {
+            DataType::Decimal(_, _) => {
+                DataType::Decimal(decimal::MAX_PRECISION, scale(l.scale(), r.scale()))
+            }
+            t => t.clone(),
+        }
+    }
+
+    /// 两个类型的值能否比较，NULL 可以和任何类型比较
+    /// 数值之间、字符串之间可以比较，其余类型只能和相同的类型比较
+    fn comparable(l: &DataType, r: &DataType) -> bool {
+        l.is_null()
+            || r.is_null()
+            || l.is_numeric() && r.is_numeric()
+            || l.is_string() && r.is_string()
+            || l == r
+    }
+
+    fn bind_placeholder(&self, placeholder: &Placeholder) -> Result<BoundExpression, ExecuteError> {
+        let Some(value) = self.params.get(placeholder.index) else {
+            return self.error(
+                ErrorCode::UndefinedParameter,
+                format!("missing value for parameter {}", placeholder.index + 1),
+                placeholder,
+            );
+        };
+        Ok(BoundExpression::Literal(BoundLiteral {
+            value: value.clone(),
+            data_type: value.data_type(),
+        }))
+    }
+
+    /// 绑定并计算 LIMIT、OFFSET 的常量表达式，NULL 表示不限制
+    fn bind_limit(
+        &self,
+        expression: &Expression,
+        clause: &str,
+    ) -> Result<Option<u64>, ExecuteError> {
+        let bound: BoundExpression = self.bind_expression(expression, &Scope::new())?;
+        let value: OwnValue = match bound.evaluate(&SimpleMemoryRow::new(vec![])) {
+            Ok(value) => value,
+            Err(error) => return Err(error.at(expression, self.raw_sql)),
+        };
+        match value.to_int64() {
+            Some(v) if v.value() >= 0 => Ok(Some(v.value() as u64)),
+            Some(_) => self.error(
+                ErrorCode::InvalidParameterValue,
+                format!("{} must not be negative", clause),
+                expression,
+            ),
+            None if value == OwnValue::Null => Ok(None),
+            None => self.error(
+                ErrorCode::DatatypeMismatch,
+                format!("{} must be an integer", clause),
+                expression,
+            ),
+        }
+    }
+
+    /// 带类型名的字面量在这里解析，格式不对时报告字面量的位置
+    fn bind_literal(&self, literal: &Literal) -> Result<BoundExpression, ExecuteError> {
+        let parsed: Result<OwnValue, ExecuteError> = match &literal.value {
+            Value::String(s) => Ok(OwnValue::String(s.to_string())),
+            Value::Integer(n) => match (i32::try_from(*n), i64::try_from(*n)) {
+                (Ok(n), _) => Ok(OwnValue::Int32(Int32::new(n))),
+                (_, Ok(n)) => Ok(OwnValue::Int64(Int64::new(n))),
+                _ => Err(ExecuteError::new(
+                    ErrorCode::NumericValueOutOfRange,
+                    format!("integer {} out of range", n),
+                )),
+            },
+            Value::Float(n) => Ok(OwnValue::Float64(Float64::new(*n))),
+            Value::Boolean(b) => Ok(OwnValue::Boolean(*b)),
+            Value::Decimal(s) => Decimal::parse(s).map(OwnValue::Decimal),
+            Value::Date(s) => Date::parse(s).map(OwnValue::Date),
+            Value::Time(s) => Time::parse(s).map(OwnValue::Time),
+            Value::Timestamp(s) => Timestamp::parse(s).map(OwnValue::Timestamp),
+            Value::Interval(s) => Interval::parse(s).map(OwnValue::Interval),
+            Value::Blob(s) => blob::parse_hex(s).map(OwnValue::Blob),
+            Value::Null => Ok(OwnValue::Null),
+        };
+        let value: OwnValue = parsed.map_err(|error| error.at(literal, self.raw_sql))?;
+        Ok(BoundExpression::Literal(BoundLiteral {
+            data_type: value.data_type(),
+            value,
+        }))
+    }
+
+    /// 绑定列引用 col 或 tab.col
+    fn bind_identifier(
+        &self,
+        identifier: &Identifier,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let (namespace, table, column): (
+            Option<&SingleIdentifier>,
+            Option<&SingleIdentifier>,
+            &SingleIdentifier,
+        ) = match identifier {
+            Identifier::Single(column) => (None, None, column),
+            Identifier::Combined(identifiers) if identifiers.len() == 2 => {
+                (None, Some(&identifiers[0]), &identifiers[1])
+            }
+            Identifier::Combined(identifiers) if identifiers.len() == 3 => (
+                Some(&identifiers[0]),
+                Some(&identifiers[1]),
+                &identifiers[2],
+            ),
+            Identifier::Combined(_) => {
+                return self.error(
+                    ErrorCode::SyntaxError,
+                    format!("invalid column name {}", identifier),
+                    identifier,
+                )
+            }
+            Identifier::WithWildcard(_) | Identifier::Wildcard(_) => {
+                return self.error(
+                    ErrorCode::SyntaxError,
+                    format!("unexpected wildcard {}", identifier),
+                    identifier,
+                )
+            }
+        };
+        // 列名无法解析时，再尝试匹配 SELECT 中的别名
+        let resolved: Result<BoundExpression, ResolveError> = match scope.resolve_in(
+            namespace.map(|n| n.value.as_ref()),
+            table.map(|t| t.value.as_ref()),
+            &column.value,
+        ) {
+            Err(ResolveError::UnknownColumn) if table.is_none() => {
+                scope.alias(&column.value).cloned()
+            }
+            Ok(column) if table.is_none() => Ok(scope
+                .merged(column.index)
+                .cloned()
+                .unwrap_or(BoundExpression::Column(column))),
+            resolved => resolved.map(BoundExpression::Column),
+        };
+        match resolved {
+            Ok(expression) => Ok(expression),
+            Err(ResolveError::UnknownTable) => {
+                let table: &SingleIdentifier = table.unwrap();
+                match namespace {
+                    Some(namespace) => self.error(
+                        ErrorCode::UndefinedTable,
+                        format!("unknown table {}.{}", namespace.value, table.value),
+                        namespace,
+                    ),
+                    None => self.error(
+                        ErrorCode::UndefinedTable,
+                        format!("unknown table {}", table.value),
+                        table,
+                    ),
+                }
+            }
+            Err(ResolveError::UnknownColumn) => self.error(
+                ErrorCode::UndefinedColumn,
+                format!("unknown column {}", identifier),
+                identifier,
+            ),
+            Err(ResolveError::Ambiguous) => self.error(
+                ErrorCode::AmbiguousColumn,
+                format!("ambiguous column {}", identifier),
+                identifier,
+            ),
+        }
+    }
+
+    /// 展开 * 和 tab.*
+    fn expand_wildcard(
+        &self,
+        identifier: &Identifier,
+        scope: &Scope,
+    ) -> Result<Vec<ColumnRef>, ExecuteError> {
+        let (namespace, table): (Option<&SingleIdentifier>, Option<&SingleIdentifier>) =
+            match identifier {
+                Identifier::WithWildcard(identifiers) if identifiers.len() == 1 => {
+                    (None, Some(&identifiers[0]))
+                }
+                Identifier::WithWildcard(identifiers) if identifiers.len() == 2 => {
+                    (Some(&identifiers[0]), Some(&identifiers[1]))
+                }
+                Identifier::WithWildcard(_) => {
+                    return self.error(
+                        ErrorCode::SyntaxError,
+                        format!("invalid wildcard {}", identifier),
+                        identifier,
+                    )
+                }
+                _ => (None, None),
+            };
+        match scope.expand_in(
+            namespace.map(|n| n.value.as_ref()),
+            table.map(|t| t.value.as_ref()),
+        ) {
+            Ok(columns) if columns.is_empty() => self.error(
+                ErrorCode::SyntaxError,
+                "wildcard without FROM clause",
+                identifier,
+            ),
+            Ok(columns) => Ok(columns),
+            Err(_) => self.error(
+                ErrorCode::UndefinedTable,
+                format!("unknown table {}", identifier),
+                identifier,
+            ),
+        }
+    }
+
+    fn bind_binary(
+        &self,
+        binary: &BinaryExpression,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let left: BoundExpression = self.bind_expression(&binary.left, scope)?;
+        let right: BoundExpression = self.bind_expression(&binary.right, scope)?;
+        let (l, r) = (left.data_type(), right.data_type());
+        let data_type: DataType = match &binary.operator {
+            BinaryOperator::Plus(_)
+            | BinaryOperator::Minus(_)
+            | BinaryOperator::Multiply(_)
+            | BinaryOperator::Divide(_)
+            | BinaryOperator::Modulo(_) => {
+                if !(l.is_numeric() || l.is_null()) || !(r.is_numeric() || r.is_null()) {
+                    return self.operand_error(&binary.operator, l, r);
+                }
+                let scale: fn(u8, u8) -> u8 = match &binary.operator {
+                    BinaryOperator::Multiply(_) => Decimal::mul_scale,
+                    BinaryOperator::Divide(_) => Decimal::div_scale,
+                    _ => Decimal::add_scale,
+                };
+                match (l.is_null(), r.is_null()) {
+                    (true, true) => DataType::Null,
+                    (true, false) => r.clone(),
+                    (false, true) => l.clone(),
+                    (false, false) => Self::numeric_type(l, r, scale),
+                }
+            }
+            BinaryOperator::Equal(_)
+            | BinaryOperator::NotEqual(_)
+            | BinaryOperator::GreaterThan(_)
+            | BinaryOperator::LessThan(_)
+            | BinaryOperator::GreaterThanOrEqual(_)
+            | BinaryOperator::LessThanOrEqual(_) => {
+                if !Self::comparable(l, r) {
+                    return self.operand_error(&binary.operator, l, r);
+                }
+                DataType::Boolean
+            }
+            BinaryOperator::AND(_) | BinaryOperator::OR(_) => {
+                if !Self::logical(l) || !Self::logical(r) {
+                    return self.operand_error(&binary.operator, l, r);
+                }
+                DataType::Boolean
+            }
+        };
+        Ok(BoundExpression::Binary(BoundBinary {
+            left: Box::new(left),
+            operator: binary.operator.clone(),
+            right: Box::new(right),
+            data_type,
+        }))
+    }
+
+    fn bind_unary(
+        &self,
+        unary: &UnaryExpression,
+        scope: &Scope,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let expression: BoundExpression = self.bind_expression(&unary.expression, scope)?;
+        let operand: &DataType = expression.data_type();
+        let data_type: Option<DataType> = match &unary.operator {
+            UnaryOperator::Plus(_) | UnaryOperator::Minus(_)
+                if operand.is_numeric() || operand.is_null() =>
+            {
+                Some(operand.clone())
+            }
+            UnaryOperator::NOT(_) if Self::logical(operand) => Some(DataType::Boolean),
+            UnaryOperator::IsNull(_) | UnaryOperator::IsNotNull(_) => Some(DataType::Boolean),
+            _ => None,
+        };
+        let Some(data_type) = data_type else {
+            return self.error(
+                ErrorCode::DatatypeMismatch,
+                format!("invalid operand type {} for {}", operand, unary.operator),
+                unary,
+            );
+        };
+        Ok(BoundExpression::Unary(BoundUnary {
+            operator: unary.operator.clone(),
+            expression: Box::new(expression),
+            data_type,
+        }))
+    }
+
+    /// 能否作为 AND、OR、NOT 的操作数或者 WHERE 等子句的条件
+    fn logical(t: &DataType) -> bool {
+        matches!(t, DataType::Boolean | DataType::Null)
+    }
+
+    fn output_column(name: Box<str>, bound: &BoundExpression) -> Column {
+        Column {
+            name,
+            data_type: bound.data_type().clone(),
+            nullable: bound.nullable(),
+            default: None,
+        }
+    }
+
+    /// 别名只能是单个标识符
+    fn single_name<'i>(
+        &self,
+        identifier: &'i Identifier,
+    ) -> Result<&'i SingleIdentifier, ExecuteError> {
+        match identifier {
+            Identifier::Single(ident) => Ok(ident),
+            _ => self.error(
+                ErrorCode::SyntaxError,
+                format!("invalid alias {}", identifier),
+                identifier,
+            ),
+        }
+    }
+
+    fn operand_error<T>(
+        &self,
+        operator: &BinaryOperator,
+        left: &DataType,
+        right: &DataType,
+    ) -> Result<T, ExecuteError> {
+        self.error(
+            ErrorCode::DatatypeMismatch,
+            format!("invalid operand types {} {} {}", left, operator, right),
+            operator,
+        )
+    }
+
+    fn error<T, Msg: Into<String>>(
+        &self,
+        code: ErrorCode,
+        message: Msg,
+        node: &dyn WithLocation,
+    ) -> Result<T, ExecuteError> {
+        Err(ExecuteError::located(code, message, node, self.raw_sql))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        executor::{context::SimpleMemoryContext, meta::data_source::SimpleMemoryDataSource},
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn table(name: &str, columns: &[(&str, DataType)]) -> SimpleMemoryDataSource {
+        SimpleMemoryDataSource::new(Schema {
+            name: name.into(),
+            columns: columns
+                .iter()
+                .map(|(name, data_type)| Column {
+                    name: (*name).into(),
+                    data_type: data_type.clone(),
+                    nullable: true,
+                    default: None,
+                })
+                .collect(),
+        })
+    }
+
+    fn context() -> SimpleMemoryContext {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        context.add_table(Box::new(table(
+            "stu",
+            &[("id", DataType::Int32), ("name", DataType::Varchar(32))],
+        )));
+        context.add_table(Box::new(table(
+            "score",
+            &[("id", DataType::Int32), ("value", DataType::Float64)],
+        )));
+        context
+    }
+
+    fn bind<'a>(
+        context: &'a SimpleMemoryContext,
+        sql: &'a str,
+    ) -> Result<BoundSelect<'a>, ExecuteError> {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        match &statements.statements[0] {
+            Statement::Select(select) => Binder::new(context, sql).bind_select(select),
+            _ => panic!("expect select"),
+        }
+    }
+
+    fn names(bound: &BoundSelect) -> Vec<String> {
+        bound.items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn bind_columns() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(&context, "SELECT name, id FROM stu").unwrap();
+        assert_eq!(names(&bound), vec!["stu.name", "stu.id"]);
+        match &bound.items[0] {
+            BoundExpression::Column(column) => {
+                assert_eq!(column.index, 1);
+                assert_eq!(column.data_type, DataType::Varchar(32));
+            }
+            _ => panic!("expect column"),
+        }
+        assert_eq!(
+            format!("{:?}", bound.schema),
+            "stu(name Varchar(32) NULLABLE, id Int32 NULLABLE)"
+        );
+    }
+
+    #[test]
+    fn bind_wildcard() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(&context, "SELECT * FROM stu, score").unwrap();
+        assert_eq!(
+            names(&bound),
+            vec!["stu.id", "stu.name", "score.id", "score.value"]
+        );
+        match &bound.items[3] {
+            BoundExpression::Column(column) => assert_eq!(column.index, 3),
+            _ => panic!("expect column"),
+        }
+    }
+
+    #[test]
+    fn bind_table_wildcard() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(&context, "SELECT s.*, stu.id FROM score AS s, stu").unwrap();
+        assert_eq!(names(&bound), vec!["s.id", "s.value", "stu.id"]);
+    }
+
+    #[test]
+    fn bind_qualified() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(&context, "SELECT score.id, name FROM stu, score").unwrap();
+        assert_eq!(names(&bound), vec!["score.id", "stu.name"]);
+        match &bound.items[0] {
+            BoundExpression::Column(column) => assert_eq!(column.index, 2),
+            _ => panic!("expect column"),
+        }
+    }
+
+    #[test]
+    fn bind_expression_type() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect =
+            bind(&context, "SELECT id + value AS v, id * 2 FROM score").unwrap();
+        assert_eq!(
+            names(&bound),
+            vec!["(score.id + score.value)", "(score.id * 2)"]
+        );
+        assert_eq!(
+            format!("{:?}", bound.schema),
+            "score(v Float64 NULLABLE, (id * 2) Int32 NULLABLE)"
+        );
+    }
+
+    #[test]
+    fn bind_subquery() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect =
+            bind(&context, "SELECT t.a FROM (SELECT name AS a FROM stu) AS t").unwrap();
+        assert_eq!(names(&bound), vec!["t.a"]);
+        match &bound.from[0] {
+            BoundFrom::SubQuery { select, schema } => {
+                assert_eq!(names(select), vec!["stu.name"]);
+                assert_eq!(schema.name.as_ref(), "t");
+            }
+            _ => panic!("expect subquery"),
+        }
+    }
+
+    #[test]
+    fn bind_order_by_alias() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect =
+            bind(&context, "SELECT id * 2 AS d FROM stu ORDER BY d DESC").unwrap();
+        assert_eq!(bound.order_by[0].expression.to_string(), "(stu.id * 2)");
+        assert!(!bound.order_by[0].asc);
+    }
+
+    #[test]
+    fn bind_ambiguous() {
+        let context: SimpleMemoryContext = context();
+        let error: ExecuteError = bind(&context, "SELECT id FROM stu, score").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "ambiguous column id in Ln 1, Col 8 near \"SELECT id FROM stu, score\""
+        );
+    }
+
+    #[test]
+    fn bind_unknown_column() {
+        let context: SimpleMemoryContext = context();
+        let error: ExecuteError = bind(&context, "SELECT stu.value FROM stu, score").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown column stu.value in Ln 1, Col 8 near \"SELECT stu.value FROM stu, score\""
+        );
+    }
+
+    #[test]
+    fn bind_unknown_table() {
+        let context: SimpleMemoryContext = context();
+        let error: ExecuteError = bind(&context, "SELECT s.id FROM stu").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unknown table s in Ln 1, Col 8"));
+        let error: ExecuteError = bind(&context, "SELECT s.* FROM stu").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unknown table s.* in Ln 1, Col 8"));
+        let error: ExecuteError = bind(&context, "SELECT id FROM teacher").unwrap_err();
+        assert!(error.to_string().starts_with("table teacher not found"));
+    }
+
+    #[test]
+    fn bind_namespace() {
+        let mut context: SimpleMemoryContext = context();
+        context.create_namespace("db").unwrap();
+        context.set_current_namespace("db").unwrap();
+        context.add_table(Box::new(table("tea", &[("id", DataType::Int32)])));
+        context.set_current_namespace("main").unwrap();
+
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT main.stu.name, db.tea.id, tea.id FROM stu, db.tea",
+        )
+        .unwrap();
+        assert_eq!(names(&bound), vec!["stu.name", "tea.id", "tea.id"]);
+        let bound: BoundSelect = bind(&context, "SELECT db.tea.* FROM db.tea").unwrap();
+        assert_eq!(names(&bound), vec!["tea.id"]);
+
+        let error: ExecuteError = bind(&context, "SELECT id FROM tea").unwrap_err();
+        assert!(error.to_string().starts_with("table tea not found"));
+        let error: ExecuteError = bind(&context, "SELECT id FROM other.tea").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("table other.tea not found in Ln 1, Col 16"));
+        let error: ExecuteError = bind(&context, "SELECT db.stu.id FROM stu").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unknown table db.stu in Ln 1, Col 8"));
+        // 使用别名后不能再用命名空间限定
+        let error: ExecuteError = bind(&context, "SELECT db.t.id FROM db.tea AS t").unwrap_err();
+        assert!(error.to_string().starts_with("unknown table db.t"));
+    }
+
+    #[test]
+    fn bind_duplicate_table() {
+        let context: SimpleMemoryContext = context();
+        assert!(bind(&context, "SELECT stu.id FROM stu, stu").is_err());
+        assert!(bind(&context, "SELECT s.id FROM stu AS s, score AS s").is_err());
+        assert!(bind(&context, "SELECT a.id, b.id FROM stu AS a, stu AS b").is_ok());
+    }
+
+    #[test]
+    fn bind_type_mismatch() {
+        let context: SimpleMemoryContext = context();
+        let error: ExecuteError = bind(&context, "SELECT name + 1 FROM stu").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid operand types Varchar(32) + Int32 in Ln 1, Col 13"));
+    }
+
+    #[test]
+    fn bind_aggregate() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT count(*), COUNT(name), sum(stu.id), avg(value), max(name) FROM stu, score",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&bound),
+            vec![
+                "COUNT(*)",
+                "COUNT(stu.name)",
+                "SUM(stu.id)",
+                "AVG(score.value)",
+                "MAX(stu.name)"
+            ]
+        );
+        assert_eq!(
+            format!("{:?}", bound.schema),
+            "stu(count(*) Int64 NOT NULL, COUNT(name) Int64 NOT NULL, sum(stu.id) Int64 NULLABLE, \
+             avg(value) Float64 NULLABLE, max(name) Varchar(32) NULLABLE)"
+        );
+    }
+
+    #[test]
+    fn bind_having_alias() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT id AS i, count(*) AS c FROM stu GROUP BY i HAVING c > 1",
+        )
+        .unwrap();
+        assert_eq!(bound.group_by[0].to_string(), "stu.id");
+        assert_eq!(bound.having.unwrap().to_string(), "(COUNT(*) > 1)");
+    }
+
+    #[test]
+    fn bind_aggregate_error() {
+        let context: SimpleMemoryContext = context();
+        for (sql, message) in [
+            (
+                "SELECT id FROM stu WHERE count(*) > 1",
+                "aggregate functions are not allowed in WHERE",
+            ),
+            (
+                "SELECT sum(count(id)) FROM stu",
+                "aggregate function calls cannot be nested",
+            ),
+            (
+                "SELECT sum(name) FROM stu",
+                "invalid argument type Varchar(32) for sum",
+            ),
+            ("SELECT sum(*) FROM stu", "invalid argument * for sum"),
+            ("SELECT lower(name) FROM stu", "unknown function lower"),
+        ] {
+            let error: ExecuteError = bind(&context, sql).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn bind_limit_offset() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(&context, "SELECT id FROM stu LIMIT 2 * 3 OFFSET 1").unwrap();
+        assert_eq!((bound.limit, bound.offset), (Some(6), Some(1)));
+
+        for (sql, message) in [
+            (
+                "SELECT id FROM stu LIMIT 0 - 1",
+                "LIMIT must not be negative",
+            ),
+            ("SELECT id FROM stu OFFSET 'a'", "OFFSET must be an integer"),
+            ("SELECT id FROM stu LIMIT id", "unknown column id"),
+            (
+                "SELECT id FROM stu LIMIT ?",
+                "missing value for parameter 1",
+            ),
+        ] {
+            let error: ExecuteError = bind(&context, sql).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn bind_join() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT *, s.id FROM score AS s, stu LEFT JOIN score ON stu.id = score.id",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&bound),
+            vec![
+                "s.id",
+                "s.value",
+                "stu.id",
+                "stu.name",
+                "score.id",
+                "score.value",
+                "s.id"
+            ]
+        );
+        assert_eq!(bound.from.len(), 2);
+        match &bound.from[1] {
+            BoundFrom::Join {
+                join_type,
+                condition: Some(BoundExpression::Binary(binary)),
+                ..
+            } => {
+                assert_eq!(*join_type, JoinType::Left);
+                // 连接条件中的下标从本项的第一张表开始计数
+                match (binary.left.as_ref(), binary.right.as_ref()) {
+                    (BoundExpression::Column(l), BoundExpression::Column(r)) => {
+                        assert_eq!((l.index, r.index), (0, 2))
+                    }
+                    _ => panic!("expect columns"),
+                }
+            }
+            _ => panic!("expect join"),
+        }
+
+        // 连接条件不能引用 FROM 中其他项的表
+        let error: ExecuteError = bind(
+            &context,
+            "SELECT * FROM score AS s, stu JOIN score ON s.id = score.id",
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().starts_with("unknown table s"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn bind_join_using() {
+        let context: SimpleMemoryContext = context();
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT *, id, score.id FROM stu JOIN score USING (id)",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&bound),
+            vec!["stu.id", "stu.name", "score.value", "stu.id", "score.id"]
+        );
+        match &bound.from[0] {
+            BoundFrom::Join { condition, .. } => {
+                assert_eq!(
+                    condition.as_ref().unwrap().to_string(),
+                    "(stu.id = score.id)"
+                )
+            }
+            _ => panic!("expect join"),
+        }
+
+        // RIGHT JOIN 不带表名引用时取右表的列
+        let bound: BoundSelect =
+            bind(&context, "SELECT * FROM stu RIGHT JOIN score USING (id)").unwrap();
+        assert_eq!(names(&bound), vec!["stu.name", "score.id", "score.value"]);
+
+        // FULL JOIN 不带表名引用时取两侧列的 COALESCE
+        let bound: BoundSelect = bind(
+            &context,
+            "SELECT *, score.id FROM stu FULL JOIN score USING (id) WHERE id > 1",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&bound),
+            vec![
+                "COALESCE(stu.id, score.id)",
+                "stu.name",
+                "score.value",
+                "score.id"
+            ]
+        );
+        assert_eq!(
+            bound.wheres.as_ref().unwrap().to_string(),
+            "(COALESCE(stu.id, score.id) > 1)"
+        );
+
+        for (sql, message) in [
+            (
+                "SELECT * FROM stu JOIN score USING (name)",
+                "column name in USING not found in right table",
+            ),
+            (
+                "SELECT * FROM stu JOIN score USING (id, id)",
+                "column id appears more than once in USING",
+            ),
+            (
+                "SELECT * FROM stu JOIN score ON TRUE JOIN stu AS t USING (id)",
+                "ambiguous column id in USING",
+            ),
+            (
+                "SELECT * FROM stu JOIN score ON stu.id",
+                "argument of JOIN must be type Boolean, not type Int32",
+            ),
+        ] {
+            let error: ExecuteError = bind(&context, sql).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::executor::{
+    expression::{BoundExpression, ColumnRef},
+    meta::{column::Column, schema::Schema},
+};
+
+/// Scope 名字解析作用域
+/// 由 FROM 子句中的表依次拼接而成，列在输入行中的下标为所在表的偏移量加列序号
+/// 直接引用的表还记录所在的命名空间，可以使用 namespace.table.column 引用其中的列
+/// 绑定 GROUP BY 和 HAVING 时，还可以引用 SELECT 中的别名
+/// JOIN ... USING 中重复的列被隐藏，不带表名引用或者展开 * 时只出现一次
+/// FULL JOIN ... USING 中留下的列合并为两侧列的 COALESCE
+#[derive(Debug, Default)]
+pub struct Scope {
+    tables: Vec<ScopeTable>,
+    aliases: Vec<(Box<str>, BoundExpression)>,
+    hidden: Vec<usize>,
+    merged: Vec<(usize, BoundExpression)>,
+}
+
+#[derive(Debug)]
+struct ScopeTable {
+    namespace: Option<Box<str>>, // 带别名的表和子查询没有命名空间
+    name: Box<str>,
+    columns: Box<[Column]>,
+    offset: usize,
+}
+
+/// 列名解析失败的原因
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResolveError {
+    UnknownTable,
+    UnknownColumn,
+    Ambiguous,
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self {
+            tables: vec![],
+            aliases: vec![],
+            hidden: vec![],
+            merged: vec![],
+        }
+    }
+
+    /// 添加 SELECT 中的别名，只在列名无法解析时使用
+    pub fn push_alias(&mut self, name: Box<str>, expression: BoundExpression) {
+        self.aliases.push((name, expression));
+    }
+
+    /// 查找别名，同名且表达式不同的别名有歧义
+    pub fn alias(&self, name: &str) -> Result<&BoundExpression, ResolveError> {
+        let mut found: Option<&BoundExpression> = None;
+        for (alias, expression) in self.aliases.iter() {
+            if alias.as_ref() == name {
+                if found.is_some_and(|f| f != expression) {
+                    return Err(ResolveError::Ambiguous);
+                }
+                found = Some(expression);
+            }
+        }
+        found.ok_or(ResolveError::UnknownColumn)
+    }
+
+    /// 追加一张表，表名取 schema.name
+    pub fn push(&mut self, schema: &Schema) {
+        self.push_table(None, schema);
+    }
+
+    /// 追加命名空间 namespace 中的一张表，表名取 schema.name
+    pub fn push_table(&mut self, namespace: Option<&str>, schema: &Schema) {
+        let offset: usize = self.width();
+        self.tables.push(ScopeTable {
+            namespace: namespace.map(|n| n.into()),
+            name: schema.name.clone(),
+            columns: schema.columns.clone(),
+            offset,
+        });
+    }
+
+    /// 隐藏下标为 index 的列，只能使用表名引用
+    pub fn hide(&mut self, index: usize) {
+        self.hidden.push(index);
+    }
+
+    /// 不带表名引用下标为 index 的列时，改为使用 expression
+    pub fn merge(&mut self, index: usize, expression: BoundExpression) {
+        self.merged.retain(|(i, _)| *i != index);
+        self.merged.push((index, expression));
+    }
+
+    /// 下标为 index 的列合并后的表达式
+    pub fn merged(&self, index: usize) -> Option<&BoundExpression> {
+        self.merged
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, expression)| expression)
+    }
+
+    /// 将下标在 range 中的列标记为可能为 NULL，用于外连接中补 NULL 的一侧
+    pub fn make_nullable(&mut self, range: std::ops::Range<usize>) {
+        for table in self.tables.iter_mut() {
+            for (index, column) in table.columns.iter_mut().enumerate() {
+                if range.contains(&(table.offset + index)) {
+                    column.nullable = true;
+                }
+            }
+        }
+    }
+
+    pub fn contains_table(&self, name: &str) -> bool {
+        self.tables.iter().any(|t| t.name.as_ref() == name)
+    }
+
+    /// 作用域内所有列的数目，即输入行的宽度
+    pub fn width(&self) -> usize {
+        self.tables.iter().map(|t| t.columns.len()).sum()
+    }
+
+    /// 解析列名。table 为 None 时在所有表中查找，出现多次则有歧义
+    pub fn resolve(&self, table: Option<&str>, column: &str) -> Result<ColumnRef, ResolveError> {
+        self.resolve_in(None, table, column)
+    }
+
+    /// 解析 namespace.table.column 形式的列名，namespace 为 None 时不限制命名空间
+    pub fn resolve_in(
+        &self,
+        namespace: Option<&str>,
+        table: Option<&str>,
+        column: &str,
+    ) -> Result<ColumnRef, ResolveError> {
+        let mut found: Option<ColumnRef> = None;
+        for t in self.tables(namespace, table)? {
+            if let Some(index) = t.columns.iter().position(|c| c.name.as_ref() == column) {
+                if table.is_none() && self.hidden.contains(&(t.offset + index)) {
+                    continue;
+                }
+                if found.is_some() {
+                    return Err(ResolveError::Ambiguous);
+                }
+                found = Some(t.column_ref(index));
+            }
+        }
+        found.ok_or(ResolveError::UnknownColumn)
+    }
+
+    /// 展开通配符。table 为 None 时展开所有表的列
+    pub fn expand(&self, table: Option<&str>) -> Result<Vec<ColumnRef>, ResolveError> {
+        self.expand_in(None, table)
+    }
+
+    /// 展开 namespace.table.* 形式的通配符，namespace 为 None 时不限制命名空间
+    pub fn expand_in(
+        &self,
+        namespace: Option<&str>,
+        table: Option<&str>,
+    ) -> Result<Vec<ColumnRef>, ResolveError> {
+        Ok(self
+            .tables(namespace, table)?
+            .flat_map(|t| (0..t.columns.len()).map(|index| t.column_ref(index)))
+            .filter(|column| table.is_some() || !self.hidden.contains(&column.index))
+            .collect())
+    }
+
+    fn tables<'s>(
+        &'s self,
+        namespace: Option<&'s str>,
+        table: Option<&'s str>,
+    ) -> Result<impl Iterator<Item = &'s ScopeTable> + 's, ResolveError> {
+        let matches = move |t: &&ScopeTable| {
+            table.is_none_or(|name| t.name.as_ref() == name)
+                && namespace.is_none_or(|namespace| t.namespace.as_deref() == Some(namespace))
+        };
+        if table.is_some() && !self.tables.iter().any(|t| matches(&t)) {
+            return Err(ResolveError::UnknownTable);
+        }
+        Ok(self.tables.iter().filter(matches))
+    }
+}
+
+impl ScopeTable {
+    fn column_ref(&self, index: usize) -> ColumnRef {
+        let column: &Column = &self.columns[index];
+        ColumnRef {
+            table: self.name.clone(),
+            name: column.name.clone(),
+            index: self.offset + index,
+            data_type: column.data_type.clone(),
+            nullable: column.nullable,
+        }
+    }
+}
@@ -1,17 +1,356 @@
 use std::collections::HashMap;
 
-use super::meta::data_source::DataSource;
+use crate::error::ErrorCode;
 
+use super::{
+    error::ExecuteError,
+    meta::{
+        data_source::{DataSource, MutableDataSource, SimpleMemoryDataSource},
+        schema::Schema,
+    },
+};
+
+/// 默认的命名空间
+pub const DEFAULT_NAMESPACE: &str = "main";
+
+/// Context 目录（catalog），管理命名空间和其中的表
+/// 未指定命名空间的表名都在当前命名空间中查找和修改
+/// 修改目录的操作在所有检查通过后才生效，失败时目录保持不变
 pub trait Context {
-    fn find_table<'a>(&'a self, name: &str) -> Option<&'a dyn DataSource>;
+    /// 当前命名空间
+    fn current_namespace(&self) -> &str;
+
+    /// 在命名空间 namespace 中查找表
+    fn find_table_in<'a>(&'a self, namespace: &str, name: &str) -> Option<&'a dyn DataSource>;
+
+    /// 在当前命名空间中查找表
+    fn find_table<'a>(&'a self, name: &str) -> Option<&'a dyn DataSource> {
+        self.find_table_in(self.current_namespace(), name)
+    }
+
+    /// 查找可写的表
+    fn find_table_mut<'a>(&'a mut self, name: &str) -> Option<&'a mut dyn MutableDataSource>;
+
+    /// 当前命名空间中所有的表名，按名字排序
+    fn list_tables(&self) -> Vec<Box<str>>;
+
+    /// 创建空表，表名取 schema.name，表已存在时报错
+    fn create_table(&mut self, schema: Schema) -> Result<(), ExecuteError>;
+
+    /// 删除表，表不存在或者有其他对象依赖该表时报错
+    fn drop_table(&mut self, name: &str) -> Result<(), ExecuteError>;
+
+    /// 重命名表，表不存在、新表名已存在或者有其他对象依赖该表时报错
+    fn rename_table(&mut self, from: &str, to: &str) -> Result<(), ExecuteError>;
+
+    /// 依赖表 table 的其他对象的名字
+    /// column 不为 None 时只返回依赖该列（或者依赖整张表）的对象
+    fn dependents(&self, table: &str, column: Option<&str>) -> Vec<Box<str>>;
+
+    /// 所有的命名空间，按名字排序
+    fn list_namespaces(&self) -> Vec<Box<str>>;
+
+    /// 创建空的命名空间，已存在时报错
+    fn create_namespace(&mut self, name: &str) -> Result<(), ExecuteError>;
+
+    /// 删除命名空间，命名空间不存在、不为空或者是当前命名空间时报错
+    fn drop_namespace(&mut self, name: &str) -> Result<(), ExecuteError>;
+
+    /// 切换当前命名空间，不存在时报错
+    fn set_current_namespace(&mut self, name: &str) -> Result<(), ExecuteError>;
+}
+
+/// Dependency 对象 object 依赖表 namespace.table，column 为 None 时依赖整张表
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub object: Box<str>,
+    pub namespace: Box<str>,
+    pub table: Box<str>,
+    pub column: Option<Box<str>>,
 }
 
+type Tables = HashMap<Box<str>, Box<dyn MutableDataSource>>;
+
 pub struct SimpleMemoryContext {
-    tables: HashMap<Box<str>, Box<dyn DataSource>>,
+    namespaces: HashMap<Box<str>, Tables>,
+    current: Box<str>,
+    dependencies: Vec<Dependency>,
+}
+
+impl Default for SimpleMemoryContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimpleMemoryContext {
+    /// 只包含空的默认命名空间 main
+    pub fn new() -> Self {
+        Self {
+            namespaces: HashMap::from([(DEFAULT_NAMESPACE.into(), Tables::new())]),
+            current: DEFAULT_NAMESPACE.into(),
+            dependencies: vec![],
+        }
+    }
+
+    /// 在当前命名空间中注册表，表名取自 DataSource::name，同名表会被覆盖
+    pub fn add_table(&mut self, table: Box<dyn MutableDataSource>) {
+        self.tables_mut()
+            .insert(table.name().into_boxed_str(), table);
+    }
+
+    /// 登记依赖关系，被依赖的表不能删除和重命名，被依赖的列不能删除和重命名
+    pub fn add_dependency(&mut self, dependency: Dependency) {
+        self.dependencies.push(dependency);
+    }
+
+    fn tables(&self) -> &Tables {
+        &self.namespaces[&self.current]
+    }
+
+    fn tables_mut(&mut self) -> &mut Tables {
+        self.namespaces.get_mut(&self.current).unwrap()
+    }
+
+    /// 有其他对象依赖该表时报错
+    fn check_dependents(&self, table: &str, action: &str) -> Result<(), ExecuteError> {
+        let dependents: Vec<Box<str>> = self.dependents(table, None);
+        if dependents.is_empty() {
+            return Ok(());
+        }
+        Err(ExecuteError::new(
+            ErrorCode::DependentObjectsStillExist,
+            format!(
+                "cannot {} table {} because {} depends on it",
+                action,
+                table,
+                dependents.join(", ")
+            ),
+        ))
+    }
 }
 
 impl Context for SimpleMemoryContext {
-    fn find_table<'a>(&'a self, name: &str) -> Option<&'a dyn DataSource> {
-        self.tables.get(name).map(|v| v.as_ref())
+    fn current_namespace(&self) -> &str {
+        &self.current
+    }
+
+    fn find_table_in<'a>(&'a self, namespace: &str, name: &str) -> Option<&'a dyn DataSource> {
+        self.namespaces
+            .get(namespace)?
+            .get(name)
+            .map(|v| v.as_ref() as &dyn DataSource)
+    }
+
+    fn find_table_mut<'a>(&'a mut self, name: &str) -> Option<&'a mut dyn MutableDataSource> {
+        match self.tables_mut().get_mut(name) {
+            Some(table) => Some(table.as_mut()),
+            None => None,
+        }
+    }
+
+    fn list_tables(&self) -> Vec<Box<str>> {
+        let mut names: Vec<Box<str>> = self.tables().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn create_table(&mut self, schema: Schema) -> Result<(), ExecuteError> {
+        if self.tables().contains_key(schema.name.as_ref()) {
+            return Err(ExecuteError::new(
+                ErrorCode::DuplicateTable,
+                format!("table {} already exists", schema.name),
+            ));
+        }
+        self.add_table(Box::new(SimpleMemoryDataSource::new(schema)));
+        Ok(())
+    }
+
+    fn drop_table(&mut self, name: &str) -> Result<(), ExecuteError> {
+        if !self.tables().contains_key(name) {
+            return Err(ExecuteError::new(
+                ErrorCode::UndefinedTable,
+                format!("table {} not found", name),
+            ));
+        }
+        self.check_dependents(name, "drop")?;
+        self.tables_mut().remove(name);
+        Ok(())
+    }
+
+    fn rename_table(&mut self, from: &str, to: &str) -> Result<(), ExecuteError> {
+        if !self.tables().contains_key(from) {
+            return Err(ExecuteError::new(
+                ErrorCode::UndefinedTable,
+                format!("table {} not found", from),
+            ));
+        }
+        if self.tables().contains_key(to) {
+            return Err(ExecuteError::new(
+                ErrorCode::DuplicateTable,
+                format!("table {} already exists", to),
+            ));
+        }
+        self.check_dependents(from, "rename")?;
+        let tables: &mut Tables = self.tables_mut();
+        let mut table: Box<dyn MutableDataSource> = tables.remove(from).unwrap();
+        table.rename(to);
+        tables.insert(to.into(), table);
+        Ok(())
+    }
+
+    fn dependents(&self, table: &str, column: Option<&str>) -> Vec<Box<str>> {
+        self.dependencies
+            .iter()
+            .filter(|d| d.namespace == self.current && d.table.as_ref() == table)
+            .filter(|d| match (&d.column, column) {
+                (Some(dependent), Some(column)) => dependent.as_ref() == column,
+                _ => true,
+            })
+            .map(|d| d.object.clone())
+            .collect()
+    }
+
+    fn list_namespaces(&self) -> Vec<Box<str>> {
+        let mut names: Vec<Box<str>> = self.namespaces.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn create_namespace(&mut self, name: &str) -> Result<(), ExecuteError> {
+        if self.namespaces.contains_key(name) {
+            return Err(ExecuteError::new(
+                ErrorCode::DuplicateObject,
+                format!("namespace {} already exists", name),
+            ));
+        }
+        self.namespaces.insert(name.into(), Tables::new());
+        Ok(())
+    }
+
+    fn drop_namespace(&mut self, name: &str) -> Result<(), ExecuteError> {
+        match self.namespaces.get(name) {
+            None => Err(ExecuteError::new(
+                ErrorCode::UndefinedObject,
+                format!("namespace {} not found", name),
+            )),
+            Some(_) if self.current.as_ref() == name => Err(ExecuteError::new(
+                ErrorCode::ObjectInUse,
+                format!("cannot drop the current namespace {}", name),
+            )),
+            Some(tables) if !tables.is_empty() => Err(ExecuteError::new(
+                ErrorCode::DependentObjectsStillExist,
+                format!("namespace {} is not empty", name),
+            )),
+            Some(_) => {
+                self.namespaces.remove(name);
+                Ok(())
+            }
+        }
+    }
+
+    fn set_current_namespace(&mut self, name: &str) -> Result<(), ExecuteError> {
+        if !self.namespaces.contains_key(name) {
+            return Err(ExecuteError::new(
+                ErrorCode::UndefinedObject,
+                format!("namespace {} not found", name),
+            ));
+        }
+        self.current = name.into();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::{
+        error::ExecuteError,
+        meta::{column::Column, schema::Schema},
+        types::DataType,
+    };
+
+    use super::{Context, Dependency, SimpleMemoryContext, DEFAULT_NAMESPACE};
+
+    fn schema(name: &str) -> Schema {
+        Schema {
+            name: name.into(),
+            columns: vec![Column {
+                name: "a".into(),
+                data_type: DataType::Int32,
+                nullable: true,
+                default: None,
+            }]
+            .into_boxed_slice(),
+        }
+    }
+
+    #[test]
+    fn test_tables() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        assert_eq!(context.current_namespace(), DEFAULT_NAMESPACE);
+        context.create_table(schema("b")).unwrap();
+        context.create_table(schema("a")).unwrap();
+        assert_eq!(context.list_tables(), vec!["a".into(), "b".into()]);
+
+        context.rename_table("a", "c").unwrap();
+        assert_eq!(context.list_tables(), vec!["b".into(), "c".into()]);
+        assert_eq!(context.find_table("c").unwrap().schema().name.as_ref(), "c");
+        assert!(context.find_table("a").is_none());
+
+        let error: ExecuteError = context.rename_table("b", "c").unwrap_err();
+        assert_eq!(error.message, "table c already exists");
+        let error: ExecuteError = context.rename_table("x", "y").unwrap_err();
+        assert_eq!(error.message, "table x not found");
+
+        context.add_dependency(Dependency {
+            object: "v".into(),
+            namespace: DEFAULT_NAMESPACE.into(),
+            table: "b".into(),
+            column: None,
+        });
+        let error: ExecuteError = context.rename_table("b", "d").unwrap_err();
+        assert_eq!(
+            error.message,
+            "cannot rename table b because v depends on it"
+        );
+        let error: ExecuteError = context.drop_table("b").unwrap_err();
+        assert_eq!(error.message, "cannot drop table b because v depends on it");
+        assert_eq!(context.list_tables(), vec!["b".into(), "c".into()]);
+
+        context.drop_table("c").unwrap();
+        assert_eq!(context.list_tables(), vec!["b".into()]);
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        context.create_table(schema("t")).unwrap();
+        context.create_namespace("db").unwrap();
+        assert_eq!(
+            context.list_namespaces(),
+            vec!["db".into(), DEFAULT_NAMESPACE.into()]
+        );
+        assert!(context.create_namespace("db").is_err());
+
+        context.set_current_namespace("db").unwrap();
+        assert!(context.find_table("t").is_none());
+        assert!(context.find_table_in(DEFAULT_NAMESPACE, "t").is_some());
+        context.create_table(schema("t")).unwrap();
+        assert!(context.find_table_in("db", "t").is_some());
+
+        let error: ExecuteError = context.drop_namespace("db").unwrap_err();
+        assert_eq!(error.message, "cannot drop the current namespace db");
+        context.set_current_namespace(DEFAULT_NAMESPACE).unwrap();
+        let error: ExecuteError = context.drop_namespace("db").unwrap_err();
+        assert_eq!(error.message, "namespace db is not empty");
+        let error: ExecuteError = context.set_current_namespace("x").unwrap_err();
+        assert_eq!(error.message, "namespace x not found");
+
+        context.set_current_namespace("db").unwrap();
+        context.drop_table("t").unwrap();
+        context.set_current_namespace(DEFAULT_NAMESPACE).unwrap();
+        context.drop_namespace("db").unwrap();
+        assert_eq!(context.list_namespaces(), vec![DEFAULT_NAMESPACE.into()]);
+        assert!(context.find_table("t").is_some());
     }
 }
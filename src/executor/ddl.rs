@@ -0,0 +1,461 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        binder::{scope::Scope, Binder},
+        context::Context,
+        error::ExecuteError,
+        meta::{column::Column, row::SimpleMemoryRow, schema::Schema},
+        types::{DataType, OwnValue},
+    },
+    sql::parser::ast::{
+        alter_table::AlterTableOperation, create_table::ColumnDefinition,
+        identifier::SingleIdentifier, AlterTable, CreateTable, DropTable,
+    },
+};
+
+/// 执行 CREATE TABLE，将列定义转换为 Schema，并在 context 中注册新表
+pub fn create_table(
+    context: &mut dyn Context,
+    create_table: &CreateTable,
+    raw_sql: &str,
+) -> Result<(), ExecuteError> {
+    let name: &str = &create_table.name.value;
+    if context.find_table(name).is_some() {
+        return Err(ExecuteError::located(
+            ErrorCode::DuplicateTable,
+            format!("table {} already exists", name),
+            &create_table.name,
+            raw_sql,
+        ));
+    }
+
+    let mut names: HashSet<&str> = HashSet::new();
+    let mut columns: Vec<Column> = Vec::with_capacity(create_table.columns.len());
+    for definition in create_table.columns.iter() {
+        if !names.insert(&definition.name.value) {
+            return Err(ExecuteError::located(
+                ErrorCode::DuplicateColumn,
+                format!("duplicate column name {}", definition.name.value),
+                definition,
+                raw_sql,
+            ));
+        }
+        columns.push(column(definition));
+    }
+
+    context.create_table(Schema {
+        name: name.into(),
+        columns: columns.into_boxed_slice(),
+    })
+}
+
+/// 执行 DROP TABLE [IF EXISTS]
+pub fn drop_table(
+    context: &mut dyn Context,
+    drop_table: &DropTable,
+    raw_sql: &str,
+) -> Result<(), ExecuteError> {
+    let name: &str = &drop_table.name.value;
+    if context.find_table(name).is_none() {
+        if drop_table.if_exists {
+            return Ok(());
+        }
+        return Err(ExecuteError::located(
+            ErrorCode::UndefinedTable,
+            format!("table {} not found", name),
+            &drop_table.name,
+            raw_sql,
+        ));
+    }
+    context
+        .drop_table(name)
+        .map_err(|error| error.at(&drop_table.name, raw_sql))
+}
+
+/// 执行 ALTER TABLE
+/// RENAME TO 只修改目录中的表名，其余操作按修改后的列重写表结构和已有的每一行
+/// ADD COLUMN 的 DEFAULT 值填充已有的行，并记录在列上，之后插入时未指定该列也使用这个值
+pub fn alter_table(
+    context: &mut dyn Context,
+    alter_table: &AlterTable,
+    raw_sql: &str,
+    params: &[OwnValue],
+) -> Result<(), ExecuteError> {
+    let name: &str = &alter_table.name.value;
+    let Some(data_source) = context.find_table(name) else {
+        return Err(ExecuteError::located(
+            ErrorCode::UndefinedTable,
+            format!("table {} not found", name),
+            &alter_table.name,
+            raw_sql,
+        ));
+    };
+    if let AlterTableOperation::RenameTable(to) = &alter_table.operation {
+        return context
+            .rename_table(name, &to.value)
+            .map_err(|error| error.at(to, raw_sql));
+    }
+
+    let mut schema: Schema = data_source.schema().clone();
+    let mut rows: Vec<Vec<OwnValue>> = data_source
+        .read()
+        .map(|row| {
+            (0..schema.columns.len())
+                .map(|index| row.get(index).cloned())
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    match &alter_table.operation {
+        AlterTableOperation::AddColumn {
+            column: definition,
+            default,
+        } => {
+            if position(&schema, &definition.name.value).is_some() {
+                return Err(ExecuteError::located(
+                    ErrorCode::DuplicateColumn,
+                    format!("duplicate column name {}", definition.name.value),
+                    definition,
+                    raw_sql,
+                ));
+            }
+            let mut column: Column = column(definition);
+            let value: OwnValue = match default {
+                Some(default) => {
+                    let value: OwnValue = Binder::new(&*context, raw_sql)
+                        .with_params(params)
+                        .bind_expression(default, &Scope::new())?
+                        .evaluate(&SimpleMemoryRow::new(vec![]))?;
+                    let value: OwnValue = column
+                        .check_value(value)
+                        .map_err(|error| error.at(default, raw_sql))?;
+                    column.default = Some(value.clone());
+                    value
+                }
+                None if rows.is_empty() => OwnValue::Null,
+                None => column
+                    .check_value(OwnValue::Null)
+                    .map_err(|error| error.at(definition, raw_sql))?,
+            };
+            let mut columns: Vec<Column> = schema.columns.into_vec();
+            columns.push(column);
+            schema.columns = columns.into_boxed_slice();
+            for row in rows.iter_mut() {
+                row.push(value.clone());
+            }
+        }
+        AlterTableOperation::DropColumn(column) => {
+            let index: usize = existing_column(context, &schema, column, raw_sql)?;
+            if schema.columns.len() == 1 {
+                return Err(ExecuteError::located(
+                    ErrorCode::InvalidTableDefinition,
+                    format!("cannot drop the only column of table {}", name),
+                    column,
+                    raw_sql,
+                ));
+            }
+            let mut columns: Vec<Column> = schema.columns.into_vec();
+            columns.remove(index);
+            schema.columns = columns.into_boxed_slice();
+            for row in rows.iter_mut() {
+                row.remove(index);
+            }
+        }
+        AlterTableOperation::RenameColumn { from, to } => {
+            let index: usize = existing_column(context, &schema, from, raw_sql)?;
+            if position(&schema, &to.value).is_some() {
+                return Err(ExecuteError::located(
+                    ErrorCode::DuplicateColumn,
+                    format!("duplicate column name {}", to.value),
+                    to,
+                    raw_sql,
+                ));
+            }
+            schema.columns[index].name = to.value.as_ref().into();
+        }
+        AlterTableOperation::RenameTable(_) => unreachable!(),
+    }
+
+    match context.find_table_mut(name) {
+        Some(data_source) => {
+            data_source.replace(schema, rows.into_iter().map(SimpleMemoryRow::new).collect());
+            Ok(())
+        }
+        None => Err(ExecuteError::new(
+            ErrorCode::UndefinedTable,
+            format!("table {} not found", name),
+        )),
+    }
+}
+
+fn column(definition: &ColumnDefinition) -> Column {
+    Column {
+        name: definition.name.value.as_ref().into(),
+        data_type: DataType::from(&definition.column_type),
+        nullable: !definition.not_null,
+        default: None,
+    }
+}
+
+fn position(schema: &Schema, name: &str) -> Option<usize> {
+    schema.columns.iter().position(|c| c.name.as_ref() == name)
+}
+
+/// 查找将被删除或者重命名的列，该列不能被其他对象依赖
+fn existing_column(
+    context: &dyn Context,
+    schema: &Schema,
+    column: &SingleIdentifier,
+    raw_sql: &str,
+) -> Result<usize, ExecuteError> {
+    let Some(index) = position(schema, &column.value) else {
+        return Err(ExecuteError::located(
+            ErrorCode::UndefinedColumn,
+            format!("unknown column {}", column.value),
+            column,
+            raw_sql,
+        ));
+    };
+    let dependents: Vec<Box<str>> = context.dependents(&schema.name, Some(&column.value));
+    if !dependents.is_empty() {
+        return Err(ExecuteError::located(
+            ErrorCode::DependentObjectsStillExist,
+            format!(
+                "cannot alter column {} because {} depends on it",
+                column.value,
+                dependents.join(", ")
+            ),
+            column,
+            raw_sql,
+        ));
+    }
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::{
+            context::{Context, Dependency, SimpleMemoryContext, DEFAULT_NAMESPACE},
+            error::ExecuteError,
+            meta::row::SimpleMemoryRow,
+            types::{int32::Int32, OwnValue},
+        },
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn execute(context: &mut SimpleMemoryContext, sql: &str) -> Result<(), ExecuteError> {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        match &statements.statements[0] {
+            Statement::CreateTable(create_table) => super::create_table(context, create_table, sql),
+            Statement::DropTable(drop_table) => super::drop_table(context, drop_table, sql),
+            Statement::AlterTable(alter_table) => {
+                super::alter_table(context, alter_table, sql, &[])
+            }
+            _ => panic!("expect ddl"),
+        }
+    }
+
+    #[test]
+    fn test_create_table() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(
+            &mut context,
+            "CREATE TABLE stu (name VARCHAR(32) NOT NULL, age INT, score FLOAT, memo TEXT)",
+        )
+        .unwrap();
+        let table = context.find_table("stu").unwrap();
+        assert_eq!(
+            format!("{:?}", table.schema()),
+            "stu(name Varchar(32) NOT NULL, age Int32 NULLABLE, score Float64 NULLABLE, memo String NULLABLE)"
+        );
+        assert_eq!(table.read().count(), 0);
+
+        let error: ExecuteError = execute(&mut context, "CREATE TABLE stu (a INT)").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("table stu already exists in Ln 1, Col 14"));
+    }
+
+    #[test]
+    fn test_duplicate_column() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        let error: ExecuteError =
+            execute(&mut context, "CREATE TABLE t (a INT, b INT, a TEXT)").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("duplicate column name a in Ln 1, Col 31"));
+        assert!(context.find_table("t").is_none());
+    }
+
+    fn table(context: &SimpleMemoryContext, name: &str) -> (String, Vec<String>) {
+        let table = context.find_table(name).unwrap();
+        (
+            format!("{:?}", table.schema()),
+            table
+                .read()
+                .map(|row| row.to_string(table.schema()).unwrap())
+                .collect(),
+        )
+    }
+
+    /// 创建 stu(name, age) 并插入两行
+    fn stu(context: &mut SimpleMemoryContext) {
+        execute(context, "CREATE TABLE stu (name TEXT NOT NULL, age INT)").unwrap();
+        context.find_table_mut("stu").unwrap().insert(vec![
+            SimpleMemoryRow::new(vec![
+                OwnValue::String("a".into()),
+                OwnValue::Int32(Int32::new(18)),
+            ]),
+            SimpleMemoryRow::new(vec![OwnValue::String("b".into()), OwnValue::Null]),
+        ]);
+    }
+
+    #[test]
+    fn test_drop_table() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        stu(&mut context);
+        context.add_dependency(Dependency {
+            object: "stu_view".into(),
+            namespace: DEFAULT_NAMESPACE.into(),
+            table: "stu".into(),
+            column: None,
+        });
+        let error: ExecuteError = execute(&mut context, "DROP TABLE stu").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("cannot drop table stu because stu_view depends on it in Ln 1, Col 12"));
+        assert!(context.find_table("stu").is_some());
+
+        execute(&mut context, "CREATE TABLE t (a INT)").unwrap();
+        execute(&mut context, "DROP TABLE t").unwrap();
+        assert!(context.find_table("t").is_none());
+
+        let error: ExecuteError = execute(&mut context, "DROP TABLE t").unwrap_err();
+        assert!(error.to_string().starts_with("table t not found"));
+        execute(&mut context, "DROP TABLE IF EXISTS t").unwrap();
+    }
+
+    #[test]
+    fn test_add_column() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        stu(&mut context);
+        execute(
+            &mut context,
+            "ALTER TABLE stu ADD COLUMN score DOUBLE NOT NULL DEFAULT 60",
+        )
+        .unwrap();
+        execute(&mut context, "ALTER TABLE stu ADD memo VARCHAR(8)").unwrap();
+        assert_eq!(
+            table(&context, "stu"),
+            (
+                "stu(name String NOT NULL, age Int32 NULLABLE, score Float64 NOT NULL DEFAULT 60, memo Varchar(8) NULLABLE)".to_string(),
+                vec![
+                    "[\"a\", 18, 60, NULL]".to_string(),
+                    "[\"b\", NULL, 60, NULL]".to_string()
+                ]
+            )
+        );
+
+        for (sql, message) in [
+            (
+                "ALTER TABLE stu ADD age INT",
+                "duplicate column name age in Ln 1, Col 21",
+            ),
+            (
+                "ALTER TABLE stu ADD rank INT NOT NULL",
+                "column rank must not be NULL in Ln 1, Col 21",
+            ),
+            (
+                "ALTER TABLE stu ADD sex VARCHAR(1) DEFAULT 'male'",
+                "value too long for column sex of type Varchar(1) in Ln 1, Col 44",
+            ),
+            (
+                "ALTER TABLE tea ADD a INT",
+                "table tea not found in Ln 1, Col 13",
+            ),
+        ] {
+            let error: ExecuteError = execute(&mut context, sql).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+
+        // 空表可以直接添加没有默认值的 NOT NULL 列
+        execute(&mut context, "CREATE TABLE t (a INT)").unwrap();
+        execute(&mut context, "ALTER TABLE t ADD b INT NOT NULL").unwrap();
+        assert_eq!(
+            table(&context, "t").0,
+            "t(a Int32 NULLABLE, b Int32 NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_drop_rename_column() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        stu(&mut context);
+        execute(&mut context, "ALTER TABLE stu RENAME COLUMN age TO years").unwrap();
+        execute(&mut context, "ALTER TABLE stu DROP COLUMN name").unwrap();
+        assert_eq!(
+            table(&context, "stu"),
+            (
+                "stu(years Int32 NULLABLE)".to_string(),
+                vec!["[18]".to_string(), "[NULL]".to_string()]
+            )
+        );
+
+        execute(&mut context, "ALTER TABLE stu ADD name TEXT").unwrap();
+        context.add_dependency(Dependency {
+            object: "years_index".into(),
+            namespace: DEFAULT_NAMESPACE.into(),
+            table: "stu".into(),
+            column: Some("years".into()),
+        });
+        for (sql, message) in [
+            (
+                "ALTER TABLE stu DROP years",
+                "cannot alter column years because years_index depends on it",
+            ),
+            (
+                "ALTER TABLE stu RENAME years TO age",
+                "cannot alter column years because years_index depends on it",
+            ),
+            (
+                "ALTER TABLE stu RENAME name TO years",
+                "duplicate column name years in Ln 1, Col 32",
+            ),
+            (
+                "ALTER TABLE stu DROP sex",
+                "unknown column sex in Ln 1, Col 22",
+            ),
+        ] {
+            let error: ExecuteError = execute(&mut context, sql).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+
+        execute(&mut context, "CREATE TABLE t (a INT)").unwrap();
+        let error: ExecuteError = execute(&mut context, "ALTER TABLE t DROP a").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("cannot drop the only column of table t"));
+    }
+
+    #[test]
+    fn test_rename_table() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        stu(&mut context);
+        execute(&mut context, "CREATE TABLE t (a INT)").unwrap();
+        let error: ExecuteError = execute(&mut context, "ALTER TABLE stu RENAME TO t").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("table t already exists in Ln 1, Col 27"));
+
+        execute(&mut context, "ALTER TABLE stu RENAME TO student").unwrap();
+        assert_eq!(context.list_tables(), vec!["student".into(), "t".into()]);
+        assert_eq!(table(&context, "student").1.len(), 2);
+    }
+}
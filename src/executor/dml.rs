@@ -0,0 +1,579 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        binder::{scope::Scope, Binder},
+        context::Context,
+        error::ExecuteError,
+        expression::BoundExpression,
+        meta::{
+            data_source::{DataSource, MutableDataSource},
+            row::{Row, SimpleMemoryRow},
+            schema::Schema,
+        },
+        plan::physical::PhysicalPlan,
+        planner::{logic::LogicPlanner, optimizer::Optimizer, physical::PhysicalPlanner},
+        types::OwnValue,
+    },
+    sql::parser::ast::{
+        expression::Expression, identifier::SingleIdentifier, insert::InsertSource,
+        leaf::WithLocation, Delete, Insert, Update,
+    },
+};
+
+/// 执行 INSERT，返回插入的行数
+/// 所有行校验通过后才写入表中，任意一行出错时表保持不变
+/// 未出现在列清单中的列填充列的默认值，没有默认值时填充 NULL
+pub fn insert(
+    context: &mut dyn Context,
+    optimizer: &Optimizer,
+    planner: &PhysicalPlanner,
+    insert: &Insert,
+    raw_sql: &str,
+    params: &[OwnValue],
+) -> Result<usize, ExecuteError> {
+    let table: &str = &insert.table.value;
+    let data_source: &dyn DataSource = find_table(&*context, &insert.table, raw_sql)?;
+    let schema: &Schema = data_source.schema();
+
+    // 列清单中第 i 列对应表中的第 targets[i] 列
+    let targets: Vec<usize> = if insert.columns.is_empty() {
+        (0..schema.columns.len()).collect()
+    } else {
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut targets: Vec<usize> = Vec::with_capacity(insert.columns.len());
+        for column in insert.columns.iter() {
+            let Some(index) = schema
+                .columns
+                .iter()
+                .position(|c| c.name.as_ref() == column.value.as_ref())
+            else {
+                return Err(ExecuteError::located(
+                    ErrorCode::UndefinedColumn,
+                    format!("unknown column {}", column.value),
+                    column,
+                    raw_sql,
+                ));
+            };
+            if !seen.insert(&column.value) {
+                return Err(ExecuteError::located(
+                    ErrorCode::DuplicateColumn,
+                    format!("duplicate column name {}", column.value),
+                    column,
+                    raw_sql,
+                ));
+            }
+            targets.push(index);
+        }
+        targets
+    };
+
+    let mut rows: Vec<SimpleMemoryRow> = vec![];
+    match &insert.source {
+        InsertSource::Values(values) => {
+            let binder: Binder = Binder::new(&*context, raw_sql).with_params(params);
+            let scope: Scope = Scope::new();
+            let empty: SimpleMemoryRow = SimpleMemoryRow::new(vec![]);
+            for row in values.iter() {
+                if row.len() != targets.len() {
+                    return Err(ExecuteError::located(
+                        ErrorCode::SyntaxError,
+                        format!(
+                            "VALUES row has {} values but {} columns are expected",
+                            row.len(),
+                            targets.len()
+                        ),
+                        &row[0],
+                        raw_sql,
+                    ));
+                }
+                let mut values: Vec<OwnValue> = defaults(schema);
+                for (expression, target) in row.iter().zip(targets.iter()) {
+                    let bound: BoundExpression = binder.bind_expression(expression, &scope)?;
+                    values[*target] = bound.evaluate(&empty)?;
+                }
+                rows.push(check_row(schema, values, &row[0], raw_sql)?);
+            }
+        }
+        InsertSource::Select(select) => {
+            let logic = LogicPlanner::new(&*context, raw_sql)
+                .with_params(params)
+                .plan_select(select)?;
+            let logic = optimizer.optimize(logic);
+            let mut physical: Box<dyn PhysicalPlan<'_> + '_> = planner.build(&logic)?;
+            let width: usize = physical.schema().columns.len();
+            if width != targets.len() {
+                return Err(ExecuteError::located(
+                    ErrorCode::SyntaxError,
+                    format!(
+                        "SELECT returns {} columns but {} columns are expected",
+                        width,
+                        targets.len()
+                    ),
+                    select.as_ref(),
+                    raw_sql,
+                ));
+            }
+            physical.open()?;
+            while let Some(row) = physical.next()? {
+                let mut values: Vec<OwnValue> = defaults(schema);
+                for (index, target) in targets.iter().enumerate() {
+                    values[*target] = row.get(index)?.clone();
+                }
+                rows.push(check_row(schema, values, select.as_ref(), raw_sql)?);
+            }
+            physical.close()?;
+        }
+    }
+
+    let count: usize = rows.len();
+    find_table_mut(context, table)?.insert(rows);
+    Ok(count)
+}
+
+/// 执行 UPDATE，返回修改的行数
+/// SET 中的表达式都基于修改前的行计算，所有行校验通过后才写入表中
+pub fn update(
+    context: &mut dyn Context,
+    update: &Update,
+    raw_sql: &str,
+    params: &[OwnValue],
+) -> Result<usize, ExecuteError> {
+    let table: &str = &update.table.value;
+    let data_source: &dyn DataSource = find_table(&*context, &update.table, raw_sql)?;
+    let schema: &Schema = data_source.schema();
+    let mut scope: Scope = Scope::new();
+    scope.push_table(Some(context.current_namespace()), schema);
+    let binder: Binder = Binder::new(&*context, raw_sql).with_params(params);
+
+    let wheres: Option<BoundExpression> =
+        bind_where(&binder, update.wheres.as_ref(), &scope, raw_sql)?;
+
+    // (列下标，新值表达式)
+    let mut assignments: Vec<(usize, BoundExpression)> = vec![];
+    for assignment in update.assignments.iter() {
+        let name: &str = &assignment.column.value;
+        let Some(index) = schema.columns.iter().position(|c| c.name.as_ref() == name) else {
+            return Err(ExecuteError::located(
+                ErrorCode::UndefinedColumn,
+                format!("unknown column {}", name),
+                assignment,
+                raw_sql,
+            ));
+        };
+        if assignments.iter().any(|(i, _)| *i == index) {
+            return Err(ExecuteError::located(
+                ErrorCode::DuplicateColumn,
+                format!("column {} assigned more than once", name),
+                assignment,
+                raw_sql,
+            ));
+        }
+        let value: BoundExpression = binder.bind_expression(&assignment.value, &scope)?;
+        if value.contains_aggregate() {
+            return Err(ExecuteError::located(
+                ErrorCode::GroupingError,
+                "aggregate functions are not allowed in UPDATE",
+                &assignment.value,
+                raw_sql,
+            ));
+        }
+        assignments.push((index, value));
+    }
+
+    let mut rows: Vec<(usize, SimpleMemoryRow)> = vec![];
+    for (position, row) in data_source.read().enumerate() {
+        if !matches(wheres.as_ref(), row)? {
+            continue;
+        }
+        let mut values: Vec<OwnValue> = (0..schema.columns.len())
+            .map(|index| row.get(index).cloned())
+            .collect::<Result<_, _>>()?;
+        for ((index, value), assignment) in assignments.iter().zip(update.assignments.iter()) {
+            let value: OwnValue = value.evaluate(row)?;
+            values[*index] = match schema.columns[*index].check_value(value) {
+                Ok(value) => value,
+                Err(error) => return Err(error.at(assignment, raw_sql)),
+            };
+        }
+        rows.push((position, SimpleMemoryRow::new(values)));
+    }
+
+    let count: usize = rows.len();
+    find_table_mut(context, table)?.update(rows);
+    Ok(count)
+}
+
+/// 执行 DELETE，返回删除的行数
+pub fn delete(
+    context: &mut dyn Context,
+    delete: &Delete,
+    raw_sql: &str,
+    params: &[OwnValue],
+) -> Result<usize, ExecuteError> {
+    let data_source: &dyn DataSource = find_table(&*context, &delete.table, raw_sql)?;
+    let mut scope: Scope = Scope::new();
+    scope.push_table(Some(context.current_namespace()), data_source.schema());
+    let binder: Binder = Binder::new(&*context, raw_sql).with_params(params);
+
+    let wheres: Option<BoundExpression> =
+        bind_where(&binder, delete.wheres.as_ref(), &scope, raw_sql)?;
+
+    let mut positions: Vec<usize> = vec![];
+    for (position, row) in data_source.read().enumerate() {
+        if matches(wheres.as_ref(), row)? {
+            positions.push(position);
+        }
+    }
+
+    find_table_mut(context, &delete.table.value)?.delete(&positions);
+    Ok(positions.len())
+}
+
+fn find_table<'a>(
+    context: &'a dyn Context,
+    table: &SingleIdentifier,
+    raw_sql: &str,
+) -> Result<&'a dyn DataSource, ExecuteError> {
+    match context.find_table(&table.value) {
+        Some(data_source) => Ok(data_source),
+        None => Err(ExecuteError::located(
+            ErrorCode::UndefinedTable,
+            format!("table {} not found", table.value),
+            table,
+            raw_sql,
+        )),
+    }
+}
+
+fn find_table_mut<'a>(
+    context: &'a mut dyn Context,
+    table: &str,
+) -> Result<&'a mut dyn MutableDataSource, ExecuteError> {
+    match context.find_table_mut(table) {
+        Some(data_source) => Ok(data_source),
+        None => Err(ExecuteError::new(
+            ErrorCode::UndefinedTable,
+            format!("table {} not found", table),
+        )),
+    }
+}
+
+/// 绑定 WHERE 条件，条件中不能包含聚合函数
+fn bind_where(
+    binder: &Binder,
+    wheres: Option<&Expression>,
+    scope: &Scope,
+    raw_sql: &str,
+) -> Result<Option<BoundExpression>, ExecuteError> {
+    let Some(wheres) = wheres else {
+        return Ok(None);
+    };
+    let bound: BoundExpression = binder.bind_predicate(wheres, scope, "WHERE")?;
+    if bound.contains_aggregate() {
+        return Err(ExecuteError::located(
+            ErrorCode::GroupingError,
+            "aggregate functions are not allowed in WHERE",
+            wheres,
+            raw_sql,
+        ));
+    }
+    Ok(Some(bound))
+}
+
+/// 没有 WHERE 时匹配所有行，条件为 NULL 的行不匹配
+fn matches(wheres: Option<&BoundExpression>, row: &dyn Row) -> Result<bool, ExecuteError> {
+    match wheres {
+        Some(wheres) => wheres.evaluate_predicate(row),
+        None => Ok(true),
+    }
+}
+
+/// 插入时没有指定的列取列的默认值，没有默认值时为 NULL
+fn defaults(schema: &Schema) -> Vec<OwnValue> {
+    schema
+        .columns
+        .iter()
+        .map(|column| column.default.clone().unwrap_or(OwnValue::Null))
+        .collect()
+}
+
+/// 按表的列定义校验一行的值，出错时报告 node 的位置
+fn check_row(
+    schema: &Schema,
+    values: Vec<OwnValue>,
+    node: &dyn WithLocation,
+    raw_sql: &str,
+) -> Result<SimpleMemoryRow, ExecuteError> {
+    let mut checked: Vec<OwnValue> = Vec::with_capacity(values.len());
+    for (column, value) in schema.columns.iter().zip(values) {
+        match column.check_value(value) {
+            Ok(value) => checked.push(value),
+            Err(error) => return Err(error.at(node, raw_sql)),
+        }
+    }
+    Ok(SimpleMemoryRow::new(checked))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::{
+            context::{Context, SimpleMemoryContext},
+            ddl,
+            error::ExecuteError,
+            planner::{optimizer::Optimizer, physical::PhysicalPlanner},
+            types::{int32::Int32, OwnValue},
+        },
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn execute(
+        context: &mut SimpleMemoryContext,
+        sql: &str,
+        params: &[OwnValue],
+    ) -> Result<usize, ExecuteError> {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        match &statements.statements[0] {
+            Statement::CreateTable(create_table) => {
+                ddl::create_table(context, create_table, sql).map(|_| 0)
+            }
+            Statement::Insert(insert) => super::insert(
+                context,
+                &Optimizer::new(),
+                &PhysicalPlanner::new(),
+                insert,
+                sql,
+                params,
+            ),
+            Statement::Update(update) => super::update(context, update, sql, params),
+            Statement::Delete(delete) => super::delete(context, delete, sql, params),
+            _ => panic!("expect create table or dml"),
+        }
+    }
+
+    fn table(context: &SimpleMemoryContext, name: &str) -> Vec<String> {
+        let table = context.find_table(name).unwrap();
+        table
+            .read()
+            .map(|row| row.to_string(table.schema()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_values() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(
+            &mut context,
+            "CREATE TABLE stu (name VARCHAR(2) NOT NULL, age INT, score DOUBLE)",
+            &[],
+        )
+        .unwrap();
+        let count: usize = execute(
+            &mut context,
+            "INSERT INTO stu VALUES ('张三', 18, 90), ('李四', 1 + 1, 80.5)",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        let count: usize = execute(
+            &mut context,
+            "INSERT INTO stu (score, name) VALUES (?, 'a')",
+            &[OwnValue::Int32(Int32::new(60))],
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            table(&context, "stu"),
+            vec![
+                "[\"张三\", 18, 90]",
+                "[\"李四\", 2, 80.5]",
+                "[\"a\", NULL, 60]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_select() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(&mut context, "CREATE TABLE stu (name TEXT, age INT)", &[]).unwrap();
+        execute(
+            &mut context,
+            "INSERT INTO stu VALUES ('a', 1), ('b', 2), ('c', 3)",
+            &[],
+        )
+        .unwrap();
+        let count: usize = execute(
+            &mut context,
+            "INSERT INTO stu (age, name) SELECT age * 10, name FROM stu WHERE age > 1",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            table(&context, "stu"),
+            vec![
+                "[\"a\", 1]",
+                "[\"b\", 2]",
+                "[\"c\", 3]",
+                "[\"b\", 20]",
+                "[\"c\", 30]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_error() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(
+            &mut context,
+            "CREATE TABLE stu (name VARCHAR(2) NOT NULL, age INT)",
+            &[],
+        )
+        .unwrap();
+        for (sql, message) in [
+            (
+                "INSERT INTO tea VALUES (1)",
+                "table tea not found in Ln 1, Col 13",
+            ),
+            (
+                "INSERT INTO stu (name, sex) VALUES ('a', 1)",
+                "unknown column sex in Ln 1, Col 24",
+            ),
+            (
+                "INSERT INTO stu (name, name) VALUES ('a', 'b')",
+                "duplicate column name name in Ln 1, Col 24",
+            ),
+            (
+                "INSERT INTO stu VALUES ('a')",
+                "VALUES row has 1 values but 2 columns are expected",
+            ),
+            (
+                "INSERT INTO stu (age) VALUES (1)",
+                "column name must not be NULL in Ln 1, Col 31",
+            ),
+            (
+                "INSERT INTO stu VALUES ('a', 1), ('abc', 2)",
+                "value too long for column name of type Varchar(2) in Ln 1, Col 35",
+            ),
+            (
+                "INSERT INTO stu VALUES ('a', 'b')",
+                "value 'b' does not match column age of type Int32",
+            ),
+            (
+                "INSERT INTO stu SELECT name FROM stu",
+                "SELECT returns 1 columns but 2 columns are expected",
+            ),
+        ] {
+            let error: ExecuteError = execute(&mut context, sql, &[]).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+        // 出错的语句不会写入任何一行
+        assert!(table(&context, "stu").is_empty());
+    }
+
+    #[test]
+    fn test_update() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(
+            &mut context,
+            "CREATE TABLE stu (name VARCHAR(2) NOT NULL, age INT, score DOUBLE)",
+            &[],
+        )
+        .unwrap();
+        execute(
+            &mut context,
+            "INSERT INTO stu VALUES ('a', 18, 60), ('b', 20, 70), ('c', ?, 80)",
+            &[OwnValue::Null],
+        )
+        .unwrap();
+
+        // SET 中的表达式使用修改前的值，age 为 NULL 的行不满足条件
+        let count: usize = execute(
+            &mut context,
+            "UPDATE stu SET score = age, age = age + 1 WHERE age >= ?",
+            &[OwnValue::Int32(Int32::new(18))],
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            table(&context, "stu"),
+            vec!["[\"a\", 19, 18]", "[\"b\", 21, 20]", "[\"c\", NULL, 80]"]
+        );
+
+        assert_eq!(
+            execute(&mut context, "UPDATE stu SET name = 'd'", &[]).unwrap(),
+            3
+        );
+
+        for (sql, message) in [
+            ("UPDATE tea SET a = 1", "table tea not found in Ln 1, Col 8"),
+            (
+                "UPDATE stu SET sex = 1",
+                "unknown column sex in Ln 1, Col 16",
+            ),
+            (
+                "UPDATE stu SET age = 1, age = 2",
+                "column age assigned more than once in Ln 1, Col 25",
+            ),
+            (
+                "UPDATE stu SET age = COUNT(*)",
+                "aggregate functions are not allowed in UPDATE",
+            ),
+            (
+                "UPDATE stu SET age = 1 WHERE SUM(age) > 1",
+                "aggregate functions are not allowed in WHERE",
+            ),
+            (
+                "UPDATE stu SET name = 'abc' WHERE age = 19",
+                "value too long for column name of type Varchar(2) in Ln 1, Col 16",
+            ),
+            (
+                "UPDATE stu SET age = 1, name = ? WHERE age > 20",
+                "column name must not be NULL",
+            ),
+        ] {
+            let error: ExecuteError = execute(&mut context, sql, &[OwnValue::Null]).unwrap_err();
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+        // 出错的语句不会修改任何一行
+        assert_eq!(
+            table(&context, "stu"),
+            vec!["[\"d\", 19, 18]", "[\"d\", 21, 20]", "[\"d\", NULL, 80]"]
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        execute(&mut context, "CREATE TABLE t (a INT)", &[]).unwrap();
+        execute(
+            &mut context,
+            "INSERT INTO t VALUES (1), (2), (3), (4), (?)",
+            &[OwnValue::Null],
+        )
+        .unwrap();
+
+        assert_eq!(
+            execute(&mut context, "DELETE FROM t WHERE a / 2 * 2 = a", &[]).unwrap(),
+            2
+        );
+        assert_eq!(table(&context, "t"), vec!["[1]", "[3]", "[NULL]"]);
+
+        let error: ExecuteError =
+            execute(&mut context, "DELETE FROM t WHERE b = 1", &[]).unwrap_err();
+        assert!(
+            error.to_string().starts_with("unknown column b"),
+            "{}",
+            error
+        );
+
+        assert_eq!(execute(&mut context, "DELETE FROM t", &[]).unwrap(), 3);
+        assert!(table(&context, "t").is_empty());
+    }
+}
@@ -1,15 +1,121 @@
 use std::{error::Error, fmt::Display};
 
+use crate::{
+    error::ErrorCode,
+    executor::types::DataType,
+    sql::parser::ast::leaf::{Location, WithLocation},
+};
+
+/// ExecuteError 语义分析、计划和执行时的错误
+/// location 是出错的 AST 节点在原始 SQL 中的位置，source 是引起该错误的底层错误
 #[derive(Debug)]
 pub struct ExecuteError {
+    pub code: ErrorCode,
     pub message: String,
-    // TODO location
+    pub location: Option<Location>,
+    near: Option<String>, // 位置附近的 SQL 文本，用于输出
+    source: Option<Box<dyn Error + Send + Sync + 'static>>,
+}
+
+impl ExecuteError {
+    pub fn new<Msg: Into<String>>(code: ErrorCode, message: Msg) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            location: None,
+            near: None,
+            source: None,
+        }
+    }
+
+    /// 附带 node 在原始 SQL 中位置的错误
+    pub fn located<Msg: Into<String>>(
+        code: ErrorCode,
+        message: Msg,
+        node: &dyn WithLocation,
+        raw_sql: &str,
+    ) -> Self {
+        Self::new(code, message).at(node, raw_sql)
+    }
+
+    /// 将错误定位到 node，已经有位置的错误保持原来更精确的位置
+    pub fn at(mut self, node: &dyn WithLocation, raw_sql: &str) -> Self {
+        if self.location.is_none() {
+            self.location = Some(node.location().clone());
+            self.near = Some(node.locate(raw_sql));
+        }
+        self
+    }
+
+    /// 设置引起该错误的底层错误
+    pub fn with_source(
+        mut self,
+        source: impl Into<Box<dyn Error + Send + Sync + 'static>>,
+    ) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// 按类型读取列的值时，列的实际类型与期望的类型不符
+    pub fn type_mismatch(column: impl Display, expected: &DataType, actual: &DataType) -> Self {
+        Self::new(
+            ErrorCode::DatatypeMismatch,
+            format!(
+                "type mismatch for column {}: expected {}, found {}",
+                column, expected, actual
+            ),
+        )
+    }
 }
 
 impl Display for ExecuteError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        match &self.near {
+            Some(near) => write!(f, "{} in {}", self.message, near),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ExecuteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn Error + 'static))
     }
 }
 
-impl Error for ExecuteError {}
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{error::ErrorCode, sql::parser::ast::leaf::Location};
+
+    use super::ExecuteError;
+
+    fn location(column_number: usize) -> Location {
+        Location {
+            line_number: 1,
+            column_number,
+            offset: column_number - 1,
+        }
+    }
+
+    #[test]
+    fn located_with_source() {
+        let sql: &str = "SELECT a FROM t";
+        let cause = std::io::Error::other("disk full");
+        let error: ExecuteError = ExecuteError::new(ErrorCode::IoError, "spill file error")
+            .with_source(cause)
+            .at(&location(8), sql)
+            .at(&location(1), sql);
+        // 保留最先定位的位置
+        assert_eq!(error.location, Some(location(8)));
+        assert_eq!(
+            error.to_string(),
+            "spill file error in Ln 1, Col 8 near \"SELECT a FROM t\""
+        );
+        assert_eq!(error.source().unwrap().to_string(), "disk full");
+        assert_eq!(error.code.sqlstate(), "58030");
+    }
+}
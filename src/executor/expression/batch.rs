@@ -0,0 +1,429 @@
+use std::borrow::Cow;
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        meta::batch::{Batch, Bitmap, ColumnData, ColumnVector},
+        types::{flout64::Float64, OwnValue},
+    },
+    sql::parser::ast::expression::BinaryOperator,
+};
+
+use super::{
+    eval::{boolean, logical},
+    BoundBinary, BoundExpression, BoundUnary, FunctionCall, ScalarFunction,
+};
+
+impl BoundExpression {
+    /// 在一批数据上按列计算表达式，结果与逐行调用 evaluate 相同
+    /// 列引用直接借用输入的列，不复制数据
+    pub fn evaluate_batch<'b>(
+        &self,
+        batch: &'b Batch,
+    ) -> Result<Cow<'b, ColumnVector>, ExecuteError> {
+        match self {
+            BoundExpression::Literal(literal) => Ok(Cow::Owned(ColumnVector::repeat(
+                &literal.data_type,
+                &literal.value,
+                batch.len(),
+            )?)),
+            BoundExpression::Column(column) => Ok(Cow::Borrowed(&batch.columns[column.index])),
+            BoundExpression::Binary(binary) => binary.evaluate_batch(batch).map(Cow::Owned),
+            BoundExpression::Unary(unary) => unary.evaluate_batch(batch).map(Cow::Owned),
+            BoundExpression::Aggregate(aggregate) => Err(ExecuteError::new(
+                ErrorCode::GroupingError,
+                format!("aggregate function {} is not allowed here", aggregate),
+            )),
+            BoundExpression::Function(call) => call.evaluate_batch(batch).map(Cow::Owned),
+        }
+    }
+
+    /// 在一批数据上计算谓词，返回结果为真的行组成的位图，NULL 视为 false
+    pub fn evaluate_predicate_batch(&self, batch: &Batch) -> Result<Bitmap, ExecuteError> {
+        let column: Cow<ColumnVector> = self.evaluate_batch(batch)?;
+        let mut selection: Bitmap = Bitmap::new(batch.len());
+        for index in 0..batch.len() {
+            selection.set(index, truth_at(&column, index) == Some(true));
+        }
+        Ok(selection)
+    }
+}
+
+/// 列中 index 处的值的真值，与 eval::truth 相同
+fn truth_at(column: &ColumnVector, index: usize) -> Option<bool> {
+    if column.is_null(index) {
+        return None;
+    }
+    match &column.data {
+        ColumnData::Boolean(data) => Some(data[index]),
+        _ => None,
+    }
+}
+
+/// 对每个不为 NULL 的行计算 f，NULL 行填默认值
+fn kernel<T: Default>(
+    nulls: &Bitmap,
+    f: impl Fn(usize) -> Result<T, ExecuteError>,
+) -> Result<Vec<T>, ExecuteError> {
+    let mut data: Vec<T> = Vec::with_capacity(nulls.len());
+    for index in 0..nulls.len() {
+        data.push(if nulls.get(index) {
+            T::default()
+        } else {
+            f(index)?
+        });
+    }
+    Ok(data)
+}
+
+impl BoundBinary {
+    fn evaluate_batch(&self, batch: &Batch) -> Result<ColumnVector, ExecuteError> {
+        if matches!(
+            self.operator,
+            BinaryOperator::AND(_) | BinaryOperator::OR(_)
+        ) {
+            return self.logical_batch(batch);
+        }
+
+        let left: Cow<ColumnVector> = self.left.evaluate_batch(batch)?;
+        let right: Cow<ColumnVector> = self.right.evaluate_batch(batch)?;
+        let nulls: Bitmap = left.nulls.or(&right.nulls);
+        let arithmetic: bool = matches!(
+            self.operator,
+            BinaryOperator::Plus(_)
+                | BinaryOperator::Minus(_)
+                | BinaryOperator::Multiply(_)
+                | BinaryOperator::Divide(_)
+                | BinaryOperator::Modulo(_)
+        );
+        // 同类型的操作数按类型逐列计算，其余情况逐个值计算
+        let data: ColumnData = match (&left.data, &right.data) {
+            (ColumnData::Int32(l), ColumnData::Int32(r)) if arithmetic => {
+                ColumnData::Int32(kernel(&nulls, |i| self.int_arithmetic(l[i], r[i]))?)
+            }
+            (ColumnData::Int32(l), ColumnData::Int32(r)) => {
+                ColumnData::Boolean(kernel(&nulls, |i| Ok(self.test(l[i].cmp(&r[i]))))?)
+            }
+            (ColumnData::Float64(l), ColumnData::Float64(r)) if arithmetic => {
+                ColumnData::Float64(kernel(&nulls, |i| self.float_arithmetic(l[i], r[i]))?)
+            }
+            (ColumnData::Float64(l), ColumnData::Float64(r)) => {
+                ColumnData::Boolean(kernel(&nulls, |i| {
+                    Ok(self.test(Float64::new(l[i]).cmp(&Float64::new(r[i]))))
+                })?)
+            }
+            (ColumnData::String(l), ColumnData::String(r)) if !arithmetic => {
+                ColumnData::Boolean(kernel(&nulls, |i| Ok(self.test(l[i].cmp(&r[i]))))?)
+            }
+            _ => {
+                let mut column: ColumnVector =
+                    ColumnVector::with_capacity(&self.data_type, batch.len());
+                for index in 0..batch.len() {
+                    column.push(self.apply(&left.get(index), &right.get(index))?)?;
+                }
+                return Ok(column);
+            }
+        };
+        Ok(ColumnVector { data, nulls })
+    }
+
+    /// AND、OR 只在左侧不能决定结果的行上计算右侧，与逐行求值的短路行为一致
+    fn logical_batch(&self, batch: &Batch) -> Result<ColumnVector, ExecuteError> {
+        let decisive: Option<bool> = Some(matches!(self.operator, BinaryOperator::OR(_)));
+        let left: Cow<ColumnVector> = self.left.evaluate_batch(batch)?;
+        let pending: Vec<usize> = (0..batch.len())
+            .filter(|i| truth_at(&left, *i) != decisive)
+            .collect();
+        let taken: Batch;
+        let right: Cow<ColumnVector> = if pending.len() == batch.len() {
+            self.right.evaluate_batch(batch)?
+        } else {
+            taken = batch.take(&pending);
+            self.right.evaluate_batch(&taken)?
+        };
+
+        let mut column: ColumnVector = ColumnVector::with_capacity(&self.data_type, batch.len());
+        let mut next: usize = 0; // right 中下一个值对应 pending[next]
+        for index in 0..batch.len() {
+            let l: Option<bool> = truth_at(&left, index);
+            let value: Option<bool> = if l == decisive {
+                l
+            } else {
+                next += 1;
+                logical(&self.operator, l, truth_at(&right, next - 1))
+            };
+            column.push(boolean(value))?;
+        }
+        Ok(column)
+    }
+}
+
+impl BoundUnary {
+    fn evaluate_batch(&self, batch: &Batch) -> Result<ColumnVector, ExecuteError> {
+        let input: Cow<ColumnVector> = self.expression.evaluate_batch(batch)?;
+        let mut column: ColumnVector = ColumnVector::with_capacity(&self.data_type, batch.len());
+        for index in 0..batch.len() {
+            column.push(self.apply(input.get(index))?)?;
+        }
+        Ok(column)
+    }
+}
+
+impl FunctionCall {
+    fn evaluate_batch(&self, batch: &Batch) -> Result<ColumnVector, ExecuteError> {
+        let mut column: ColumnVector = ColumnVector::with_capacity(&self.data_type, batch.len());
+        match self.function {
+            ScalarFunction::Coalesce | ScalarFunction::IfNull => {
+                for value in self.coalesce_batch(batch)? {
+                    column.push(value)?;
+                }
+            }
+            ScalarFunction::NullIf => {
+                let value: Cow<ColumnVector> = self.arguments[0].evaluate_batch(batch)?;
+                let other: Cow<ColumnVector> = self.arguments[1].evaluate_batch(batch)?;
+                for index in 0..batch.len() {
+                    column.push(self.null_if(value.get(index), &other.get(index))?)?;
+                }
+            }
+        }
+        Ok(column)
+    }
+
+    /// 后面的参数只在前面的参数都为 NULL 的行上计算，与逐行求值的行为一致
+    fn coalesce_batch(&self, batch: &Batch) -> Result<Vec<OwnValue>, ExecuteError> {
+        let mut values: Vec<OwnValue> = vec![OwnValue::Null; batch.len()];
+        let mut pending: Vec<usize> = (0..batch.len()).collect();
+        for argument in self.arguments.iter() {
+            if pending.is_empty() {
+                break;
+            }
+            let taken: Batch;
+            let column: Cow<ColumnVector> = if pending.len() == batch.len() {
+                argument.evaluate_batch(batch)?
+            } else {
+                taken = batch.take(&pending);
+                argument.evaluate_batch(&taken)?
+            };
+            let mut remain: Vec<usize> = vec![];
+            for (position, index) in pending.into_iter().enumerate() {
+                if column.is_null(position) {
+                    remain.push(index);
+                } else {
+                    values[index] = self.cast(column.get(position));
+                }
+            }
+            pending = remain;
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::{
+            expression::{
+                BoundBinary, BoundExpression, BoundLiteral, BoundUnary, ColumnRef, FunctionCall,
+                ScalarFunction,
+            },
+            meta::{
+                batch::Batch,
+                column::Column,
+                row::{Row, SimpleMemoryRow},
+                schema::Schema,
+            },
+            types::{flout64::Float64, int32::Int32, DataType, OwnValue},
+        },
+        sql::parser::ast::{
+            expression::{BinaryOperator, UnaryOperator},
+            leaf::Location,
+        },
+    };
+
+    fn location() -> Location {
+        Location {
+            line_number: 1,
+            column_number: 1,
+            offset: 0,
+        }
+    }
+
+    fn column(index: usize, data_type: DataType) -> BoundExpression {
+        BoundExpression::Column(ColumnRef {
+            table: "t".into(),
+            name: format!("c{}", index).into(),
+            index,
+            data_type,
+            nullable: true,
+        })
+    }
+
+    fn int(value: i32) -> BoundExpression {
+        BoundExpression::Literal(BoundLiteral {
+            value: OwnValue::Int32(Int32::new(value)),
+            data_type: DataType::Int32,
+        })
+    }
+
+    fn binary(
+        left: BoundExpression,
+        operator: BinaryOperator,
+        right: BoundExpression,
+    ) -> BoundExpression {
+        let data_type: DataType = match (left.data_type(), &operator) {
+            (DataType::Float64, BinaryOperator::Plus(_)) => DataType::Float64,
+            (
+                _,
+                BinaryOperator::Plus(_)
+                | BinaryOperator::Multiply(_)
+                | BinaryOperator::Divide(_)
+                | BinaryOperator::Modulo(_),
+            ) => DataType::Int32,
+            _ => DataType::Boolean,
+        };
+        BoundExpression::Binary(BoundBinary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+            data_type,
+        })
+    }
+
+    /// c0 Int32, c1 Float64, c2 String
+    fn rows() -> Vec<SimpleMemoryRow> {
+        let int = |v: i32| OwnValue::Int32(Int32::new(v));
+        let float = |v: f64| OwnValue::Float64(Float64::new(v));
+        let string = |s: &str| OwnValue::String(s.into());
+        vec![
+            SimpleMemoryRow::new(vec![int(1), float(0.5), string("a")]),
+            SimpleMemoryRow::new(vec![int(0), OwnValue::Null, string("b")]),
+            SimpleMemoryRow::new(vec![OwnValue::Null, float(2.0), OwnValue::Null]),
+            SimpleMemoryRow::new(vec![int(-3), float(-1.0), string("c")]),
+        ]
+    }
+
+    fn batch() -> Batch {
+        let column = |name: &str, data_type: DataType| Column {
+            name: name.into(),
+            data_type,
+            nullable: true,
+            default: None,
+        };
+        let mut batch: Batch = Batch::new(&Schema {
+            name: "t".into(),
+            columns: vec![
+                column("c0", DataType::Int32),
+                column("c1", DataType::Float64),
+                column("c2", DataType::String),
+            ]
+            .into_boxed_slice(),
+        });
+        for row in rows() {
+            batch.push_row(&row).unwrap();
+        }
+        batch
+    }
+
+    /// 按列计算的结果与逐行计算相同
+    fn check(expression: &BoundExpression) {
+        let batch: Batch = batch();
+        let column = expression.evaluate_batch(&batch).unwrap();
+        for (index, row) in rows().iter().enumerate() {
+            assert_eq!(
+                column.get(index),
+                expression.evaluate(row as &dyn Row).unwrap(),
+                "{} at row {}",
+                expression,
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn batch_same_as_row() {
+        let c0 = || column(0, DataType::Int32);
+        let c1 = || column(1, DataType::Float64);
+        let c2 = || column(2, DataType::String);
+        check(&binary(c0(), BinaryOperator::Multiply(location()), int(2)));
+        check(&binary(
+            c0(),
+            BinaryOperator::GreaterThan(location()),
+            int(0),
+        ));
+        check(&binary(c1(), BinaryOperator::Plus(location()), c0()));
+        check(&binary(c0(), BinaryOperator::Modulo(location()), int(2)));
+        check(&binary(
+            c1(),
+            BinaryOperator::LessThanOrEqual(location()),
+            c1(),
+        ));
+        check(&binary(
+            c2(),
+            BinaryOperator::NotEqual(location()),
+            BoundExpression::Literal(BoundLiteral {
+                value: OwnValue::String("b".into()),
+                data_type: DataType::String,
+            }),
+        ));
+        let positive =
+            |e: BoundExpression| binary(e, BinaryOperator::GreaterThan(location()), int(0));
+        check(&binary(
+            positive(c0()),
+            BinaryOperator::AND(location()),
+            positive(c1()),
+        ));
+        check(&binary(
+            positive(c1()),
+            BinaryOperator::OR(location()),
+            positive(c0()),
+        ));
+        check(&BoundExpression::Unary(BoundUnary {
+            operator: UnaryOperator::NOT(location()),
+            expression: Box::new(positive(c0())),
+            data_type: DataType::Boolean,
+        }));
+        check(&BoundExpression::Unary(BoundUnary {
+            operator: UnaryOperator::IsNull(location()),
+            expression: Box::new(c2()),
+            data_type: DataType::Boolean,
+        }));
+        check(&BoundExpression::Function(FunctionCall {
+            function: ScalarFunction::Coalesce,
+            arguments: vec![c1(), c0(), int(9)].into_boxed_slice(),
+            data_type: DataType::Float64,
+        }));
+        check(&BoundExpression::Function(FunctionCall {
+            function: ScalarFunction::NullIf,
+            arguments: vec![c0(), int(0)].into_boxed_slice(),
+            data_type: DataType::Int32,
+        }));
+    }
+
+    #[test]
+    fn batch_short_circuit() {
+        // c0 <> 0 AND 6 / c0 > 1：c0 为 0 的行不计算除法
+        let c0 = || column(0, DataType::Int32);
+        let predicate: BoundExpression = binary(
+            binary(c0(), BinaryOperator::NotEqual(location()), int(0)),
+            BinaryOperator::AND(location()),
+            binary(
+                binary(int(6), BinaryOperator::Divide(location()), c0()),
+                BinaryOperator::GreaterThan(location()),
+                int(1),
+            ),
+        );
+        check(&predicate);
+        let selection = predicate.evaluate_predicate_batch(&batch()).unwrap();
+        assert_eq!(
+            (0..4).map(|i| selection.get(i)).collect::<Vec<bool>>(),
+            vec![true, false, false, false]
+        );
+
+        let e: BoundExpression = binary(int(6), BinaryOperator::Divide(location()), c0());
+        assert_eq!(
+            e.evaluate_batch(&batch()).unwrap_err().to_string(),
+            "division by zero"
+        );
+    }
+}
@@ -0,0 +1,562 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        meta::row::Row,
+        types::{
+            decimal::Decimal, flout64::Float64, int32::Int32, int64::Int64, DataType, OwnValue,
+        },
+    },
+    sql::parser::ast::expression::{BinaryOperator, UnaryOperator},
+};
+
+use super::{BoundBinary, BoundExpression, BoundUnary, FunctionCall, ScalarFunction};
+
+impl BoundExpression {
+    /// 在一行数据上计算表达式
+    /// 遵循 SQL 三值逻辑：除 AND、OR、IS [NOT] NULL 和处理 NULL 的函数外，
+    /// 任一操作数为 NULL 则结果为 NULL
+    pub fn evaluate(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        match self {
+            BoundExpression::Literal(literal) => Ok(literal.value.clone()),
+            BoundExpression::Column(column) => row.get(column.index).cloned(),
+            BoundExpression::Binary(binary) => binary.evaluate(row),
+            BoundExpression::Unary(unary) => unary.evaluate(row),
+            BoundExpression::Aggregate(aggregate) => Err(ExecuteError::new(
+                ErrorCode::GroupingError,
+                format!("aggregate function {} is not allowed here", aggregate),
+            )),
+            BoundExpression::Function(call) => call.evaluate(row),
+        }
+    }
+
+    /// 计算谓词，只有结果为真时返回 true，NULL 视为 false
+    pub fn evaluate_predicate(&self, row: &dyn Row) -> Result<bool, ExecuteError> {
+        Ok(truth(&self.evaluate(row)?) == Some(true))
+    }
+}
+
+/// 三值逻辑的真值，NULL 为 None，binder 保证条件的类型是 Boolean
+pub fn truth(value: &OwnValue) -> Option<bool> {
+    match value {
+        OwnValue::Boolean(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// 三值逻辑的 AND、OR
+pub(super) fn logical(
+    operator: &BinaryOperator,
+    left: Option<bool>,
+    right: Option<bool>,
+) -> Option<bool> {
+    match (operator, left, right) {
+        (BinaryOperator::AND(_), Some(false), _) | (BinaryOperator::AND(_), _, Some(false)) => {
+            Some(false)
+        }
+        (BinaryOperator::AND(_), Some(true), Some(true)) => Some(true),
+        (BinaryOperator::OR(_), Some(true), _) | (BinaryOperator::OR(_), _, Some(true)) => {
+            Some(true)
+        }
+        (BinaryOperator::OR(_), Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+pub(super) fn boolean(value: Option<bool>) -> OwnValue {
+    match value {
+        Some(value) => OwnValue::Boolean(value),
+        None => OwnValue::Null,
+    }
+}
+
+/// 比较两个非 NULL 的值，数值类型之间会做数值提升，浮点数按全序比较（NaN 最大），类型不兼容时返回 None
+pub fn compare(left: &OwnValue, right: &OwnValue) -> Option<Ordering> {
+    match (left, right) {
+        (OwnValue::Boolean(l), OwnValue::Boolean(r)) => Some(l.cmp(r)),
+        (OwnValue::String(l), OwnValue::String(r)) => Some(l.cmp(r)),
+        (OwnValue::Date(l), OwnValue::Date(r)) => Some(l.cmp(r)),
+        (OwnValue::Time(l), OwnValue::Time(r)) => Some(l.cmp(r)),
+        (OwnValue::Timestamp(l), OwnValue::Timestamp(r)) => Some(l.cmp(r)),
+        (OwnValue::Interval(l), OwnValue::Interval(r)) => Some(l.cmp(r)),
+        (OwnValue::Blob(l), OwnValue::Blob(r)) => Some(l.cmp(r)),
+        _ => match promote(left, right)? {
+            Promoted::Int32(l, r) => Some(l.cmp(&r)),
+            Promoted::Int64(l, r) => Some(l.cmp(&r)),
+            Promoted::Decimal(l, r) => Some(l.cmp(&r)),
+            Promoted::Float64(l, r) => Some(l.cmp(&r)),
+        },
+    }
+}
+
+/// 数值提升后类型相同的两个操作数
+enum Promoted {
+    Int32(Int32, Int32),
+    Int64(Int64, Int64),
+    Decimal(Decimal, Decimal),
+    Float64(Float64, Float64),
+}
+
+/// 数值提升：按 Int32 < Int64 < Decimal < Float64 的顺序转换为两者中较大的类型，不是数值时返回 None
+fn promote(left: &OwnValue, right: &OwnValue) -> Option<Promoted> {
+    let rank = |value: &OwnValue| value.data_type().numeric_rank();
+    match rank(left)?.max(rank(right)?) {
+        0 => match (left, right) {
+            (OwnValue::Int32(l), OwnValue::Int32(r)) => Some(Promoted::Int32(*l, *r)),
+            _ => None,
+        },
+        1 => Some(Promoted::Int64(left.to_int64()?, right.to_int64()?)),
+        2 => Some(Promoted::Decimal(left.to_decimal()?, right.to_decimal()?)),
+        _ => Some(Promoted::Float64(left.to_float64()?, right.to_float64()?)),
+    }
+}
+
+impl BoundBinary {
+    fn evaluate(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        match &self.operator {
+            BinaryOperator::AND(_) => return self.and(row),
+            BinaryOperator::OR(_) => return self.or(row),
+            _ => {}
+        }
+
+        let left: OwnValue = self.left.evaluate(row)?;
+        let right: OwnValue = self.right.evaluate(row)?;
+        self.apply(&left, &right)
+    }
+
+    /// 在两个操作数上计算除 AND、OR 外的运算
+    pub(super) fn apply(
+        &self,
+        left: &OwnValue,
+        right: &OwnValue,
+    ) -> Result<OwnValue, ExecuteError> {
+        if *left == OwnValue::Null || *right == OwnValue::Null {
+            return Ok(OwnValue::Null);
+        }
+        match &self.operator {
+            BinaryOperator::Plus(_)
+            | BinaryOperator::Minus(_)
+            | BinaryOperator::Multiply(_)
+            | BinaryOperator::Divide(_)
+            | BinaryOperator::Modulo(_) => self.arithmetic(left, right),
+            _ => Ok(boolean(Some(self.test(self.compare(left, right)?)))),
+        }
+    }
+
+    /// 比较运算符是否接受比较的结果
+    pub(super) fn test(&self, ordering: Ordering) -> bool {
+        match &self.operator {
+            BinaryOperator::Equal(_) => ordering.is_eq(),
+            BinaryOperator::NotEqual(_) => ordering.is_ne(),
+            BinaryOperator::GreaterThan(_) => ordering.is_gt(),
+            BinaryOperator::LessThan(_) => ordering.is_lt(),
+            BinaryOperator::GreaterThanOrEqual(_) => ordering.is_ge(),
+            BinaryOperator::LessThanOrEqual(_) => ordering.is_le(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// FALSE AND x 为 FALSE，TRUE AND NULL 为 NULL
+    fn and(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        let left: Option<bool> = truth(&self.left.evaluate(row)?);
+        if left == Some(false) {
+            return Ok(boolean(Some(false)));
+        }
+        let right: Option<bool> = truth(&self.right.evaluate(row)?);
+        Ok(boolean(logical(&self.operator, left, right)))
+    }
+
+    /// TRUE OR x 为 TRUE，FALSE OR NULL 为 NULL
+    fn or(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        let left: Option<bool> = truth(&self.left.evaluate(row)?);
+        if left == Some(true) {
+            return Ok(boolean(Some(true)));
+        }
+        let right: Option<bool> = truth(&self.right.evaluate(row)?);
+        Ok(boolean(logical(&self.operator, left, right)))
+    }
+
+    /// 两个 Int32 的算术运算，溢出和除零时报错
+    pub(super) fn int_arithmetic(&self, l: i32, r: i32) -> Result<i32, ExecuteError> {
+        Ok(self.calculate(Int32::new(l), Int32::new(r))?.value())
+    }
+
+    /// 两个 Float64 的算术运算，溢出或除以零时返回错误
+    pub(super) fn float_arithmetic(&self, l: f64, r: f64) -> Result<f64, ExecuteError> {
+        Ok(self.calculate(Float64::new(l), Float64::new(r))?.value())
+    }
+
+    /// 按运算符计算两个同类型的数值
+    fn calculate<T>(&self, l: T, r: T) -> Result<T, ExecuteError>
+    where
+        T: Add<Output = Result<T, ExecuteError>>
+            + Sub<Output = Result<T, ExecuteError>>
+            + Mul<Output = Result<T, ExecuteError>>
+            + Div<Output = Result<T, ExecuteError>>
+            + Rem<Output = Result<T, ExecuteError>>,
+    {
+        match &self.operator {
+            BinaryOperator::Plus(_) => l + r,
+            BinaryOperator::Minus(_) => l - r,
+            BinaryOperator::Multiply(_) => l * r,
+            BinaryOperator::Modulo(_) => l % r,
+            _ => l / r,
+        }
+    }
+
+    fn arithmetic(&self, left: &OwnValue, right: &OwnValue) -> Result<OwnValue, ExecuteError> {
+        match promote(left, right) {
+            Some(Promoted::Int32(l, r)) => self.calculate(l, r).map(OwnValue::Int32),
+            Some(Promoted::Int64(l, r)) => self.calculate(l, r).map(OwnValue::Int64),
+            Some(Promoted::Decimal(l, r)) => self.calculate(l, r).map(OwnValue::Decimal),
+            Some(Promoted::Float64(l, r)) => self.calculate(l, r).map(OwnValue::Float64),
+            None => Err(self.type_error(left, right)),
+        }
+    }
+
+    fn compare(&self, left: &OwnValue, right: &OwnValue) -> Result<Ordering, ExecuteError> {
+        compare(left, right).ok_or_else(|| self.type_error(left, right))
+    }
+
+    pub(super) fn type_error(&self, left: &OwnValue, right: &OwnValue) -> ExecuteError {
+        ExecuteError::new(
+            ErrorCode::DatatypeMismatch,
+            format!("unsupported operand {} {} {}", left, self.operator, right),
+        )
+    }
+}
+
+impl BoundUnary {
+    fn evaluate(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        self.apply(self.expression.evaluate(row)?)
+    }
+
+    /// 在操作数上计算一元运算
+    pub(super) fn apply(&self, value: OwnValue) -> Result<OwnValue, ExecuteError> {
+        match (&self.operator, &value) {
+            (UnaryOperator::IsNull(_), _) => Ok(boolean(Some(value == OwnValue::Null))),
+            (UnaryOperator::IsNotNull(_), _) => Ok(boolean(Some(value != OwnValue::Null))),
+            (_, OwnValue::Null) => Ok(OwnValue::Null),
+            (UnaryOperator::NOT(_), _) => Ok(boolean(truth(&value).map(|v| !v))),
+            (UnaryOperator::Plus(_), _) if value.data_type().is_numeric() => Ok(value),
+            (UnaryOperator::Minus(_), OwnValue::Int32(v)) => Ok(OwnValue::Int32((-*v)?)),
+            (UnaryOperator::Minus(_), OwnValue::Int64(v)) => Ok(OwnValue::Int64((-*v)?)),
+            (UnaryOperator::Minus(_), OwnValue::Decimal(v)) => Ok(OwnValue::Decimal(-*v)),
+            (UnaryOperator::Minus(_), OwnValue::Float64(v)) => Ok(OwnValue::Float64(-*v)),
+            _ => Err(ExecuteError::new(
+                ErrorCode::DatatypeMismatch,
+                format!("unsupported operand {} {}", self.operator, value),
+            )),
+        }
+    }
+}
+
+impl FunctionCall {
+    /// COALESCE、IFNULL 从左到右计算参数，遇到第一个不为 NULL 的值即返回
+    fn evaluate(&self, row: &dyn Row) -> Result<OwnValue, ExecuteError> {
+        match self.function {
+            ScalarFunction::Coalesce | ScalarFunction::IfNull => {
+                for argument in self.arguments.iter() {
+                    let value: OwnValue = argument.evaluate(row)?;
+                    if value != OwnValue::Null {
+                        return Ok(self.cast(value));
+                    }
+                }
+                Ok(OwnValue::Null)
+            }
+            ScalarFunction::NullIf => {
+                let value: OwnValue = self.arguments[0].evaluate(row)?;
+                let other: OwnValue = self.arguments[1].evaluate(row)?;
+                self.null_if(value, &other)
+            }
+        }
+    }
+
+    /// NULLIF(value, other)，other 为 NULL 时 value = other 不为真，返回 value
+    pub(super) fn null_if(
+        &self,
+        value: OwnValue,
+        other: &OwnValue,
+    ) -> Result<OwnValue, ExecuteError> {
+        if value == OwnValue::Null || *other == OwnValue::Null {
+            return Ok(self.cast(value));
+        }
+        match compare(&value, other) {
+            Some(Ordering::Equal) => Ok(OwnValue::Null),
+            Some(_) => Ok(self.cast(value)),
+            None => Err(ExecuteError::new(
+                ErrorCode::DatatypeMismatch,
+                format!(
+                    "unsupported operand {}({}, {})",
+                    self.function, value, other
+                ),
+            )),
+        }
+    }
+
+    /// 参数的类型不同时，数值转换为函数的类型，十进制数保留原来的小数位数
+    pub(super) fn cast(&self, value: OwnValue) -> OwnValue {
+        let converted: Option<OwnValue> = match &self.data_type {
+            DataType::Int64 => value.to_int64().map(OwnValue::Int64),
+            DataType::Decimal(_, _) => value.to_decimal().map(OwnValue::Decimal),
+            DataType::Float64 => value.to_float64().map(OwnValue::Float64),
+            _ => None,
+        };
+        converted.unwrap_or(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        executor::{
+            expression::{BoundLiteral, ColumnRef},
+            meta::row::SimpleMemoryRow,
+            types::DataType,
+        },
+        sql::parser::ast::leaf::Location,
+    };
+
+    fn location() -> Location {
+        Location {
+            line_number: 1,
+            column_number: 1,
+            offset: 0,
+        }
+    }
+
+    fn literal(value: OwnValue) -> BoundExpression {
+        let data_type: DataType = match &value {
+            OwnValue::Boolean(_) => DataType::Boolean,
+            OwnValue::Float64(_) => DataType::Float64,
+            OwnValue::String(_) => DataType::String,
+            _ => DataType::Int32,
+        };
+        BoundExpression::Literal(BoundLiteral { value, data_type })
+    }
+
+    fn int(value: i32) -> BoundExpression {
+        literal(OwnValue::Int32(Int32::new(value)))
+    }
+
+    fn float(value: f64) -> BoundExpression {
+        literal(OwnValue::Float64(Float64::new(value)))
+    }
+
+    fn null() -> BoundExpression {
+        literal(OwnValue::Null)
+    }
+
+    fn column(index: usize) -> BoundExpression {
+        BoundExpression::Column(ColumnRef {
+            table: "t".into(),
+            name: "c".into(),
+            index,
+            data_type: DataType::Int32,
+            nullable: true,
+        })
+    }
+
+    fn binary(
+        left: BoundExpression,
+        operator: BinaryOperator,
+        right: BoundExpression,
+    ) -> BoundExpression {
+        BoundExpression::Binary(BoundBinary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+            data_type: DataType::Int32,
+        })
+    }
+
+    fn unary(operator: UnaryOperator, expression: BoundExpression) -> BoundExpression {
+        BoundExpression::Unary(BoundUnary {
+            operator,
+            expression: Box::new(expression),
+            data_type: DataType::Int32,
+        })
+    }
+
+    fn eval(expression: &BoundExpression) -> Result<OwnValue, ExecuteError> {
+        expression.evaluate(&SimpleMemoryRow::new(vec![]))
+    }
+
+    fn t() -> BoundExpression {
+        literal(OwnValue::Boolean(true))
+    }
+
+    fn f() -> BoundExpression {
+        literal(OwnValue::Boolean(false))
+    }
+
+    #[test]
+    fn eval_column() {
+        let row: SimpleMemoryRow =
+            SimpleMemoryRow::new(vec![OwnValue::Null, OwnValue::Int32(Int32::new(7))]);
+        assert_eq!(
+            column(1).evaluate(&row).unwrap(),
+            OwnValue::Int32(Int32::new(7))
+        );
+        assert_eq!(column(0).evaluate(&row).unwrap(), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_arithmetic() {
+        let e = binary(int(7), BinaryOperator::Divide(location()), int(2));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Int32(Int32::new(3)));
+        let e = binary(int(7), BinaryOperator::Multiply(location()), float(0.5));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Float64(Float64::new(3.5)));
+        let e = binary(float(1.5), BinaryOperator::Minus(location()), int(2));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Float64(Float64::new(-0.5)));
+        let e = binary(int(1), BinaryOperator::Plus(location()), null());
+        assert_eq!(eval(&e).unwrap(), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_arithmetic_error() {
+        let e = binary(int(1), BinaryOperator::Divide(location()), int(0));
+        assert_eq!(eval(&e).unwrap_err().to_string(), "division by zero");
+        let e = binary(int(i32::MAX), BinaryOperator::Plus(location()), int(1));
+        assert!(eval(&e).is_err());
+        let e = unary(UnaryOperator::Minus(location()), int(i32::MIN));
+        assert!(eval(&e).is_err());
+        let e = binary(float(1.5), BinaryOperator::Modulo(location()), int(0));
+        assert_eq!(eval(&e).unwrap_err().to_string(), "division by zero");
+    }
+
+    #[test]
+    fn eval_modulo() {
+        let e = binary(int(-7), BinaryOperator::Modulo(location()), int(3));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Int32(Int32::new(-1)));
+        let e = binary(int(i32::MIN), BinaryOperator::Modulo(location()), int(-1));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Int32(Int32::new(0)));
+        let e = binary(float(7.5), BinaryOperator::Modulo(location()), int(2));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Float64(Float64::new(1.5)));
+    }
+
+    #[test]
+    fn eval_comparison() {
+        let e = binary(int(2), BinaryOperator::GreaterThan(location()), float(1.5));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Boolean(true));
+        let e = binary(
+            literal(OwnValue::String("a".into())),
+            BinaryOperator::LessThan(location()),
+            literal(OwnValue::String("b".into())),
+        );
+        assert_eq!(eval(&e).unwrap(), OwnValue::Boolean(true));
+        let e = binary(int(2), BinaryOperator::Equal(location()), null());
+        assert_eq!(eval(&e).unwrap(), OwnValue::Null);
+        let e = binary(null(), BinaryOperator::NotEqual(location()), null());
+        assert_eq!(eval(&e).unwrap(), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_three_valued_and() {
+        let and = |l, r| eval(&binary(l, BinaryOperator::AND(location()), r)).unwrap();
+        assert_eq!(and(t(), t()), OwnValue::Boolean(true));
+        assert_eq!(and(t(), f()), OwnValue::Boolean(false));
+        assert_eq!(and(f(), null()), OwnValue::Boolean(false));
+        assert_eq!(and(null(), f()), OwnValue::Boolean(false));
+        assert_eq!(and(t(), null()), OwnValue::Null);
+        assert_eq!(and(null(), null()), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_three_valued_or() {
+        let or = |l, r| eval(&binary(l, BinaryOperator::OR(location()), r)).unwrap();
+        assert_eq!(or(f(), f()), OwnValue::Boolean(false));
+        assert_eq!(or(t(), null()), OwnValue::Boolean(true));
+        assert_eq!(or(null(), t()), OwnValue::Boolean(true));
+        assert_eq!(or(f(), null()), OwnValue::Null);
+        assert_eq!(or(null(), null()), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_unary() {
+        let not = |e| eval(&unary(UnaryOperator::NOT(location()), e)).unwrap();
+        assert_eq!(not(t()), OwnValue::Boolean(false));
+        assert_eq!(not(f()), OwnValue::Boolean(true));
+        assert_eq!(not(null()), OwnValue::Null);
+        let e = unary(UnaryOperator::Minus(location()), float(2.5));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Float64(Float64::new(-2.5)));
+        let e = unary(UnaryOperator::Plus(location()), int(3));
+        assert_eq!(eval(&e).unwrap(), OwnValue::Int32(Int32::new(3)));
+    }
+
+    #[test]
+    fn eval_is_null() {
+        let is_null = |e| eval(&unary(UnaryOperator::IsNull(location()), e)).unwrap();
+        let is_not_null = |e| eval(&unary(UnaryOperator::IsNotNull(location()), e)).unwrap();
+        assert_eq!(is_null(null()), OwnValue::Boolean(true));
+        assert_eq!(is_null(f()), OwnValue::Boolean(false));
+        assert_eq!(is_not_null(null()), OwnValue::Boolean(false));
+        assert_eq!(is_not_null(float(0.5)), OwnValue::Boolean(true));
+        let e = unary(
+            UnaryOperator::IsNull(location()),
+            binary(int(1), BinaryOperator::Plus(location()), null()),
+        );
+        assert!(!e.nullable());
+        assert_eq!(eval(&e).unwrap(), OwnValue::Boolean(true));
+    }
+
+    #[test]
+    fn eval_null_functions() {
+        let call = |function, arguments: Vec<BoundExpression>, data_type| {
+            BoundExpression::Function(FunctionCall {
+                function,
+                arguments: arguments.into_boxed_slice(),
+                data_type,
+            })
+        };
+        let e = call(
+            ScalarFunction::Coalesce,
+            vec![null(), int(2), float(1.5)],
+            DataType::Float64,
+        );
+        assert_eq!(eval(&e).unwrap(), OwnValue::Float64(Float64::new(2.0)));
+        assert!(!e.nullable());
+        // 找到不为 NULL 的参数后不再计算后面的参数
+        let e = call(
+            ScalarFunction::IfNull,
+            vec![
+                int(1),
+                binary(int(1), BinaryOperator::Divide(location()), int(0)),
+            ],
+            DataType::Int32,
+        );
+        assert_eq!(eval(&e).unwrap(), OwnValue::Int32(Int32::new(1)));
+        let e = call(
+            ScalarFunction::Coalesce,
+            vec![null(), null()],
+            DataType::Null,
+        );
+        assert_eq!(eval(&e).unwrap(), OwnValue::Null);
+
+        let null_if = |l, r| eval(&call(ScalarFunction::NullIf, vec![l, r], DataType::Int32));
+        assert_eq!(null_if(int(1), float(1.0)).unwrap(), OwnValue::Null);
+        assert_eq!(
+            null_if(int(1), int(2)).unwrap(),
+            OwnValue::Int32(Int32::new(1))
+        );
+        assert_eq!(
+            null_if(int(1), null()).unwrap(),
+            OwnValue::Int32(Int32::new(1))
+        );
+        assert_eq!(null_if(null(), int(1)).unwrap(), OwnValue::Null);
+    }
+
+    #[test]
+    fn eval_predicate() {
+        let row: SimpleMemoryRow = SimpleMemoryRow::new(vec![]);
+        assert!(t().evaluate_predicate(&row).unwrap());
+        assert!(!f().evaluate_predicate(&row).unwrap());
+        assert!(!null().evaluate_predicate(&row).unwrap());
+    }
+}
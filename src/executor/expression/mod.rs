@@ -0,0 +1,386 @@
+use std::fmt::Display;
+
+use crate::{
+    executor::types::{DataType, OwnValue},
+    sql::parser::ast::{
+        expression::{BinaryOperator, UnaryOperator},
+        leaf::Location,
+    },
+};
+
+/// 表达式求值
+pub mod eval;
+
+/// 在一批数据上按列求值
+pub mod batch;
+
+/// BoundExpression 绑定后的表达式
+/// 列引用已经解析为输入行中的下标，每个节点都有确定的数据类型
+#[derive(Debug, PartialEq, Clone)]
+pub enum BoundExpression {
+    Literal(BoundLiteral),
+    Column(ColumnRef),
+    Binary(BoundBinary),
+    Unary(BoundUnary),
+    Aggregate(AggregateCall),
+    Function(FunctionCall),
+}
+
+impl BoundExpression {
+    pub fn data_type(&self) -> &DataType {
+        match self {
+            BoundExpression::Literal(literal) => &literal.data_type,
+            BoundExpression::Column(column) => &column.data_type,
+            BoundExpression::Binary(binary) => &binary.data_type,
+            BoundExpression::Unary(unary) => &unary.data_type,
+            BoundExpression::Aggregate(aggregate) => &aggregate.data_type,
+            BoundExpression::Function(call) => &call.data_type,
+        }
+    }
+
+    /// 表达式结果是否可能为 NULL
+    pub fn nullable(&self) -> bool {
+        match self {
+            BoundExpression::Literal(literal) => literal.value == OwnValue::Null,
+            BoundExpression::Column(column) => column.nullable,
+            BoundExpression::Binary(binary) => binary.left.nullable() || binary.right.nullable(),
+            BoundExpression::Unary(unary) => {
+                !unary.operator.is_postfix() && unary.expression.nullable()
+            }
+            BoundExpression::Aggregate(aggregate) => aggregate.function != AggregateFunction::Count,
+            BoundExpression::Function(call) => match call.function {
+                ScalarFunction::Coalesce | ScalarFunction::IfNull => {
+                    call.arguments.iter().all(|argument| argument.nullable())
+                }
+                ScalarFunction::NullIf => true,
+            },
+        }
+    }
+
+    /// 表达式中是否包含聚合函数
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            BoundExpression::Literal(_) | BoundExpression::Column(_) => false,
+            BoundExpression::Binary(binary) => {
+                binary.left.contains_aggregate() || binary.right.contains_aggregate()
+            }
+            BoundExpression::Unary(unary) => unary.expression.contains_aggregate(),
+            BoundExpression::Aggregate(_) => true,
+            BoundExpression::Function(call) => call
+                .arguments
+                .iter()
+                .any(|argument| argument.contains_aggregate()),
+        }
+    }
+
+    /// 按出现顺序收集表达式中的聚合函数，相同的聚合函数只收集一次
+    pub fn collect_aggregates(&self, aggregates: &mut Vec<AggregateCall>) {
+        match self {
+            BoundExpression::Literal(_) | BoundExpression::Column(_) => {}
+            BoundExpression::Binary(binary) => {
+                binary.left.collect_aggregates(aggregates);
+                binary.right.collect_aggregates(aggregates);
+            }
+            BoundExpression::Unary(unary) => unary.expression.collect_aggregates(aggregates),
+            BoundExpression::Aggregate(aggregate) => {
+                if !aggregates.contains(aggregate) {
+                    aggregates.push(aggregate.clone());
+                }
+            }
+            BoundExpression::Function(call) => {
+                for argument in call.arguments.iter() {
+                    argument.collect_aggregates(aggregates);
+                }
+            }
+        }
+    }
+
+    /// 收集表达式引用的列下标
+    pub fn collect_columns(&self, columns: &mut Vec<usize>) {
+        match self {
+            BoundExpression::Literal(_) => {}
+            BoundExpression::Column(column) => columns.push(column.index),
+            BoundExpression::Binary(binary) => {
+                binary.left.collect_columns(columns);
+                binary.right.collect_columns(columns);
+            }
+            BoundExpression::Unary(unary) => unary.expression.collect_columns(columns),
+            BoundExpression::Aggregate(aggregate) => {
+                if let Some(argument) = &aggregate.argument {
+                    argument.collect_columns(columns);
+                }
+            }
+            BoundExpression::Function(call) => {
+                for argument in call.arguments.iter() {
+                    argument.collect_columns(columns);
+                }
+            }
+        }
+    }
+
+    /// 将表达式中的每个列引用替换为 f 的返回值
+    pub fn map_columns(&self, f: &dyn Fn(&ColumnRef) -> ColumnRef) -> BoundExpression {
+        match self {
+            BoundExpression::Literal(_) => self.clone(),
+            BoundExpression::Column(column) => BoundExpression::Column(f(column)),
+            BoundExpression::Binary(binary) => BoundExpression::Binary(BoundBinary {
+                left: Box::new(binary.left.map_columns(f)),
+                operator: binary.operator.clone(),
+                right: Box::new(binary.right.map_columns(f)),
+                data_type: binary.data_type.clone(),
+            }),
+            BoundExpression::Unary(unary) => BoundExpression::Unary(BoundUnary {
+                operator: unary.operator.clone(),
+                expression: Box::new(unary.expression.map_columns(f)),
+                data_type: unary.data_type.clone(),
+            }),
+            BoundExpression::Aggregate(aggregate) => BoundExpression::Aggregate(AggregateCall {
+                function: aggregate.function,
+                argument: aggregate
+                    .argument
+                    .as_ref()
+                    .map(|argument| Box::new(argument.map_columns(f))),
+                data_type: aggregate.data_type.clone(),
+            }),
+            BoundExpression::Function(call) => {
+                BoundExpression::Function(call.map_arguments(|argument| argument.map_columns(f)))
+            }
+        }
+    }
+
+    /// 将表达式中的每个列引用替换为 f 返回的表达式
+    pub fn replace_columns(&self, f: &dyn Fn(&ColumnRef) -> BoundExpression) -> BoundExpression {
+        match self {
+            BoundExpression::Literal(_) => self.clone(),
+            BoundExpression::Column(column) => f(column),
+            BoundExpression::Binary(binary) => BoundExpression::Binary(BoundBinary {
+                left: Box::new(binary.left.replace_columns(f)),
+                operator: binary.operator.clone(),
+                right: Box::new(binary.right.replace_columns(f)),
+                data_type: binary.data_type.clone(),
+            }),
+            BoundExpression::Unary(unary) => BoundExpression::Unary(BoundUnary {
+                operator: unary.operator.clone(),
+                expression: Box::new(unary.expression.replace_columns(f)),
+                data_type: unary.data_type.clone(),
+            }),
+            BoundExpression::Aggregate(aggregate) => BoundExpression::Aggregate(AggregateCall {
+                function: aggregate.function,
+                argument: aggregate
+                    .argument
+                    .as_ref()
+                    .map(|argument| Box::new(argument.replace_columns(f))),
+                data_type: aggregate.data_type.clone(),
+            }),
+            BoundExpression::Function(call) => BoundExpression::Function(
+                call.map_arguments(|argument| argument.replace_columns(f)),
+            ),
+        }
+    }
+
+    /// 按 AND 拆分为合取项，a AND (b AND c) 拆分为 [a, b, c]
+    pub fn split_conjunction(self) -> Vec<BoundExpression> {
+        match self {
+            BoundExpression::Binary(binary)
+                if matches!(binary.operator, BinaryOperator::AND(_)) =>
+            {
+                let mut conjuncts: Vec<BoundExpression> = binary.left.split_conjunction();
+                conjuncts.extend(binary.right.split_conjunction());
+                conjuncts
+            }
+            _ => vec![self],
+        }
+    }
+
+    /// 用 AND 连接合取项，没有合取项时返回 None
+    /// 合成的 AND 没有对应的 SQL 文本，位置记为 0 行 0 列
+    pub fn conjunction(conjuncts: Vec<BoundExpression>) -> Option<BoundExpression> {
+        conjuncts.into_iter().reduce(|left, right| {
+            BoundExpression::Binary(BoundBinary {
+                left: Box::new(left),
+                operator: BinaryOperator::AND(Location {
+                    line_number: 0,
+                    column_number: 0,
+                    offset: 0,
+                }),
+                right: Box::new(right),
+                data_type: DataType::Boolean,
+            })
+        })
+    }
+}
+
+impl Display for BoundExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundExpression::Literal(literal) => write!(f, "{}", literal.value),
+            BoundExpression::Column(column) => write!(f, "{}", column),
+            BoundExpression::Binary(binary) => {
+                write!(f, "({} {} {})", binary.left, binary.operator, binary.right)
+            }
+            BoundExpression::Unary(unary) if unary.operator.is_postfix() => {
+                write!(f, "({} {})", unary.expression, unary.operator)
+            }
+            BoundExpression::Unary(unary) => write!(f, "{} {}", unary.operator, unary.expression),
+            BoundExpression::Aggregate(aggregate) => write!(f, "{}", aggregate),
+            BoundExpression::Function(call) => write!(f, "{}", call),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundLiteral {
+    pub value: OwnValue,
+    pub data_type: DataType,
+}
+
+/// ColumnRef 列引用，index 是列在输入行中的下标
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnRef {
+    pub table: Box<str>,
+    pub name: Box<str>,
+    pub index: usize,
+    pub data_type: DataType,
+    pub nullable: bool,
+}
+
+impl Display for ColumnRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.table.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}.{}", self.table, self.name)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundBinary {
+    pub left: Box<BoundExpression>,
+    pub operator: BinaryOperator,
+    pub right: Box<BoundExpression>,
+    pub data_type: DataType,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundUnary {
+    pub operator: UnaryOperator,
+    pub expression: Box<BoundExpression>,
+    pub data_type: DataType,
+}
+
+/// AggregateCall 聚合函数调用，argument 为 None 表示 COUNT(*)
+#[derive(Debug, PartialEq, Clone)]
+pub struct AggregateCall {
+    pub function: AggregateFunction,
+    pub argument: Option<Box<BoundExpression>>,
+    pub data_type: DataType,
+}
+
+impl Display for AggregateCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.argument {
+            Some(argument) => write!(f, "{}({})", self.function, argument),
+            None => write!(f, "{}(*)", self.function),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    /// 按函数名查找聚合函数，不区分大小写
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "COUNT" => Some(AggregateFunction::Count),
+            "SUM" => Some(AggregateFunction::Sum),
+            "AVG" => Some(AggregateFunction::Avg),
+            "MIN" => Some(AggregateFunction::Min),
+            "MAX" => Some(AggregateFunction::Max),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateFunction::Count => write!(f, "COUNT"),
+            AggregateFunction::Sum => write!(f, "SUM"),
+            AggregateFunction::Avg => write!(f, "AVG"),
+            AggregateFunction::Min => write!(f, "MIN"),
+            AggregateFunction::Max => write!(f, "MAX"),
+        }
+    }
+}
+
+/// FunctionCall 标量函数调用，逐行计算
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionCall {
+    pub function: ScalarFunction,
+    pub arguments: Box<[BoundExpression]>,
+    pub data_type: DataType,
+}
+
+impl FunctionCall {
+    /// 用 f 替换每个参数，函数和类型不变
+    pub fn map_arguments(&self, f: impl Fn(&BoundExpression) -> BoundExpression) -> FunctionCall {
+        FunctionCall {
+            function: self.function,
+            arguments: self.arguments.iter().map(f).collect(),
+            data_type: self.data_type.clone(),
+        }
+    }
+}
+
+impl Display for FunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.function)?;
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", argument)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// 处理 NULL 的标量函数
+/// COALESCE 返回第一个不为 NULL 的参数，IFNULL(a, b) 等价于 COALESCE(a, b)，
+/// NULLIF(a, b) 在 a = b 时返回 NULL，否则返回 a
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScalarFunction {
+    Coalesce,
+    NullIf,
+    IfNull,
+}
+
+impl ScalarFunction {
+    /// 按函数名查找标量函数，不区分大小写
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "COALESCE" => Some(ScalarFunction::Coalesce),
+            "NULLIF" => Some(ScalarFunction::NullIf),
+            "IFNULL" => Some(ScalarFunction::IfNull),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ScalarFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScalarFunction::Coalesce => write!(f, "COALESCE"),
+            ScalarFunction::NullIf => write!(f, "NULLIF"),
+            ScalarFunction::IfNull => write!(f, "IFNULL"),
+        }
+    }
+}
@@ -0,0 +1,674 @@
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        types::{
+            datetime::{Date, Interval, Time, Timestamp},
+            decimal::{Decimal, MAX_PRECISION},
+            flout64::Float64,
+            int32::Int32,
+            int64::Int64,
+            varchar::Varchar,
+            DataType, OwnValue,
+        },
+    },
+};
+
+use super::{
+    row::{out_of_range, Row, SimpleMemoryRow},
+    rows::Rows,
+    schema::Schema,
+};
+
+/// 一批最多包含的行数
+pub const BATCH_SIZE: usize = 1024;
+
+/// Bitmap 定长位图，每一位对应批中的一行
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    /// 长度为 len、所有位为 0 的位图
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        debug_assert!(index < self.len);
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        debug_assert!(index < self.len);
+        if value {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// 为 1 的位数
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// 为 1 的位的下标，升序
+    pub fn ones(&self) -> Vec<usize> {
+        (0..self.len).filter(|i| self.get(*i)).collect()
+    }
+
+    /// 按位或，两个位图的长度必须相同
+    pub fn or(&self, other: &Bitmap) -> Bitmap {
+        debug_assert_eq!(self.len, other.len);
+        Bitmap {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(l, r)| l | r)
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+/// ColumnData 一列的定长数据，NULL 处存放类型的默认值
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Boolean(Vec<bool>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Decimal(Vec<Decimal>),
+    Float64(Vec<f64>),
+    String(Vec<String>), // Varchar 和 String 都存为 String
+    Date(Vec<Date>),
+    Time(Vec<Time>),
+    Timestamp(Vec<Timestamp>),
+    Interval(Vec<Interval>),
+    Blob(Vec<Vec<u8>>),
+}
+
+impl ColumnData {
+    fn type_name(&self) -> &'static str {
+        match self {
+            ColumnData::Boolean(_) => "Boolean",
+            ColumnData::Int32(_) => "Int32",
+            ColumnData::Int64(_) => "Int64",
+            ColumnData::Decimal(_) => "Decimal",
+            ColumnData::Float64(_) => "Float64",
+            ColumnData::String(_) => "String",
+            ColumnData::Date(_) => "Date",
+            ColumnData::Time(_) => "Time",
+            ColumnData::Timestamp(_) => "Timestamp",
+            ColumnData::Interval(_) => "Interval",
+            ColumnData::Blob(_) => "Blob",
+        }
+    }
+
+    /// 追加 NULL 处的默认值
+    fn push_default(&mut self) {
+        match self {
+            ColumnData::Boolean(data) => data.push(false),
+            ColumnData::Int32(data) => data.push(0),
+            ColumnData::Int64(data) => data.push(0),
+            ColumnData::Decimal(data) => data.push(Decimal::default()),
+            ColumnData::Float64(data) => data.push(0.0),
+            ColumnData::String(data) => data.push(String::new()),
+            ColumnData::Date(data) => data.push(Date::default()),
+            ColumnData::Time(data) => data.push(Time::default()),
+            ColumnData::Timestamp(data) => data.push(Timestamp::default()),
+            ColumnData::Interval(data) => data.push(Interval::default()),
+            ColumnData::Blob(data) => data.push(vec![]),
+        }
+    }
+}
+
+/// ColumnVector 一列数据和它的 NULL 位图，位为 1 表示 NULL
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnVector {
+    pub data: ColumnData,
+    pub nulls: Bitmap,
+}
+
+impl ColumnVector {
+    pub fn with_capacity(data_type: &DataType, capacity: usize) -> Self {
+        let data: ColumnData = match data_type {
+            DataType::Boolean => ColumnData::Boolean(Vec::with_capacity(capacity)),
+            // NULL 类型的列只有 NULL，按 Int32 存储
+            DataType::Int32 | DataType::Null => ColumnData::Int32(Vec::with_capacity(capacity)),
+            DataType::Int64 => ColumnData::Int64(Vec::with_capacity(capacity)),
+            DataType::Decimal(_, _) => ColumnData::Decimal(Vec::with_capacity(capacity)),
+            DataType::Float64 => ColumnData::Float64(Vec::with_capacity(capacity)),
+            DataType::Varchar(_) | DataType::String => {
+                ColumnData::String(Vec::with_capacity(capacity))
+            }
+            DataType::Date => ColumnData::Date(Vec::with_capacity(capacity)),
+            DataType::Time => ColumnData::Time(Vec::with_capacity(capacity)),
+            DataType::Timestamp => ColumnData::Timestamp(Vec::with_capacity(capacity)),
+            DataType::Interval => ColumnData::Interval(Vec::with_capacity(capacity)),
+            DataType::Blob => ColumnData::Blob(Vec::with_capacity(capacity)),
+        };
+        Self {
+            data,
+            nulls: Bitmap::default(),
+        }
+    }
+
+    /// 值都为 value 的一列
+    pub fn repeat(
+        data_type: &DataType,
+        value: &OwnValue,
+        len: usize,
+    ) -> Result<Self, ExecuteError> {
+        let mut column: ColumnVector = Self::with_capacity(data_type, len);
+        for _ in 0..len {
+            column.push(value.clone())?;
+        }
+        Ok(column)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nulls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nulls.is_empty()
+    }
+
+    pub fn is_null(&self, index: usize) -> bool {
+        self.nulls.get(index)
+    }
+
+    /// 追加一个值，整数可以追加到范围更大的数值列中，其余类型必须与列的类型一致
+    pub fn push(&mut self, value: OwnValue) -> Result<(), ExecuteError> {
+        if value == OwnValue::Null {
+            self.data.push_default();
+            self.nulls.push(true);
+            return Ok(());
+        }
+        match (&mut self.data, value) {
+            (ColumnData::Boolean(data), OwnValue::Boolean(v)) => data.push(v),
+            (ColumnData::Int32(data), OwnValue::Int32(v)) => data.push(v.value()),
+            (ColumnData::Int64(data), value @ (OwnValue::Int32(_) | OwnValue::Int64(_))) => {
+                data.push(value.to_int64().unwrap().value())
+            }
+            (ColumnData::Decimal(data), value @ (OwnValue::Int32(_) | OwnValue::Int64(_))) => {
+                data.push(value.to_decimal().unwrap())
+            }
+            (ColumnData::Decimal(data), OwnValue::Decimal(v)) => data.push(v),
+            (ColumnData::Float64(data), OwnValue::Float64(v)) => data.push(v.value()),
+            (ColumnData::Float64(data), OwnValue::Int32(v)) => data.push(v.value() as f64),
+            (ColumnData::String(data), OwnValue::String(s)) => data.push(s),
+            (ColumnData::Date(data), OwnValue::Date(v)) => data.push(v),
+            (ColumnData::Time(data), OwnValue::Time(v)) => data.push(v),
+            (ColumnData::Timestamp(data), OwnValue::Timestamp(v)) => data.push(v),
+            (ColumnData::Interval(data), OwnValue::Interval(v)) => data.push(v),
+            (ColumnData::Blob(data), OwnValue::Blob(v)) => data.push(v),
+            (data, value) => {
+                return Err(ExecuteError::new(
+                    ErrorCode::DatatypeMismatch,
+                    format!(
+                        "type mismatch: cannot store {} in {} column",
+                        value,
+                        data.type_name()
+                    ),
+                ))
+            }
+        }
+        self.nulls.push(false);
+        Ok(())
+    }
+
+    /// 取出 index 处的值
+    pub fn get(&self, index: usize) -> OwnValue {
+        if self.is_null(index) {
+            return OwnValue::Null;
+        }
+        match &self.data {
+            ColumnData::Boolean(data) => OwnValue::Boolean(data[index]),
+            ColumnData::Int32(data) => OwnValue::Int32(Int32::new(data[index])),
+            ColumnData::Int64(data) => OwnValue::Int64(Int64::new(data[index])),
+            ColumnData::Decimal(data) => OwnValue::Decimal(data[index]),
+            ColumnData::Float64(data) => OwnValue::Float64(Float64::new(data[index])),
+            ColumnData::String(data) => OwnValue::String(data[index].clone()),
+            ColumnData::Date(data) => OwnValue::Date(data[index]),
+            ColumnData::Time(data) => OwnValue::Time(data[index]),
+            ColumnData::Timestamp(data) => OwnValue::Timestamp(data[index]),
+            ColumnData::Interval(data) => OwnValue::Interval(data[index]),
+            ColumnData::Blob(data) => OwnValue::Blob(data[index].clone()),
+        }
+    }
+
+    /// 按 indices 的顺序取出若干行组成新的一列
+    pub fn take(&self, indices: &[usize]) -> Self {
+        let mut nulls: Bitmap = Bitmap::new(indices.len());
+        for (i, index) in indices.iter().enumerate() {
+            nulls.set(i, self.is_null(*index));
+        }
+        let data: ColumnData = match &self.data {
+            ColumnData::Boolean(data) => ColumnData::Boolean(gather(data, indices)),
+            ColumnData::Int32(data) => ColumnData::Int32(gather(data, indices)),
+            ColumnData::Int64(data) => ColumnData::Int64(gather(data, indices)),
+            ColumnData::Decimal(data) => ColumnData::Decimal(gather(data, indices)),
+            ColumnData::Float64(data) => ColumnData::Float64(gather(data, indices)),
+            ColumnData::String(data) => ColumnData::String(gather(data, indices)),
+            ColumnData::Date(data) => ColumnData::Date(gather(data, indices)),
+            ColumnData::Time(data) => ColumnData::Time(gather(data, indices)),
+            ColumnData::Timestamp(data) => ColumnData::Timestamp(gather(data, indices)),
+            ColumnData::Interval(data) => ColumnData::Interval(gather(data, indices)),
+            ColumnData::Blob(data) => ColumnData::Blob(gather(data, indices)),
+        };
+        Self { data, nulls }
+    }
+}
+
+/// 按 indices 的顺序复制元素
+fn gather<T: Clone>(data: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|i| data[*i].clone()).collect()
+}
+
+/// Batch 按列存储的一批行，所有列的长度相同
+/// 没有列时由 len 记录行数，例如只需要行数的 COUNT(*)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    pub columns: Vec<ColumnVector>,
+    len: usize,
+}
+
+impl Batch {
+    /// 按 schema 创建空的一批
+    pub fn new(schema: &Schema) -> Self {
+        Self {
+            columns: schema
+                .columns
+                .iter()
+                .map(|c| ColumnVector::with_capacity(&c.data_type, BATCH_SIZE))
+                .collect(),
+            len: 0,
+        }
+    }
+
+    pub fn from_columns(columns: Vec<ColumnVector>, len: usize) -> Self {
+        debug_assert!(columns.iter().all(|c| c.len() == len));
+        Self { columns, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 追加一行，values 的个数与列数相同
+    pub fn push(&mut self, values: Vec<OwnValue>) -> Result<(), ExecuteError> {
+        debug_assert_eq!(values.len(), self.columns.len());
+        for (column, value) in self.columns.iter_mut().zip(values) {
+            column.push(value)?;
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn push_row(&mut self, row: &dyn Row) -> Result<(), ExecuteError> {
+        for (index, column) in self.columns.iter_mut().enumerate() {
+            column.push(row.get(index)?.clone())?;
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// 按 indices 的顺序取出若干行
+    pub fn take(&self, indices: &[usize]) -> Self {
+        Self {
+            columns: self.columns.iter().map(|c| c.take(indices)).collect(),
+            len: indices.len(),
+        }
+    }
+
+    /// 第 index 行
+    pub fn row(&self, index: usize) -> SimpleMemoryRow {
+        SimpleMemoryRow::new(self.columns.iter().map(|c| c.get(index)).collect())
+    }
+
+    /// 逐行访问这一批的游标
+    pub fn rows(self) -> BatchRows {
+        BatchRows {
+            batch: self,
+            position: None,
+        }
+    }
+}
+
+/// BatchRows 在 Batch 上逐行移动的游标，调用 next 之后才指向第一行
+#[derive(Debug)]
+pub struct BatchRows {
+    batch: Batch,
+    position: Option<usize>,
+}
+
+impl BatchRows {
+    /// 第 index 列和当前行在其中的位置
+    fn column(&self, index: usize) -> Result<(&ColumnVector, usize), ExecuteError> {
+        let Some(position) = self.position.filter(|p| *p < self.batch.len()) else {
+            return Err(ExecuteError::new(
+                ErrorCode::InternalError,
+                "no current row, call next before reading rows",
+            ));
+        };
+        match self.batch.columns.get(index) {
+            Some(column) => Ok((column, position)),
+            None => Err(out_of_range(index, self.batch.columns.len())),
+        }
+    }
+
+    /// 读取当前行第 index 列的值，NULL 或者 f 返回 None 时返回类型不符的错误
+    fn typed<'s, T>(
+        &'s self,
+        index: usize,
+        expected: DataType,
+        f: impl FnOnce(&'s ColumnData, usize) -> Option<T>,
+    ) -> Result<T, ExecuteError> {
+        let (column, position) = self.column(index)?;
+        match column.is_null(position) {
+            true => None,
+            false => f(&column.data, position),
+        }
+        .ok_or_else(|| self.mismatch(index, expected))
+    }
+
+    fn mismatch(&self, index: usize, expected: DataType) -> ExecuteError {
+        let actual: DataType = match self.column(index) {
+            Ok((column, position)) if column.is_null(position) => DataType::Null,
+            Ok((column, position)) => column.get(position).data_type(),
+            Err(error) => return error,
+        };
+        ExecuteError::type_mismatch(format!("#{}", index), &expected, &actual)
+    }
+}
+
+impl Rows for BatchRows {
+    fn next(&mut self) -> bool {
+        let position: usize = self.position.map_or(0, |p| p + 1);
+        self.position = Some(position.min(self.batch.len()));
+        position < self.batch.len()
+    }
+
+    fn is_null(&self, index: usize) -> Result<bool, ExecuteError> {
+        let (column, position) = self.column(index)?;
+        Ok(column.is_null(position))
+    }
+
+    fn get_boolean(&self, index: usize) -> Result<bool, ExecuteError> {
+        self.typed(index, DataType::Boolean, |data, i| match data {
+            ColumnData::Boolean(data) => Some(data[i]),
+            _ => None,
+        })
+    }
+
+    fn get_int32(&self, index: usize) -> Result<Int32, ExecuteError> {
+        self.typed(index, DataType::Int32, |data, i| match data {
+            ColumnData::Int32(data) => Some(Int32::new(data[i])),
+            _ => None,
+        })
+    }
+
+    fn get_int64(&self, index: usize) -> Result<Int64, ExecuteError> {
+        self.typed(index, DataType::Int64, |data, i| match data {
+            ColumnData::Int64(data) => Some(Int64::new(data[i])),
+            _ => None,
+        })
+    }
+
+    fn get_decimal(&self, index: usize) -> Result<Decimal, ExecuteError> {
+        self.typed(
+            index,
+            DataType::Decimal(MAX_PRECISION, 0),
+            |data, i| match data {
+                ColumnData::Decimal(data) => Some(data[i]),
+                _ => None,
+            },
+        )
+    }
+
+    fn get_float64(&self, index: usize) -> Result<Float64, ExecuteError> {
+        self.typed(index, DataType::Float64, |data, i| match data {
+            ColumnData::Float64(data) => Some(Float64::new(data[i])),
+            _ => None,
+        })
+    }
+
+    fn get_varchar(&self, index: usize) -> Result<Varchar<'_>, ExecuteError> {
+        self.get_string(index).map(Varchar::ref_string)
+    }
+
+    fn get_string(&self, index: usize) -> Result<&str, ExecuteError> {
+        self.typed(index, DataType::String, |data, i| match data {
+            ColumnData::String(data) => Some(data[i].as_str()),
+            _ => None,
+        })
+    }
+
+    fn get_date(&self, index: usize) -> Result<Date, ExecuteError> {
+        self.typed(index, DataType::Date, |data, i| match data {
+            ColumnData::Date(data) => Some(data[i]),
+            _ => None,
+        })
+    }
+
+    fn get_time(&self, index: usize) -> Result<Time, ExecuteError> {
+        self.typed(index, DataType::Time, |data, i| match data {
+            ColumnData::Time(data) => Some(data[i]),
+            _ => None,
+        })
+    }
+
+    fn get_timestamp(&self, index: usize) -> Result<Timestamp, ExecuteError> {
+        self.typed(index, DataType::Timestamp, |data, i| match data {
+            ColumnData::Timestamp(data) => Some(data[i]),
+            _ => None,
+        })
+    }
+
+    fn get_interval(&self, index: usize) -> Result<Interval, ExecuteError> {
+        self.typed(index, DataType::Interval, |data, i| match data {
+            ColumnData::Interval(data) => Some(data[i]),
+            _ => None,
+        })
+    }
+
+    fn get_blob(&self, index: usize) -> Result<&[u8], ExecuteError> {
+        self.typed(index, DataType::Blob, |data, i| match data {
+            ColumnData::Blob(data) => Some(data[i].as_slice()),
+            _ => None,
+        })
+    }
+}
+
+/// BatchReader 把逐行读取的数据源转换为每批最多 BATCH_SIZE 行的 Batch，只读取 projection 中的列
+pub struct BatchReader<'a> {
+    rows: Box<dyn Iterator<Item = &'a dyn Row> + 'a>,
+    projection: Vec<usize>,
+    schema: Schema,
+}
+
+impl<'a> BatchReader<'a> {
+    pub fn new(
+        table: &Schema,
+        rows: Box<dyn Iterator<Item = &'a dyn Row> + 'a>,
+        projection: &[u16],
+    ) -> Self {
+        Self {
+            rows,
+            projection: projection.iter().map(|i| *i as usize).collect(),
+            schema: Schema {
+                name: table.name.clone(),
+                columns: projection
+                    .iter()
+                    .map(|i| table.columns[*i as usize].clone())
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for BatchReader<'a> {
+    type Item = Result<Batch, ExecuteError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch: Batch = Batch::new(&self.schema);
+        for row in self.rows.by_ref().take(BATCH_SIZE) {
+            for (column, index) in batch.columns.iter_mut().zip(self.projection.iter()) {
+                if let Err(e) = row.get(*index).and_then(|v| column.push(v.clone())) {
+                    return Some(Err(e));
+                }
+            }
+            batch.len += 1;
+        }
+        if batch.is_empty() {
+            None
+        } else {
+            Some(Ok(batch))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::meta::column::Column;
+
+    fn schema() -> Schema {
+        Schema {
+            name: "stu".into(),
+            columns: vec![
+                Column {
+                    name: "name".into(),
+                    data_type: DataType::Varchar(32),
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "score".into(),
+                    data_type: DataType::Float64,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        }
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let mut bitmap: Bitmap = Bitmap::new(3);
+        bitmap.set(1, true);
+        for i in 0..100 {
+            bitmap.push(i % 3 == 0);
+        }
+        assert_eq!(bitmap.len(), 103);
+        assert!(bitmap.get(1) && !bitmap.get(2) && bitmap.get(3) && bitmap.get(102));
+        assert_eq!(bitmap.count_ones(), 35);
+        assert_eq!(bitmap.ones()[..3], [1, 3, 6]);
+        bitmap.set(1, false);
+        assert_eq!(bitmap.or(&Bitmap::new(103)).count_ones(), 34);
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut batch: Batch = Batch::new(&schema());
+        batch
+            .push(vec![
+                OwnValue::String("张三".into()),
+                OwnValue::Float64(Float64::new(90.5)),
+            ])
+            .unwrap();
+        // Int32 可以放入 Float64 列
+        batch
+            .push(vec![OwnValue::Null, OwnValue::Int32(Int32::new(80))])
+            .unwrap();
+        batch
+            .push(vec![OwnValue::String("王五".into()), OwnValue::Null])
+            .unwrap();
+        assert!(batch
+            .push(vec![OwnValue::Int32(Int32::new(1)), OwnValue::Null])
+            .is_err());
+
+        assert_eq!(batch.len(), 3);
+        assert_eq!(
+            batch.columns[1].get(1),
+            OwnValue::Float64(Float64::new(80.0))
+        );
+        assert_eq!(
+            batch.take(&[2, 0]).row(1).to_string(&schema()).unwrap(),
+            "[\"张三\", 90.5]"
+        );
+
+        let mut rows: BatchRows = batch.rows();
+        assert!(rows.next());
+        assert_eq!(rows.get_varchar(0).unwrap().to_string(), "张三");
+        assert_eq!(rows.get_float64(1).unwrap(), Float64::new(90.5));
+        assert!(rows.next());
+        assert!(rows.is_null(0).unwrap() && !rows.is_null(1).unwrap());
+        assert_eq!(
+            rows.get_string(0).unwrap_err().to_string(),
+            "type mismatch for column #0: expected String, found Null"
+        );
+        assert!(rows.next());
+        assert_eq!(rows.get_string(0).unwrap(), "王五");
+        assert!(rows.is_null(1).unwrap());
+        assert!(rows.get_int32(0).is_err());
+        assert!(!rows.next());
+        assert!(!rows.next());
+        assert!(rows.get_string(0).is_err());
+    }
+
+    #[test]
+    fn test_batch_reader() {
+        let rows: Vec<SimpleMemoryRow> = (0..BATCH_SIZE + 1)
+            .map(|i| {
+                SimpleMemoryRow::new(vec![
+                    OwnValue::String(i.to_string()),
+                    OwnValue::Float64(Float64::new(i as f64)),
+                ])
+            })
+            .collect();
+        let batches: Vec<Batch> = BatchReader::new(
+            &schema(),
+            Box::new(rows.iter().map(|r| r as &dyn Row)),
+            &[1],
+        )
+        .collect::<Result<_, _>>()
+        .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), BATCH_SIZE);
+        assert_eq!(batches[0].columns.len(), 1);
+        assert_eq!(
+            batches[1].columns[0].get(0),
+            OwnValue::Float64(Float64::new(BATCH_SIZE as f64))
+        );
+    }
+}
@@ -1,6 +1,12 @@
 use std::fmt::{Debug, Display};
 
-use crate::executor::types::DataType;
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        types::{decimal::Decimal, varchar::Varchar, DataType, OwnValue},
+    },
+};
 
 /// 列（字段）元信息
 #[derive(Clone)]
@@ -8,6 +14,70 @@ pub struct Column {
     pub name: Box<str>,
     pub data_type: DataType,
     pub nullable: bool,
+    pub default: Option<OwnValue>, // 插入时没有指定该列则使用默认值，没有默认值时为 NULL
+}
+
+impl Column {
+    /// 检查写入该列的值：非空约束、类型以及 VARCHAR(n) 的字符数上限
+    /// 数值写入范围更大或者精度不同的数值列、Date 写入 Timestamp 列时自动转换，返回实际存储的值
+    pub fn check_value(&self, value: OwnValue) -> Result<OwnValue, ExecuteError> {
+        let value: OwnValue = match (&self.data_type, value) {
+            (_, OwnValue::Null) if !self.nullable => {
+                return Err(ExecuteError::new(
+                    ErrorCode::NotNullViolation,
+                    format!("column {} must not be NULL", self.name),
+                ))
+            }
+            (_, OwnValue::Null) => OwnValue::Null,
+            (DataType::Boolean, value @ OwnValue::Boolean(_)) => value,
+            (DataType::Int32, value @ OwnValue::Int32(_)) => value,
+            (DataType::Int32, OwnValue::Int64(v)) => OwnValue::Int32(v.to_int32()?),
+            (DataType::Int64, value @ (OwnValue::Int32(_) | OwnValue::Int64(_))) => {
+                OwnValue::Int64(value.to_int64().unwrap())
+            }
+            (DataType::Decimal(precision, scale), OwnValue::Float64(v)) => {
+                OwnValue::Decimal(Decimal::from_float64(v, *scale)?.check(*precision, *scale)?)
+            }
+            (
+                DataType::Decimal(precision, scale),
+                value @ (OwnValue::Int32(_) | OwnValue::Int64(_) | OwnValue::Decimal(_)),
+            ) => OwnValue::Decimal(value.to_decimal().unwrap().check(*precision, *scale)?),
+            (DataType::Float64, value @ OwnValue::Float64(_)) => value,
+            (
+                DataType::Float64,
+                value @ (OwnValue::Int32(_) | OwnValue::Int64(_) | OwnValue::Decimal(_)),
+            ) => OwnValue::Float64(value.to_float64().unwrap()),
+            (DataType::Varchar(len), OwnValue::String(s)) => {
+                if Varchar::new(&s, *len).is_err() {
+                    return Err(ExecuteError::new(
+                        ErrorCode::StringDataRightTruncation,
+                        format!(
+                            "value too long for column {} of type {}",
+                            self.name, self.data_type
+                        ),
+                    ));
+                }
+                OwnValue::String(s)
+            }
+            (DataType::String, value @ OwnValue::String(_)) => value,
+            (DataType::Date, value @ OwnValue::Date(_)) => value,
+            (DataType::Time, value @ OwnValue::Time(_)) => value,
+            (DataType::Timestamp, value @ OwnValue::Timestamp(_)) => value,
+            (DataType::Timestamp, OwnValue::Date(v)) => OwnValue::Timestamp(v.into()),
+            (DataType::Interval, value @ OwnValue::Interval(_)) => value,
+            (DataType::Blob, value @ OwnValue::Blob(_)) => value,
+            (_, value) => {
+                return Err(ExecuteError::new(
+                    ErrorCode::DatatypeMismatch,
+                    format!(
+                        "value {} does not match column {} of type {}",
+                        value, self.name, self.data_type
+                    ),
+                ))
+            }
+        };
+        Ok(value)
+    }
 }
 
 impl Display for Column {
@@ -23,6 +93,117 @@ impl Debug for Column {
         } else {
             "NOT NULL"
         };
-        write!(f, "{} {} {}", self.name, self.data_type, nullable)
+        write!(f, "{} {} {}", self.name, self.data_type, nullable)?;
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {}", default)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::ErrorCode,
+        executor::types::{
+            datetime::{Date, Timestamp},
+            decimal::Decimal,
+            flout64::Float64,
+            int32::Int32,
+            int64::Int64,
+            DataType, OwnValue,
+        },
+    };
+
+    use super::Column;
+
+    fn column(data_type: DataType, nullable: bool) -> Column {
+        Column {
+            name: "c".into(),
+            data_type,
+            nullable,
+            default: None,
+        }
+    }
+
+    #[test]
+    fn test_check_value() {
+        let int: Column = column(DataType::Int32, false);
+        assert_eq!(
+            int.check_value(OwnValue::Int32(Int32::new(1))).unwrap(),
+            OwnValue::Int32(Int32::new(1))
+        );
+        assert_eq!(
+            int.check_value(OwnValue::Null).unwrap_err().message,
+            "column c must not be NULL"
+        );
+        assert_eq!(
+            int.check_value(OwnValue::Float64(Float64::new(1.5)))
+                .unwrap_err()
+                .message,
+            "value 1.5 does not match column c of type Int32"
+        );
+
+        let float: Column = column(DataType::Float64, true);
+        assert_eq!(float.check_value(OwnValue::Null).unwrap(), OwnValue::Null);
+        assert_eq!(
+            float.check_value(OwnValue::Int32(Int32::new(2))).unwrap(),
+            OwnValue::Float64(Float64::new(2.0))
+        );
+
+        let decimal: Column = column(DataType::Decimal(5, 2), true);
+        assert_eq!(
+            decimal.check_value(OwnValue::Int32(Int32::new(7))).unwrap(),
+            OwnValue::Decimal(Decimal::parse("7.00").unwrap())
+        );
+        assert_eq!(
+            decimal
+                .check_value(OwnValue::Float64(Float64::new(1.005)))
+                .unwrap()
+                .to_string(),
+            "DECIMAL '1.00'"
+        );
+        assert_eq!(
+            decimal
+                .check_value(OwnValue::Decimal(Decimal::parse("999.995").unwrap()))
+                .unwrap_err()
+                .code,
+            ErrorCode::NumericValueOutOfRange
+        );
+
+        let bigint: Column = column(DataType::Int64, true);
+        assert_eq!(
+            bigint.check_value(OwnValue::Int32(Int32::new(-1))).unwrap(),
+            OwnValue::Int64(Int64::new(-1))
+        );
+        assert_eq!(
+            int.check_value(OwnValue::Int64(Int64::new(1 << 40)))
+                .unwrap_err()
+                .code,
+            ErrorCode::NumericValueOutOfRange
+        );
+
+        let timestamp: Column = column(DataType::Timestamp, true);
+        assert_eq!(
+            timestamp
+                .check_value(OwnValue::Date(Date::parse("2024-01-02").unwrap()))
+                .unwrap(),
+            OwnValue::Timestamp(Timestamp::parse("2024-01-02 00:00:00").unwrap())
+        );
+
+        let varchar: Column = column(DataType::Varchar(2), true);
+        assert_eq!(
+            varchar
+                .check_value(OwnValue::String("张三".into()))
+                .unwrap(),
+            OwnValue::String("张三".into())
+        );
+        assert_eq!(
+            varchar
+                .check_value(OwnValue::String("abc".into()))
+                .unwrap_err()
+                .message,
+            "value too long for column c of type Varchar(2)"
+        );
     }
 }
@@ -1,22 +1,61 @@
-use std::fmt::{Debug, Display};
+use std::{
+    cell::OnceCell,
+    fmt::{Debug, Display},
+};
+
+use crate::executor::error::ExecuteError;
 
 use super::{
+    batch::{Batch, BatchReader},
     row::{Row, SimpleMemoryRow},
-    rows::Rows,
     schema::Schema,
+    statistics::Statistics,
 };
 
 pub trait DataSource: Debug {
     fn name(&self) -> String;
     fn schema(&self) -> &Schema;
     fn read<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn Row> + 'a>;
-    fn batch_read<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn Rows> + 'a>;
+
+    /// 按列读取 projection 指定的列，每批最多 BATCH_SIZE 行
+    /// 默认把 read 读出的行转换为列，按列存储的数据源可以直接返回列
+    fn batch_read<'a>(
+        &'a self,
+        projection: &[u16],
+    ) -> Box<dyn Iterator<Item = Result<Batch, ExecuteError>> + 'a> {
+        Box::new(BatchReader::new(self.schema(), self.read(), projection))
+    }
+
+    /// 表的统计信息，默认扫描全表计算，数据源可以返回维护好的统计信息
+    fn statistics(&self) -> Statistics {
+        Statistics::compute(self.schema(), self.read())
+    }
+}
+
+/// 可写的数据源
+/// 行的位置 position 指行在 DataSource::read 中的序号，调用方保证行中的值已经按 schema 校验过
+pub trait MutableDataSource: DataSource {
+    /// 追加行
+    fn insert(&mut self, rows: Vec<SimpleMemoryRow>);
+
+    /// 将 position 处的行替换为新的行
+    fn update(&mut self, rows: Vec<(usize, SimpleMemoryRow)>);
+
+    /// 删除 positions 处的行，positions 升序且不重复
+    fn delete(&mut self, positions: &[usize]);
+
+    /// 用新的表结构和数据替换整张表，用于 ALTER TABLE
+    fn replace(&mut self, schema: Schema, rows: Vec<SimpleMemoryRow>);
+
+    /// 修改表名，即 schema.name
+    fn rename(&mut self, name: &str);
 }
 
 #[derive(Clone)]
 pub struct SimpleMemoryDataSource {
     schema: Schema,
     rows: Vec<SimpleMemoryRow>,
+    statistics: OnceCell<Statistics>, // 第一次估算代价时计算，修改数据后失效
 }
 
 impl SimpleMemoryDataSource {
@@ -24,10 +63,12 @@ impl SimpleMemoryDataSource {
         Self {
             schema,
             rows: Vec::new(),
+            statistics: OnceCell::new(),
         }
     }
 
     pub fn push_row(&mut self, row: SimpleMemoryRow) {
+        self.statistics.take();
         self.rows.push(row);
     }
 }
@@ -45,8 +86,45 @@ impl DataSource for SimpleMemoryDataSource {
         Box::new(self.rows.iter().map(|r| r as &dyn Row))
     }
 
-    fn batch_read<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn Rows> + 'a> {
-        panic!("not implemented")
+    fn statistics(&self) -> Statistics {
+        self.statistics
+            .get_or_init(|| Statistics::compute(&self.schema, self.read()))
+            .clone()
+    }
+}
+
+impl MutableDataSource for SimpleMemoryDataSource {
+    fn insert(&mut self, rows: Vec<SimpleMemoryRow>) {
+        self.statistics.take();
+        self.rows.extend(rows);
+    }
+
+    fn update(&mut self, rows: Vec<(usize, SimpleMemoryRow)>) {
+        self.statistics.take();
+        for (position, row) in rows {
+            self.rows[position] = row;
+        }
+    }
+
+    fn delete(&mut self, positions: &[usize]) {
+        self.statistics.take();
+        let mut positions = positions.iter().peekable();
+        let mut position: usize = 0;
+        self.rows.retain(|_| {
+            let deleted: bool = positions.next_if_eq(&&position).is_some();
+            position += 1;
+            !deleted
+        });
+    }
+
+    fn replace(&mut self, schema: Schema, rows: Vec<SimpleMemoryRow>) {
+        self.statistics.take();
+        self.schema = schema;
+        self.rows = rows;
+    }
+
+    fn rename(&mut self, name: &str) {
+        self.schema.name = name.into();
     }
 }
 
@@ -76,6 +154,7 @@ mod test {
             name: "name".into(),
             data_type: DataType::Varchar(32),
             nullable: true,
+            default: None,
         };
         println!("column_name = {:?}", column_name);
 
@@ -83,6 +162,7 @@ mod test {
             name: "age".into(),
             data_type: DataType::Int32,
             nullable: true,
+            default: None,
         };
         println!("column_age = {:?}", column_age);
 
@@ -108,7 +188,7 @@ mod test {
         println!("source = {:?}", source);
 
         for row in source.read() {
-            println!("row = {}", row.to_string(source.schema()));
+            println!("row = {}", row.to_string(source.schema()).unwrap());
         }
     }
 }
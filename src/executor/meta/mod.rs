@@ -4,6 +4,9 @@ pub mod row;
 /// 多行
 pub mod rows;
 
+/// 列式存储的一批行
+pub mod batch;
+
 /// 列（字段）元信息
 pub mod column;
 
@@ -12,3 +15,6 @@ pub mod schema;
 
 /// 数据源
 pub mod data_source;
+
+/// 表的统计信息
+pub mod statistics;
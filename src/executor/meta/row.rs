@@ -1,52 +1,211 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
 
-use crate::executor::types::{
-    flout64::Float64, int32::Int32, varchar::Varchar, DataType, OwnValue,
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        types::{
+            blob,
+            datetime::{Date, Interval, Time, Timestamp},
+            decimal::{Decimal, MAX_PRECISION},
+            flout64::Float64,
+            int32::Int32,
+            int64::Int64,
+            varchar::Varchar,
+            DataType, OwnValue,
+        },
+    },
 };
 
 use super::schema::Schema;
 
 /// Row 行接口
+/// 读取列的方法在下标越界或者类型不符（包括值为 NULL）时返回错误
+/// 行不知道列名，错误中用 #下标 表示列，知道 schema 的调用方再替换为列名，见 Row::to_string
 pub trait Row: Debug {
-    fn is_null(&self, index: usize) -> bool;
-    fn get_int32(&self, index: usize) -> Int32;
-    fn get_float64(&self, index: usize) -> Float64;
-    fn get_varchar<'a>(&'a self, index: usize) -> Varchar<'a>;
-    fn get_string<'a>(&'a self, index: usize) -> &'a str;
+    fn get(&self, index: usize) -> Result<&OwnValue, ExecuteError>;
 
-    fn get(&self, index: usize) -> &OwnValue;
+    fn is_null(&self, index: usize) -> Result<bool, ExecuteError> {
+        Ok(matches!(self.get(index)?, OwnValue::Null))
+    }
+
+    fn get_int32(&self, index: usize) -> Result<Int32, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Int32(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Int32, value)),
+        }
+    }
+
+    fn get_float64(&self, index: usize) -> Result<Float64, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Float64(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Float64, value)),
+        }
+    }
+
+    fn get_boolean(&self, index: usize) -> Result<bool, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Boolean(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Boolean, value)),
+        }
+    }
+
+    fn get_int64(&self, index: usize) -> Result<Int64, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Int64(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Int64, value)),
+        }
+    }
+
+    fn get_decimal(&self, index: usize) -> Result<Decimal, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Decimal(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Decimal(MAX_PRECISION, 0), value)),
+        }
+    }
+
+    fn get_date(&self, index: usize) -> Result<Date, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Date(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Date, value)),
+        }
+    }
+
+    fn get_time(&self, index: usize) -> Result<Time, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Time(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Time, value)),
+        }
+    }
+
+    fn get_timestamp(&self, index: usize) -> Result<Timestamp, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Timestamp(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Timestamp, value)),
+        }
+    }
+
+    fn get_interval(&self, index: usize) -> Result<Interval, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Interval(v) => Ok(*v),
+            value => Err(mismatch(index, DataType::Interval, value)),
+        }
+    }
+
+    fn get_blob(&self, index: usize) -> Result<&[u8], ExecuteError> {
+        match self.get(index)? {
+            OwnValue::Blob(v) => Ok(v),
+            value => Err(mismatch(index, DataType::Blob, value)),
+        }
+    }
+
+    fn get_varchar(&self, index: usize) -> Result<Varchar<'_>, ExecuteError> {
+        self.get_string(index).map(Varchar::ref_string)
+    }
+
+    fn get_string(&self, index: usize) -> Result<&str, ExecuteError> {
+        match self.get(index)? {
+            OwnValue::String(s) => Ok(s),
+            value => Err(mismatch(index, DataType::String, value)),
+        }
+    }
 
-    fn to_string<'a>(&'a self, schema: &Schema) -> String {
+    /// 按 schema 中的类型格式化整行，类型不符时返回带列名的错误
+    fn to_string(&self, schema: &Schema) -> Result<String, ExecuteError> {
         let mut buf: String = "[".to_string();
         for (index, column) in schema.columns.iter().enumerate() {
             if index > 0 {
                 buf.push_str(", ");
             }
-            if self.is_null(index) {
+            let actual: &OwnValue = self.get(index)?;
+            if *actual == OwnValue::Null {
                 buf.push_str("NULL");
                 continue;
             }
-            match column.data_type {
-                DataType::Int32 => {
-                    buf.push_str(&self.get_int32(index).to_string());
-                }
-                DataType::Float64 => {
-                    buf.push_str(&self.get_float64(index).to_string());
-                }
-                DataType::Varchar(_) => {
-                    buf.push('"');
-                    buf.push_str(&self.get_varchar(index).to_string());
-                    buf.push('"');
-                }
-                DataType::String => {
-                    buf.push('"');
-                    buf.push_str(&self.get_string(index).to_string());
-                    buf.push('"');
-                }
-            }
+            let value: Result<String, ExecuteError> = match column.data_type {
+                DataType::Boolean => self.get_boolean(index).map(|v| v.to_string()),
+                DataType::Int32 => self.get_int32(index).map(|v| v.to_string()),
+                DataType::Int64 => self.get_int64(index).map(|v| v.to_string()),
+                DataType::Decimal(precision, scale) => self
+                    .get_decimal(index)
+                    .and_then(|v| v.check(precision, scale))
+                    .map(|v| v.to_string()),
+                DataType::Float64 => self.get_float64(index).map(|v| v.to_string()),
+                DataType::Varchar(limit) => self
+                    .get_varchar(index)
+                    .and_then(|v| v.check(limit))
+                    .map(|v| format!("\"{}\"", v)),
+                DataType::String => self.get_string(index).map(|s| format!("\"{}\"", s)),
+                DataType::Date => self.get_date(index).map(|v| v.to_string()),
+                DataType::Time => self.get_time(index).map(|v| v.to_string()),
+                DataType::Timestamp => self.get_timestamp(index).map(|v| v.to_string()),
+                DataType::Interval => self.get_interval(index).map(|v| v.to_string()),
+                DataType::Blob => self
+                    .get_blob(index)
+                    .map(|v| format!("\\x{}", blob::to_hex(v))),
+                DataType::Null => Err(mismatch(index, DataType::Null, actual)),
+            };
+            // 错误中的列下标替换为列名，类型不符时报告 schema 中声明的类型
+            let value: String = value.map_err(|error| match error.code {
+                ErrorCode::StringDataRightTruncation => ExecuteError::new(
+                    error.code,
+                    format!(
+                        "value too long for column {} of type {}",
+                        column.name, column.data_type
+                    ),
+                ),
+                ErrorCode::DatatypeMismatch => ExecuteError::type_mismatch(
+                    &column.name,
+                    &column.data_type,
+                    &actual.data_type(),
+                ),
+                _ => error,
+            })?;
+            buf.push_str(&value);
         }
         buf.push(']');
-        buf
+        Ok(buf)
+    }
+}
+
+fn mismatch(index: usize, expected: DataType, value: &OwnValue) -> ExecuteError {
+    ExecuteError::type_mismatch(format!("#{}", index), &expected, &value.data_type())
+}
+
+/// 第 index 列超出行的宽度，说明计划中的列下标有误
+pub fn out_of_range(index: usize, width: usize) -> ExecuteError {
+    ExecuteError::new(
+        ErrorCode::InternalError,
+        format!(
+            "column #{} out of range, rows have {} columns",
+            index, width
+        ),
+    )
+}
+
+/// ProjectedRow 借用底层的行，第 i 列是底层行的第 projection[i] 列，不复制任何值
+#[derive(Debug, Clone)]
+pub struct ProjectedRow<'a> {
+    row: &'a dyn Row,
+    projection: Rc<[u16]>,
+}
+
+impl<'a> ProjectedRow<'a> {
+    pub fn new(row: &'a dyn Row, projection: Rc<[u16]>) -> Self {
+        Self { row, projection }
+    }
+
+    fn index(&self, index: usize) -> Result<usize, ExecuteError> {
+        match self.projection.get(index) {
+            Some(index) => Ok(*index as usize),
+            None => Err(out_of_range(index, self.projection.len())),
+        }
+    }
+}
+
+impl<'a> Row for ProjectedRow<'a> {
+    fn get(&self, index: usize) -> Result<&OwnValue, ExecuteError> {
+        self.row.get(self.index(index)?)
     }
 }
 
@@ -64,42 +223,98 @@ impl SimpleMemoryRow {
 }
 
 impl Row for SimpleMemoryRow {
-    fn is_null(&self, index: usize) -> bool {
-        match &self.values[index] {
-            OwnValue::Null => true,
-            _ => false,
-        }
+    fn get(&self, index: usize) -> Result<&OwnValue, ExecuteError> {
+        self.values
+            .get(index)
+            .ok_or_else(|| out_of_range(index, self.values.len()))
     }
+}
 
-    fn get_int32(&self, index: usize) -> Int32 {
-        match &self.values[index] {
-            OwnValue::Int32(v) => *v,
-            _ => panic!("type mismatch"),
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
 
-    fn get_float64(&self, index: usize) -> Float64 {
-        match &self.values[index] {
-            OwnValue::Float64(v) => *v,
-            _ => panic!("type mismatch"),
-        }
-    }
+    use crate::{
+        error::ErrorCode,
+        executor::{
+            error::ExecuteError,
+            meta::{column::Column, schema::Schema},
+            types::{int32::Int32, DataType, OwnValue},
+        },
+    };
 
-    fn get_varchar(&self, index: usize) -> Varchar<'_> {
-        match &self.values[index] {
-            OwnValue::String(s) => Varchar::ref_string(s),
-            _ => panic!("type mismatch"),
-        }
-    }
+    use super::{ProjectedRow, Row, SimpleMemoryRow};
 
-    fn get_string(&self, index: usize) -> &str {
-        match &self.values[index] {
-            OwnValue::String(s) => s,
-            _ => panic!("type mismatch"),
-        }
-    }
+    #[test]
+    fn typed_accessors() {
+        let column = |name: &str, data_type: DataType| Column {
+            name: name.into(),
+            data_type,
+            nullable: true,
+            default: None,
+        };
+        let schema: Schema = Schema {
+            name: "t".into(),
+            columns: vec![
+                column("id", DataType::Int32),
+                column("name", DataType::Varchar(8)),
+                column("score", DataType::Float64),
+            ]
+            .into_boxed_slice(),
+        };
+        let row: SimpleMemoryRow = SimpleMemoryRow::new(vec![
+            OwnValue::Int32(Int32::new(1)),
+            OwnValue::String("a".into()),
+            OwnValue::Null,
+        ]);
+        assert_eq!(row.get_int32(0).unwrap(), Int32::new(1));
+        assert_eq!(row.get_varchar(1).unwrap().to_string(), "a");
+        assert!(row.is_null(2).unwrap());
+        assert_eq!(
+            row.get_float64(0).unwrap_err().to_string(),
+            "type mismatch for column #0: expected Float64, found Int32"
+        );
+        assert_eq!(
+            row.get_string(2).unwrap_err().to_string(),
+            "type mismatch for column #2: expected String, found Null"
+        );
+        // 越界的下标返回内部错误
+        let error: ExecuteError = row.is_null(3).unwrap_err();
+        assert_eq!(error.code, ErrorCode::InternalError);
+        assert_eq!(
+            error.to_string(),
+            "column #3 out of range, rows have 3 columns"
+        );
+
+        let projected: ProjectedRow = ProjectedRow::new(&row, Rc::new([1, 0, 5]));
+        assert_eq!(projected.get_string(0).unwrap(), "a");
+        assert!(projected.get_string(1).is_err());
+        assert_eq!(
+            projected.get(3).unwrap_err().to_string(),
+            "column #3 out of range, rows have 3 columns"
+        );
+        assert_eq!(
+            projected.get(2).unwrap_err().to_string(),
+            "column #5 out of range, rows have 3 columns"
+        );
 
-    fn get(&self, index: usize) -> &OwnValue {
-        &self.values[index]
+        // 按 schema 格式化时，错误中是列名和声明的类型
+        assert_eq!(row.to_string(&schema).unwrap(), "[1, \"a\", NULL]");
+        assert_eq!(
+            projected.to_string(&schema).unwrap_err().to_string(),
+            "type mismatch for column id: expected Int32, found String"
+        );
+        let schema: Schema = Schema {
+            name: "t".into(),
+            columns: vec![
+                column("id", DataType::Int32),
+                column("name", DataType::Varchar(0)),
+            ]
+            .into_boxed_slice(),
+        };
+        assert_eq!(
+            row.to_string(&schema).unwrap_err().to_string(),
+            "value too long for column name of type Varchar(0)"
+        );
     }
 }
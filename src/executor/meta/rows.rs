@@ -1,11 +1,31 @@
-use crate::executor::types::{flout64::Float64, int32::Int32, varchar::Varchar};
+use crate::executor::{
+    error::ExecuteError,
+    types::{
+        datetime::{Date, Interval, Time, Timestamp},
+        decimal::Decimal,
+        flout64::Float64,
+        int32::Int32,
+        int64::Int64,
+        varchar::Varchar,
+    },
+};
 
+/// Rows 逐行遍历的游标，next 返回 true 后才能读取当前行
+/// 读取的方法在没有当前行、下标越界或者类型不符时返回错误，与 Row 一样用 #下标 表示列
 pub trait Rows {
     fn next(&mut self) -> bool;
 
-    fn is_null(&self, index: usize) -> bool;
-    fn get_int32(&self, index: usize) -> Int32;
-    fn get_float64(&self, index: usize) -> Float64;
-    fn get_varchar<'a>(&'a self, index: usize) -> Varchar<'a>;
-    fn get_string<'a>(&'a self, index: usize) -> &'a str;
+    fn is_null(&self, index: usize) -> Result<bool, ExecuteError>;
+    fn get_boolean(&self, index: usize) -> Result<bool, ExecuteError>;
+    fn get_int32(&self, index: usize) -> Result<Int32, ExecuteError>;
+    fn get_int64(&self, index: usize) -> Result<Int64, ExecuteError>;
+    fn get_decimal(&self, index: usize) -> Result<Decimal, ExecuteError>;
+    fn get_float64(&self, index: usize) -> Result<Float64, ExecuteError>;
+    fn get_varchar(&self, index: usize) -> Result<Varchar<'_>, ExecuteError>;
+    fn get_string(&self, index: usize) -> Result<&str, ExecuteError>;
+    fn get_date(&self, index: usize) -> Result<Date, ExecuteError>;
+    fn get_time(&self, index: usize) -> Result<Time, ExecuteError>;
+    fn get_timestamp(&self, index: usize) -> Result<Timestamp, ExecuteError>;
+    fn get_interval(&self, index: usize) -> Result<Interval, ExecuteError>;
+    fn get_blob(&self, index: usize) -> Result<&[u8], ExecuteError>;
 }
@@ -19,6 +19,13 @@ impl Schema {
         }
         false
     }
+
+    /// 按列名查找列下标
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.name.as_ref() == name)
+    }
 }
 
 impl Display for Schema {
@@ -0,0 +1,163 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use crate::executor::{
+    expression::eval::compare, plan::physical::aggregate::GroupKey, types::OwnValue,
+};
+
+use super::{row::Row, schema::Schema};
+
+/// Statistics 表的统计信息，用于估算计划的代价
+/// columns 与 schema 中的列一一对应
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    pub row_count: usize,
+    pub columns: Box<[ColumnStatistics]>,
+}
+
+/// ColumnStatistics 列的统计信息，min、max 只统计非 NULL 值，没有非 NULL 值时为 NULL
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStatistics {
+    pub distinct_count: usize,
+    pub null_count: usize,
+    pub min: OwnValue,
+    pub max: OwnValue,
+}
+
+impl Statistics {
+    /// 扫描所有行计算统计信息，不同值的个数是精确值
+    pub fn compute<'a>(schema: &Schema, rows: impl Iterator<Item = &'a dyn Row>) -> Self {
+        let width: usize = schema.columns.len();
+        let mut distinct: Vec<HashSet<GroupKey>> = vec![HashSet::new(); width];
+        let mut columns: Vec<ColumnStatistics> = vec![
+            ColumnStatistics {
+                distinct_count: 0,
+                null_count: 0,
+                min: OwnValue::Null,
+                max: OwnValue::Null,
+            };
+            width
+        ];
+        let mut row_count: usize = 0;
+        for row in rows {
+            row_count += 1;
+            for (index, column) in columns.iter_mut().enumerate() {
+                // 统计信息只用于估算代价，读不出的值按 NULL 统计
+                let value: &OwnValue = match row.get(index) {
+                    Ok(OwnValue::Null) | Err(_) => {
+                        column.null_count += 1;
+                        continue;
+                    }
+                    Ok(value) => value,
+                };
+                if column.min == OwnValue::Null
+                    || compare(value, &column.min) == Some(Ordering::Less)
+                {
+                    column.min = value.clone();
+                }
+                if column.max == OwnValue::Null
+                    || compare(value, &column.max) == Some(Ordering::Greater)
+                {
+                    column.max = value.clone();
+                }
+                distinct[index].insert(GroupKey::new(vec![value.clone()]));
+            }
+        }
+        for (column, values) in columns.iter_mut().zip(distinct) {
+            column.distinct_count = values.len();
+        }
+        Self {
+            row_count,
+            columns: columns.into_boxed_slice(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::{
+        meta::{
+            column::Column,
+            data_source::{DataSource, MutableDataSource, SimpleMemoryDataSource},
+            row::SimpleMemoryRow,
+            schema::Schema,
+        },
+        types::{int32::Int32, DataType, OwnValue},
+    };
+
+    use super::{ColumnStatistics, Statistics};
+
+    #[test]
+    fn test_statistics() {
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(Schema {
+            name: "stu".into(),
+            columns: vec![
+                Column {
+                    name: "name".into(),
+                    data_type: DataType::String,
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "age".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        });
+        for (name, age) in [
+            (
+                OwnValue::String("张三".into()),
+                OwnValue::Int32(Int32::new(18)),
+            ),
+            (
+                OwnValue::String("李四".into()),
+                OwnValue::Int32(Int32::new(20)),
+            ),
+            (OwnValue::String("张三".into()), OwnValue::Null),
+            (OwnValue::Null, OwnValue::Int32(Int32::new(18))),
+        ] {
+            source.push_row(SimpleMemoryRow::new(vec![name, age]));
+        }
+
+        let statistics: Statistics = source.statistics();
+        assert_eq!(statistics.row_count, 4);
+        assert_eq!(
+            statistics.columns[0],
+            ColumnStatistics {
+                distinct_count: 2,
+                null_count: 1,
+                min: OwnValue::String("张三".into()),
+                max: OwnValue::String("李四".into()),
+            }
+        );
+        assert_eq!(
+            statistics.columns[1],
+            ColumnStatistics {
+                distinct_count: 2,
+                null_count: 1,
+                min: OwnValue::Int32(Int32::new(18)),
+                max: OwnValue::Int32(Int32::new(20)),
+            }
+        );
+
+        // 修改数据后重新计算
+        source.insert(vec![SimpleMemoryRow::new(vec![
+            OwnValue::String("王五".into()),
+            OwnValue::Int32(Int32::new(22)),
+        ])]);
+        assert_eq!(source.statistics().row_count, 5);
+        source.update(vec![(
+            4,
+            SimpleMemoryRow::new(vec![OwnValue::Null, OwnValue::Null]),
+        )]);
+        assert_eq!(source.statistics().columns[1].null_count, 2);
+        source.delete(&[0, 1]);
+        assert_eq!(source.statistics().row_count, 3);
+
+        let empty: Statistics = SimpleMemoryDataSource::new(source.schema().clone()).statistics();
+        assert_eq!(empty.row_count, 0);
+        assert_eq!(empty.columns[1].min, OwnValue::Null);
+    }
+}
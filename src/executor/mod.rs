@@ -12,3 +12,22 @@ pub mod context;
 
 /// 错误对象
 pub mod error;
+
+/// 绑定后的表达式
+pub mod expression;
+
+/// 语义分析，将 AST 中的标识符绑定到 Context 中的表和列
+pub mod binder;
+
+/// 计划生成：SQL 语句 -> 逻辑计划 -> 物理计划
+pub mod planner;
+
+/// 数据定义语句：CREATE TABLE、DROP TABLE、ALTER TABLE
+pub mod ddl;
+
+/// 数据操作语句：INSERT、UPDATE、DELETE
+pub mod dml;
+
+/// 单元测试共用的上下文和辅助函数
+#[cfg(test)]
+pub(crate) mod test_util;
@@ -0,0 +1,58 @@
+use crate::executor::{
+    expression::{AggregateCall, BoundExpression},
+    meta::schema::Schema,
+    plan::format_tree,
+};
+
+use super::{LogicNode, LogicPlan};
+
+/// Aggregate 按 group_by 分组，每组输出一行
+/// 输出列依次为 group_by 的各个表达式，然后是 aggregates 的各个聚合函数
+/// 没有 group_by 时，所有行为一组，即使输入为空也输出一行
+#[derive(Debug)]
+pub struct Aggregate<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub group_by: Box<[BoundExpression]>,
+    pub aggregates: Box<[AggregateCall]>,
+    pub schema: Schema,
+}
+
+impl<'a> Aggregate<'a> {
+    pub fn new(
+        input: LogicNode<'a>,
+        group_by: Box<[BoundExpression]>,
+        aggregates: Box<[AggregateCall]>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(group_by.len() + aggregates.len(), schema.columns.len());
+        Self {
+            input: Box::new(input),
+            group_by,
+            aggregates,
+            schema,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Aggregate<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let group_by: Vec<String> = self.group_by.iter().map(|e| e.to_string()).collect();
+        let aggregates: Vec<String> = self.aggregates.iter().map(|a| a.to_string()).collect();
+        format_tree(
+            format!(
+                "Aggregate: group_by=[{}] aggregates=[{}]",
+                group_by.join(", "),
+                aggregates.join(", ")
+            ),
+            vec![self.input.to_string()],
+        )
+    }
+}
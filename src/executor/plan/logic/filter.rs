@@ -0,0 +1,36 @@
+use crate::executor::{expression::BoundExpression, meta::schema::Schema, plan::format_tree};
+
+use super::{LogicNode, LogicPlan};
+
+/// Filter 只保留 predicate 为真的行
+#[derive(Debug)]
+pub struct Filter<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub predicate: BoundExpression,
+}
+
+impl<'a> Filter<'a> {
+    pub fn new(input: LogicNode<'a>, predicate: BoundExpression) -> Self {
+        Self {
+            input: Box::new(input),
+            predicate,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Filter<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        format_tree(
+            format!("Filter: {}", self.predicate),
+            vec![self.input.to_string()],
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    executor::{expression::BoundExpression, meta::schema::Schema, plan::format_tree},
+    sql::parser::ast::select::JoinType,
+};
+
+use super::{LogicNode, LogicPlan};
+
+/// Join 连接左右两个输入，输出的行为左右两行的拼接
+/// condition 中的列引用指向拼接后的行，为 None 时是笛卡尔积
+#[derive(Debug)]
+pub struct Join<'a> {
+    pub left: Box<LogicNode<'a>>,
+    pub right: Box<LogicNode<'a>>,
+    pub join_type: JoinType,
+    pub condition: Option<BoundExpression>,
+    pub schema: Schema,
+}
+
+impl<'a> Join<'a> {
+    pub fn new(
+        left: LogicNode<'a>,
+        right: LogicNode<'a>,
+        join_type: JoinType,
+        condition: Option<BoundExpression>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(
+            left.schema().columns.len() + right.schema().columns.len(),
+            schema.columns.len()
+        );
+        Self {
+            left: Box::new(left),
+            right: Box::new(right),
+            join_type,
+            condition,
+            schema,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Join<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let head: String = match &self.condition {
+            Some(condition) => format!("Join: {} ON {}", self.join_type, condition),
+            None => format!("Join: {}", self.join_type),
+        };
+        format_tree(head, vec![self.left.to_string(), self.right.to_string()])
+    }
+}
@@ -0,0 +1,42 @@
+use crate::executor::{meta::schema::Schema, plan::format_tree};
+
+use super::{LogicNode, LogicPlan};
+
+/// Limit 跳过前 offset 行，最多输出 limit 行
+#[derive(Debug)]
+pub struct Limit<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub limit: Option<u64>,
+    pub offset: u64,
+}
+
+impl<'a> Limit<'a> {
+    pub fn new(input: LogicNode<'a>, limit: Option<u64>, offset: u64) -> Self {
+        Self {
+            input: Box::new(input),
+            limit,
+            offset,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Limit<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        let limit: String = match self.limit {
+            Some(limit) => limit.to_string(),
+            None => "ALL".to_string(),
+        };
+        format_tree(
+            format!("Limit: limit={} offset={}", limit, self.offset),
+            vec![self.input.to_string()],
+        )
+    }
+}
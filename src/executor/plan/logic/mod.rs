@@ -1,11 +1,125 @@
 use crate::executor::meta::schema::Schema;
 
+use aggregate::Aggregate;
+use filter::Filter;
+use join::Join;
+use limit::Limit;
+use one_row::OneRow;
+use projection::Projection;
+use scan::Scan;
+use sort::Sort;
+use subquery_alias::SubqueryAlias;
+
 /// 表扫描逻辑计划
 pub mod scan;
 
+/// 单行逻辑计划，对应没有 FROM 子句的 SELECT
+pub mod one_row;
+
+/// 过滤逻辑计划，对应 WHERE 和 HAVING
+pub mod filter;
+
+/// 投影逻辑计划，计算 SELECT 的输出列
+pub mod projection;
+
+/// 聚合逻辑计划，对应 GROUP BY
+pub mod aggregate;
+
+/// 排序逻辑计划，对应 ORDER BY
+pub mod sort;
+
+/// 分页逻辑计划，对应 LIMIT 和 OFFSET
+pub mod limit;
+
+/// 子查询别名逻辑计划，对应 FROM (SELECT ...) AS t
+pub mod subquery_alias;
+
+/// 连接逻辑计划，对应 JOIN 和 FROM 中逗号分隔的多个表
+pub mod join;
+
 /// 逻辑计划
 pub trait LogicPlan {
-    fn children(&self) -> &[&dyn LogicPlan];
+    fn children(&self) -> Vec<&dyn LogicPlan>;
     fn schema(&self) -> &Schema;
     fn to_string(&self) -> String;
 }
+
+/// LogicNode 逻辑计划树的节点
+#[derive(Debug)]
+pub enum LogicNode<'a> {
+    Scan(Scan<'a>),
+    OneRow(OneRow),
+    Filter(Filter<'a>),
+    Projection(Projection<'a>),
+    Aggregate(Aggregate<'a>),
+    Sort(Sort<'a>),
+    Limit(Limit<'a>),
+    SubqueryAlias(SubqueryAlias<'a>),
+    Join(Join<'a>),
+}
+
+impl<'a> LogicNode<'a> {
+    fn plan(&self) -> &dyn LogicPlan {
+        match self {
+            LogicNode::Scan(plan) => plan,
+            LogicNode::OneRow(plan) => plan,
+            LogicNode::Filter(plan) => plan,
+            LogicNode::Projection(plan) => plan,
+            LogicNode::Aggregate(plan) => plan,
+            LogicNode::Sort(plan) => plan,
+            LogicNode::Limit(plan) => plan,
+            LogicNode::SubqueryAlias(plan) => plan,
+            LogicNode::Join(plan) => plan,
+        }
+    }
+
+    /// 用 f 的返回值依次替换每个子节点
+    pub fn map_children(self, f: &mut dyn FnMut(LogicNode<'a>) -> LogicNode<'a>) -> LogicNode<'a> {
+        match self {
+            LogicNode::Scan(_) | LogicNode::OneRow(_) => self,
+            LogicNode::Filter(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::Filter(plan)
+            }
+            LogicNode::Projection(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::Projection(plan)
+            }
+            LogicNode::Aggregate(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::Aggregate(plan)
+            }
+            LogicNode::Sort(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::Sort(plan)
+            }
+            LogicNode::Limit(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::Limit(plan)
+            }
+            LogicNode::SubqueryAlias(mut plan) => {
+                plan.input = Box::new(f(*plan.input));
+                LogicNode::SubqueryAlias(plan)
+            }
+            LogicNode::Join(mut plan) => {
+                plan.left = Box::new(f(*plan.left));
+                plan.right = Box::new(f(*plan.right));
+                LogicNode::Join(plan)
+            }
+        }
+    }
+}
+
+impl<'a> LogicPlan for LogicNode<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        self.plan().children()
+    }
+
+    fn schema(&self) -> &Schema {
+        self.plan().schema()
+    }
+
+    fn to_string(&self) -> String {
+        self.plan().to_string()
+    }
+}
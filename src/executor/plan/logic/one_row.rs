@@ -0,0 +1,40 @@
+use crate::executor::meta::schema::Schema;
+
+use super::LogicPlan;
+
+/// OneRow 输出一行没有列的空行，作为没有 FROM 子句的 SELECT 的输入
+#[derive(Debug)]
+pub struct OneRow {
+    pub schema: Schema,
+}
+
+impl OneRow {
+    pub fn new() -> Self {
+        Self {
+            schema: Schema {
+                name: "".into(),
+                columns: vec![].into_boxed_slice(),
+            },
+        }
+    }
+}
+
+impl Default for OneRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogicPlan for OneRow {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        "OneRow".to_string()
+    }
+}
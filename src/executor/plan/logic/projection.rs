@@ -0,0 +1,40 @@
+use crate::executor::{expression::BoundExpression, meta::schema::Schema, plan::format_tree};
+
+use super::{LogicNode, LogicPlan};
+
+/// Projection 对每一行计算 expressions，输出列由 schema 描述
+#[derive(Debug)]
+pub struct Projection<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub expressions: Box<[BoundExpression]>,
+    pub schema: Schema,
+}
+
+impl<'a> Projection<'a> {
+    pub fn new(input: LogicNode<'a>, expressions: Box<[BoundExpression]>, schema: Schema) -> Self {
+        debug_assert_eq!(expressions.len(), schema.columns.len());
+        Self {
+            input: Box::new(input),
+            expressions,
+            schema,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Projection<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let expressions: Vec<String> = self.expressions.iter().map(|e| e.to_string()).collect();
+        format_tree(
+            format!("Projection: {}", expressions.join(", ")),
+            vec![self.input.to_string()],
+        )
+    }
+}
@@ -1,12 +1,18 @@
-use crate::executor::meta::{data_source::DataSource, schema::Schema};
+use crate::executor::{
+    expression::BoundExpression,
+    meta::{data_source::DataSource, schema::Schema},
+};
 
 use super::LogicPlan;
 
+/// Scan 读取表中 projection 指定的列，输出列由 schema 描述
+/// filter 为下推到扫描中的谓词，列引用指向扫描的输出行
 #[derive(Debug)]
 pub struct Scan<'a> {
     pub data_source: &'a dyn DataSource,
     pub projection: Box<[Box<str>]>,
     pub schema: Schema,
+    pub filter: Option<BoundExpression>,
 }
 
 impl<'a> Scan<'a> {
@@ -17,7 +23,7 @@ impl<'a> Scan<'a> {
     ) -> Self {
         #[cfg(debug_assertions)]
         {
-            assert!(projection.len() > 0, "projection must not be empty");
+            assert!(!projection.is_empty(), "projection must not be empty");
             for col in &projection {
                 assert!(
                     schema.contains_column_name(col.as_ref()),
@@ -30,13 +36,14 @@ impl<'a> Scan<'a> {
             data_source,
             projection,
             schema,
+            filter: None,
         }
     }
 }
 
 impl<'a> LogicPlan for Scan<'a> {
-    fn children(&self) -> &[&dyn LogicPlan] {
-        &[]
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![]
     }
 
     fn schema(&self) -> &Schema {
@@ -44,6 +51,10 @@ impl<'a> LogicPlan for Scan<'a> {
     }
 
     fn to_string(&self) -> String {
-        format!("{}", self.schema)
+        let head: String = format!("Scan: {} [{}]", self.schema, self.projection.join(", "));
+        match &self.filter {
+            Some(filter) => format!("{} filter={}", head, filter),
+            None => head,
+        }
     }
 }
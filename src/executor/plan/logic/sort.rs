@@ -0,0 +1,37 @@
+use crate::executor::{binder::bound::BoundOrderBy, meta::schema::Schema, plan::format_tree};
+
+use super::{LogicNode, LogicPlan};
+
+/// Sort 按 order_by 依次比较排序
+#[derive(Debug)]
+pub struct Sort<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub order_by: Box<[BoundOrderBy]>,
+}
+
+impl<'a> Sort<'a> {
+    pub fn new(input: LogicNode<'a>, order_by: Box<[BoundOrderBy]>) -> Self {
+        Self {
+            input: Box::new(input),
+            order_by,
+        }
+    }
+}
+
+impl<'a> LogicPlan for Sort<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        let order_by: Vec<String> = self.order_by.iter().map(|o| o.to_string()).collect();
+        format_tree(
+            format!("Sort: {}", order_by.join(", ")),
+            vec![self.input.to_string()],
+        )
+    }
+}
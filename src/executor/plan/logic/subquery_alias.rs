@@ -0,0 +1,37 @@
+use crate::executor::{meta::schema::Schema, plan::format_tree};
+
+use super::{LogicNode, LogicPlan};
+
+/// SubqueryAlias 给子查询的输出命名，schema.name 即别名
+#[derive(Debug)]
+pub struct SubqueryAlias<'a> {
+    pub input: Box<LogicNode<'a>>,
+    pub schema: Schema,
+}
+
+impl<'a> SubqueryAlias<'a> {
+    pub fn new(input: LogicNode<'a>, schema: Schema) -> Self {
+        debug_assert_eq!(input.schema().columns.len(), schema.columns.len());
+        Self {
+            input: Box::new(input),
+            schema,
+        }
+    }
+}
+
+impl<'a> LogicPlan for SubqueryAlias<'a> {
+    fn children(&self) -> Vec<&dyn LogicPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        format_tree(
+            format!("SubqueryAlias: {}", self.schema),
+            vec![self.input.to_string()],
+        )
+    }
+}
@@ -3,3 +3,15 @@ pub mod logic;
 
 /// 物理计划
 pub mod physical;
+
+/// 渲染缩进的树形计划，每个子树相对父节点缩进两个空格
+pub fn format_tree(head: String, children: Vec<String>) -> String {
+    let mut buf: String = head;
+    for child in children {
+        for line in child.lines() {
+            buf.push_str("\n  ");
+            buf.push_str(line);
+        }
+    }
+    buf
+}
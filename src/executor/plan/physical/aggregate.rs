@@ -0,0 +1,518 @@
+use std::{borrow::Cow, cmp::Ordering, collections::HashMap};
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        expression::{eval::compare, AggregateCall, AggregateFunction, BoundExpression},
+        meta::{
+            batch::{Batch, ColumnVector, BATCH_SIZE},
+            row::{Row, SimpleMemoryRow},
+            schema::Schema,
+        },
+        plan::format_tree,
+        types::{flout64::Float64, int32::Int32, int64::Int64, OwnValue},
+    },
+};
+
+use super::PhysicalPlan;
+
+/// HashAggregate 哈希聚合
+/// open 时按批读完子计划的所有行，按列计算分组表达式和聚合函数的参数，
+/// 按 group_by 的值放入哈希表并更新各组的聚合状态，之后按分组第一次出现的顺序输出
+pub struct HashAggregate<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub group_by: Box<[BoundExpression]>,
+    pub aggregates: Box<[AggregateCall]>,
+    pub schema: Schema,
+
+    output: std::vec::IntoIter<Vec<OwnValue>>,
+}
+
+impl<'a> HashAggregate<'a> {
+    pub fn new(
+        input: Box<dyn PhysicalPlan<'a> + 'a>,
+        group_by: Box<[BoundExpression]>,
+        aggregates: Box<[AggregateCall]>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(group_by.len() + aggregates.len(), schema.columns.len());
+        Self {
+            input,
+            group_by,
+            aggregates,
+            schema,
+            output: vec![].into_iter(),
+        }
+    }
+
+    fn aggregate(&mut self) -> Result<Vec<Vec<OwnValue>>, ExecuteError> {
+        let mut indices: HashMap<GroupKey, usize> = HashMap::new();
+        let mut groups: Vec<(GroupKey, Vec<Accumulator>)> = vec![];
+        while let Some(batch) = self.input.batch()? {
+            let mut keys: Vec<Cow<ColumnVector>> = Vec::with_capacity(self.group_by.len());
+            for expression in self.group_by.iter() {
+                keys.push(expression.evaluate_batch(&batch)?);
+            }
+            // COUNT(*) 没有参数，统计所有行
+            let mut arguments: Vec<Option<Cow<ColumnVector>>> =
+                Vec::with_capacity(self.aggregates.len());
+            for call in self.aggregates.iter() {
+                arguments.push(match &call.argument {
+                    Some(argument) => Some(argument.evaluate_batch(&batch)?),
+                    None => None,
+                });
+            }
+
+            for row in 0..batch.len() {
+                let key: GroupKey = GroupKey::new(keys.iter().map(|key| key.get(row)).collect());
+                let index: usize = match indices.get(&key) {
+                    Some(index) => *index,
+                    None => {
+                        indices.insert(key.clone(), groups.len());
+                        groups.push((key, self.accumulators()));
+                        groups.len() - 1
+                    }
+                };
+                let accumulators: &mut Vec<Accumulator> = &mut groups[index].1;
+                for ((accumulator, call), argument) in accumulators
+                    .iter_mut()
+                    .zip(self.aggregates.iter())
+                    .zip(arguments.iter())
+                {
+                    let value: OwnValue = match argument {
+                        Some(argument) => argument.get(row),
+                        None => OwnValue::Int32(Int32::new(1)),
+                    };
+                    accumulator.accumulate(call, value)?;
+                }
+            }
+        }
+
+        // 没有 GROUP BY 时，空输入也输出一行
+        if groups.is_empty() && self.group_by.is_empty() {
+            groups.push((GroupKey::new(vec![]), self.accumulators()));
+        }
+
+        let mut rows: Vec<Vec<OwnValue>> = Vec::with_capacity(groups.len());
+        for (key, accumulators) in groups {
+            let mut values: Vec<OwnValue> = key.0.into_vec();
+            for accumulator in accumulators {
+                values.push(accumulator.finish()?);
+            }
+            rows.push(values);
+        }
+        Ok(rows)
+    }
+
+    fn accumulators(&self) -> Vec<Accumulator> {
+        self.aggregates
+            .iter()
+            .map(|call| Accumulator::new(call.function))
+            .collect()
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for HashAggregate<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let group_by: Vec<String> = self.group_by.iter().map(|e| e.to_string()).collect();
+        let aggregates: Vec<String> = self.aggregates.iter().map(|a| a.to_string()).collect();
+        format_tree(
+            format!(
+                "HashAggregate: group_by=[{}] aggregates=[{}]",
+                group_by.join(", "),
+                aggregates.join(", ")
+            ),
+            vec![self.input.to_string()],
+        )
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.input.open()?;
+        self.output = self.aggregate()?.into_iter();
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        Ok(self
+            .output
+            .next()
+            .map(|values| Box::new(SimpleMemoryRow::new(values)) as Box<dyn Row + 'a>))
+    }
+
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        let mut batch: Batch = Batch::new(&self.schema);
+        for values in self.output.by_ref().take(BATCH_SIZE) {
+            batch.push(values)?;
+        }
+        Ok(if batch.is_empty() { None } else { Some(batch) })
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.output = vec![].into_iter();
+        self.input.close()
+    }
+}
+
+/// GroupKey 分组键
+/// NULL 与 NULL 属于同一组；浮点数按全序比较，0.0 与 -0.0 同组，所有 NaN 同组
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroupKey(pub Box<[OwnValue]>);
+
+impl GroupKey {
+    pub fn new(values: Vec<OwnValue>) -> Self {
+        Self(values.into_boxed_slice())
+    }
+}
+
+/// Accumulator 一个分组内聚合函数的中间状态，NULL 值不参与聚合
+#[derive(Debug)]
+pub enum Accumulator {
+    Count(i64),
+    Sum(OwnValue),
+    Avg { sum: f64, count: i64 },
+    Min(OwnValue),
+    Max(OwnValue),
+}
+
+impl Accumulator {
+    pub fn new(function: AggregateFunction) -> Self {
+        match function {
+            AggregateFunction::Count => Accumulator::Count(0),
+            AggregateFunction::Sum => Accumulator::Sum(OwnValue::Null),
+            AggregateFunction::Avg => Accumulator::Avg { sum: 0.0, count: 0 },
+            AggregateFunction::Min => Accumulator::Min(OwnValue::Null),
+            AggregateFunction::Max => Accumulator::Max(OwnValue::Null),
+        }
+    }
+
+    /// 用一个值更新状态
+    pub fn accumulate(
+        &mut self,
+        call: &AggregateCall,
+        value: OwnValue,
+    ) -> Result<(), ExecuteError> {
+        if value == OwnValue::Null {
+            return Ok(());
+        }
+        match self {
+            Accumulator::Count(count) => *count += 1,
+            Accumulator::Sum(sum) => {
+                // Int32 的和与 SUM 的结果类型一致，按 Int64 累加
+                let value: OwnValue = match value {
+                    OwnValue::Int32(v) => OwnValue::Int64(Int64::new(v.value() as i64)),
+                    value => value,
+                };
+                *sum = match (&*sum, &value) {
+                    (OwnValue::Null, _) => value,
+                    (OwnValue::Int64(l), OwnValue::Int64(r)) => {
+                        OwnValue::Int64((*l + *r).map_err(|_| {
+                            ExecuteError::new(
+                                ErrorCode::NumericValueOutOfRange,
+                                format!("bigint overflow in {}", call),
+                            )
+                        })?)
+                    }
+                    (OwnValue::Decimal(l), OwnValue::Decimal(r)) => {
+                        OwnValue::Decimal((*l + *r).map_err(|_| {
+                            ExecuteError::new(
+                                ErrorCode::NumericValueOutOfRange,
+                                format!("decimal overflow in {}", call),
+                            )
+                        })?)
+                    }
+                    (OwnValue::Float64(l), OwnValue::Float64(r)) => {
+                        OwnValue::Float64((*l + *r).map_err(|_| {
+                            ExecuteError::new(
+                                ErrorCode::NumericValueOutOfRange,
+                                format!("float overflow in {}", call),
+                            )
+                        })?)
+                    }
+                    _ => return Err(Self::type_error(call, &value)),
+                }
+            }
+            Accumulator::Avg { sum, count } => {
+                *sum += match value.to_float64() {
+                    Some(v) => v.value(),
+                    None => return Err(Self::type_error(call, &value)),
+                };
+                *count += 1;
+            }
+            Accumulator::Min(min) => {
+                if Self::replace(call, min, &value, Ordering::Less)? {
+                    *min = value;
+                }
+            }
+            Accumulator::Max(max) => {
+                if Self::replace(call, max, &value, Ordering::Greater)? {
+                    *max = value;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 输出聚合结果，除 COUNT 外没有非 NULL 输入时结果为 NULL
+    pub fn finish(self) -> Result<OwnValue, ExecuteError> {
+        match self {
+            Accumulator::Count(count) => Ok(OwnValue::Int64(Int64::new(count))),
+            Accumulator::Sum(value) | Accumulator::Min(value) | Accumulator::Max(value) => {
+                Ok(value)
+            }
+            Accumulator::Avg { count: 0, .. } => Ok(OwnValue::Null),
+            Accumulator::Avg { sum, count } => {
+                Ok(OwnValue::Float64(Float64::new(sum / count as f64)))
+            }
+        }
+    }
+
+    /// value 与当前值比较的结果为 ordering 时替换当前值
+    fn replace(
+        call: &AggregateCall,
+        current: &OwnValue,
+        value: &OwnValue,
+        ordering: Ordering,
+    ) -> Result<bool, ExecuteError> {
+        if *current == OwnValue::Null {
+            return Ok(true);
+        }
+        match compare(value, current) {
+            Some(o) => Ok(o == ordering),
+            None => Err(Self::type_error(call, value)),
+        }
+    }
+
+    fn type_error(call: &AggregateCall, value: &OwnValue) -> ExecuteError {
+        ExecuteError::new(
+            ErrorCode::DatatypeMismatch,
+            format!("invalid value {} for {}", value, call),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::ErrorCode,
+        executor::{
+            error::ExecuteError,
+            expression::{AggregateCall, AggregateFunction, BoundExpression, ColumnRef},
+            meta::{
+                column::Column,
+                data_source::{DataSource, SimpleMemoryDataSource},
+                row::SimpleMemoryRow,
+                schema::Schema,
+            },
+            plan::physical::{
+                aggregate::{Accumulator, GroupKey, HashAggregate},
+                scan::SeqScan,
+                PhysicalPlan,
+            },
+            test_util::{collect, collect_batches},
+            types::{flout64::Float64, int32::Int32, int64::Int64, DataType, OwnValue},
+        },
+    };
+
+    fn column(name: &str, index: usize, data_type: DataType) -> BoundExpression {
+        BoundExpression::Column(ColumnRef {
+            table: "score".into(),
+            name: name.into(),
+            index,
+            data_type,
+            nullable: true,
+        })
+    }
+
+    fn call(function: AggregateFunction, data_type: DataType) -> AggregateCall {
+        AggregateCall {
+            function,
+            argument: Some(Box::new(column("point", 1, DataType::Int32))),
+            data_type,
+        }
+    }
+
+    fn source() -> SimpleMemoryDataSource {
+        let schema: Schema = Schema {
+            name: "score".into(),
+            columns: vec![
+                Column {
+                    name: "class".into(),
+                    data_type: DataType::Varchar(8),
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "point".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        for (class, point) in [
+            (OwnValue::String("a".into()), OwnValue::Int32(Int32::new(3))),
+            (OwnValue::Null, OwnValue::Int32(Int32::new(7))),
+            (OwnValue::String("a".into()), OwnValue::Null),
+            (OwnValue::String("b".into()), OwnValue::Int32(Int32::new(2))),
+            (OwnValue::String("a".into()), OwnValue::Int32(Int32::new(6))),
+            (OwnValue::Null, OwnValue::Int32(Int32::new(1))),
+        ] {
+            source.push_row(SimpleMemoryRow::new(vec![class, point]));
+        }
+        source
+    }
+
+    fn aggregate<'a>(
+        source: &'a SimpleMemoryDataSource,
+        group_by: Vec<BoundExpression>,
+    ) -> HashAggregate<'a> {
+        let scan: SeqScan = SeqScan::new(
+            source,
+            vec![0, 1].into_boxed_slice(),
+            source.schema().clone(),
+        );
+        let aggregates: Vec<AggregateCall> = vec![
+            AggregateCall {
+                function: AggregateFunction::Count,
+                argument: None,
+                data_type: DataType::Int64,
+            },
+            call(AggregateFunction::Count, DataType::Int64),
+            call(AggregateFunction::Sum, DataType::Int64),
+            call(AggregateFunction::Avg, DataType::Float64),
+            call(AggregateFunction::Min, DataType::Int32),
+            call(AggregateFunction::Max, DataType::Int32),
+        ];
+        let mut columns: Vec<Column> = group_by
+            .iter()
+            .map(|g| Column {
+                name: g.to_string().into(),
+                data_type: g.data_type().clone(),
+                nullable: true,
+                default: None,
+            })
+            .collect();
+        for a in aggregates.iter() {
+            columns.push(Column {
+                name: a.to_string().into(),
+                data_type: a.data_type.clone(),
+                nullable: true,
+                default: None,
+            });
+        }
+        HashAggregate::new(
+            Box::new(scan),
+            group_by.into_boxed_slice(),
+            aggregates.into_boxed_slice(),
+            Schema {
+                name: "score".into(),
+                columns: columns.into_boxed_slice(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_group_by() {
+        let source: SimpleMemoryDataSource = source();
+        let mut plan: HashAggregate =
+            aggregate(&source, vec![column("class", 0, DataType::Varchar(8))]);
+        assert_eq!(
+            plan.to_string(),
+            [
+                "HashAggregate: group_by=[score.class] aggregates=[COUNT(*), COUNT(score.point), \
+                 SUM(score.point), AVG(score.point), MIN(score.point), MAX(score.point)]",
+                "  SeqScan: score [class, point]",
+            ]
+            .join("\n")
+        );
+        // NULL 作为一组，按分组第一次出现的顺序输出
+        assert_eq!(
+            collect(&mut plan),
+            vec![
+                "[\"a\", 3, 2, 9, 4.5, 3, 6]",
+                "[NULL, 2, 2, 8, 4, 1, 7]",
+                "[\"b\", 1, 1, 2, 2, 2, 2]",
+            ]
+        );
+        // 可以重复执行，按批输出相同的结果
+        assert_eq!(collect(&mut plan).len(), 3);
+        assert_eq!(collect_batches(&mut plan), collect(&mut plan));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let mut source: SimpleMemoryDataSource =
+            SimpleMemoryDataSource::new(source().schema().clone());
+
+        // 没有 GROUP BY 时空输入输出一行
+        assert_eq!(
+            collect(&mut aggregate(&source, vec![])),
+            vec!["[0, 0, NULL, NULL, NULL, NULL]"]
+        );
+        assert!(collect(&mut aggregate(
+            &source,
+            vec![column("class", 0, DataType::Varchar(8))]
+        ))
+        .is_empty());
+
+        source.push_row(SimpleMemoryRow::new(vec![OwnValue::Null, OwnValue::Null]));
+        assert_eq!(
+            collect(&mut aggregate(&source, vec![])),
+            vec!["[1, 0, NULL, NULL, NULL, NULL]"]
+        );
+    }
+
+    #[test]
+    fn test_results_past_int32() {
+        // SUM(Int32) 按 Int64 累加，超过 Int32 的范围不溢出
+        let sum: AggregateCall = call(AggregateFunction::Sum, DataType::Int64);
+        let mut accumulator: Accumulator = Accumulator::new(AggregateFunction::Sum);
+        for _ in 0..3 {
+            accumulator
+                .accumulate(&sum, OwnValue::Int32(Int32::new(i32::MAX)))
+                .unwrap();
+        }
+        assert_eq!(
+            accumulator.finish().unwrap(),
+            OwnValue::Int64(Int64::new(3 * i32::MAX as i64))
+        );
+
+        // 超过 Int64 的范围时报错
+        let mut accumulator: Accumulator = Accumulator::Sum(OwnValue::Int64(Int64::new(i64::MAX)));
+        let error: ExecuteError = accumulator
+            .accumulate(&sum, OwnValue::Int32(Int32::new(1)))
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::NumericValueOutOfRange);
+        assert_eq!(error.to_string(), "bigint overflow in SUM(score.point)");
+
+        // COUNT 的结果为 Int64
+        let count: i64 = i32::MAX as i64 + 1;
+        assert_eq!(
+            Accumulator::Count(count).finish().unwrap(),
+            OwnValue::Int64(Int64::new(count))
+        );
+    }
+
+    #[test]
+    fn test_float_group_key() {
+        let key = |v: f64| GroupKey::new(vec![OwnValue::Float64(Float64::new(v))]);
+        assert_eq!(key(0.0), key(-0.0));
+        assert_eq!(key(f64::NAN), key(f64::NAN));
+        assert_ne!(key(1.0), key(2.0));
+        assert_ne!(
+            GroupKey::new(vec![OwnValue::Null]),
+            GroupKey::new(vec![OwnValue::Int32(Int32::new(0))])
+        );
+    }
+}
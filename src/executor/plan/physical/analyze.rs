@@ -0,0 +1,151 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::executor::{
+    error::ExecuteError,
+    meta::{batch::Batch, row::Row, schema::Schema},
+};
+
+use super::PhysicalPlan;
+
+/// Metrics 算子的执行统计
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Metrics {
+    pub rows: u64,           // 输出的行数
+    pub next_time: Duration, // 在 next 和 batch 中花费的时间，包含子算子的时间
+    pub opens: u64,          // open 的调用次数
+    pub closes: u64,         // close 的调用次数
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rows={} time={:.3}ms opens={} closes={}",
+            self.rows,
+            self.next_time.as_secs_f64() * 1000.0,
+            self.opens,
+            self.closes
+        )
+    }
+}
+
+/// Analyze 包装一个算子并统计它的执行情况，用于 EXPLAIN ANALYZE
+/// 输出计划树时，在被包装算子的那一行后面追加统计信息
+pub struct Analyze<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub metrics: Metrics,
+}
+
+impl<'a> Analyze<'a> {
+    pub fn new(input: Box<dyn PhysicalPlan<'a> + 'a>) -> Self {
+        Self {
+            input,
+            metrics: Metrics::default(),
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for Analyze<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        self.input.children()
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        let text: String = self.input.to_string();
+        match text.split_once('\n') {
+            Some((head, children)) => format!("{} ({})\n{}", head, self.metrics, children),
+            None => format!("{} ({})", text, self.metrics),
+        }
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.metrics.opens += 1;
+        self.input.open()
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        let start: Instant = Instant::now();
+        let row: Result<Option<Box<dyn Row + 'a>>, ExecuteError> = self.input.next();
+        self.metrics.next_time += start.elapsed();
+        if let Ok(Some(_)) = row {
+            self.metrics.rows += 1;
+        }
+        row
+    }
+
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        let start: Instant = Instant::now();
+        let batch: Result<Option<Batch>, ExecuteError> = self.input.batch();
+        self.metrics.next_time += start.elapsed();
+        if let Ok(Some(batch)) = &batch {
+            self.metrics.rows += batch.len() as u64;
+        }
+        batch
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.metrics.closes += 1;
+        self.input.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::{
+        meta::{
+            column::Column,
+            data_source::{DataSource, SimpleMemoryDataSource},
+            row::SimpleMemoryRow,
+            schema::Schema,
+        },
+        plan::physical::{limit::Limit, scan::SeqScan, PhysicalPlan},
+        types::{int32::Int32, DataType, OwnValue},
+    };
+
+    use super::Analyze;
+
+    #[test]
+    fn test_analyze() {
+        let schema: Schema = Schema {
+            name: "t".into(),
+            columns: vec![Column {
+                name: "a".into(),
+                data_type: DataType::Int32,
+                nullable: false,
+                default: None,
+            }]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        for value in 0..5 {
+            source.push_row(SimpleMemoryRow::new(vec![OwnValue::Int32(Int32::new(
+                value,
+            ))]));
+        }
+        let scan: Analyze = Analyze::new(Box::new(SeqScan::new(
+            &source,
+            vec![0].into_boxed_slice(),
+            source.schema().clone(),
+        )));
+        let mut limit: Analyze = Analyze::new(Box::new(Limit::new(Box::new(scan), Some(2), 1)));
+        limit.open().unwrap();
+        while limit.next().unwrap().is_some() {}
+        limit.close().unwrap();
+
+        assert_eq!(limit.metrics.rows, 2);
+        assert_eq!((limit.metrics.opens, limit.metrics.closes), (1, 1));
+        let text: String = limit.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Limit: limit=2 offset=1 (rows=2 time="));
+        assert!(lines[0].ends_with("ms opens=1 closes=1)"));
+        assert!(lines[1].starts_with("  SeqScan: t [a] (rows=3 time="));
+    }
+}
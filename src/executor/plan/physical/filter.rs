@@ -0,0 +1,203 @@
+use crate::executor::{
+    error::ExecuteError,
+    expression::BoundExpression,
+    meta::{
+        batch::{Batch, Bitmap},
+        row::Row,
+        schema::Schema,
+    },
+    plan::format_tree,
+};
+
+use super::PhysicalPlan;
+
+/// Filter 从子计划中拉取数据，只输出 predicate 为真的行
+pub struct Filter<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub predicate: BoundExpression,
+}
+
+impl<'a> Filter<'a> {
+    pub fn new(input: Box<dyn PhysicalPlan<'a> + 'a>, predicate: BoundExpression) -> Self {
+        Self { input, predicate }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for Filter<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        format_tree(
+            format!("Filter: {}", self.predicate),
+            vec![self.input.to_string()],
+        )
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.input.open()
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        while let Some(row) = self.input.next()? {
+            if self.predicate.evaluate_predicate(row.as_ref())? {
+                return Ok(Some(row));
+            }
+        }
+        Ok(None)
+    }
+
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        while let Some(batch) = self.input.batch()? {
+            let selection: Bitmap = self.predicate.evaluate_predicate_batch(&batch)?;
+            match selection.count_ones() {
+                0 => continue,
+                n if n == batch.len() => return Ok(Some(batch)),
+                _ => return Ok(Some(batch.take(&selection.ones()))),
+            }
+        }
+        Ok(None)
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.input.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::{
+            expression::{BoundBinary, BoundExpression, BoundLiteral, ColumnRef},
+            meta::{
+                batch::Batch,
+                column::Column,
+                data_source::{DataSource, SimpleMemoryDataSource},
+                row::{Row, SimpleMemoryRow},
+                schema::Schema,
+            },
+            plan::physical::{filter::Filter, projection::Projection, scan::SeqScan, PhysicalPlan},
+            types::{int32::Int32, DataType, OwnValue},
+        },
+        sql::parser::ast::{expression::BinaryOperator, leaf::Location},
+    };
+
+    fn location() -> Location {
+        Location {
+            line_number: 1,
+            column_number: 1,
+            offset: 0,
+        }
+    }
+
+    fn age() -> BoundExpression {
+        BoundExpression::Column(ColumnRef {
+            table: "stu".into(),
+            name: "age".into(),
+            index: 1,
+            data_type: DataType::Int32,
+            nullable: true,
+        })
+    }
+
+    fn int(value: i32) -> BoundExpression {
+        BoundExpression::Literal(BoundLiteral {
+            value: OwnValue::Int32(Int32::new(value)),
+            data_type: DataType::Int32,
+        })
+    }
+
+    #[test]
+    fn test_filter_projection() {
+        let schema: Schema = Schema {
+            name: "stu".into(),
+            columns: vec![
+                Column {
+                    name: "name".into(),
+                    data_type: DataType::Varchar(32),
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "age".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        for (name, age) in [
+            ("张三", OwnValue::Int32(Int32::new(18))),
+            ("李四", OwnValue::Null),
+            ("王五", OwnValue::Int32(Int32::new(20))),
+        ] {
+            source.push_row(SimpleMemoryRow::new(vec![
+                OwnValue::String(name.into()),
+                age,
+            ]));
+        }
+
+        let scan: SeqScan = SeqScan::new(
+            &source,
+            vec![0, 1].into_boxed_slice(),
+            source.schema().clone(),
+        );
+        // WHERE age > 18，NULL 不满足条件
+        let filter: Filter = Filter::new(
+            Box::new(scan),
+            BoundExpression::Binary(BoundBinary {
+                left: Box::new(age()),
+                operator: BinaryOperator::GreaterThan(location()),
+                right: Box::new(int(18)),
+                data_type: DataType::Int32,
+            }),
+        );
+        // SELECT age + 1
+        let mut projection: Projection = Projection::new(
+            Box::new(filter),
+            vec![BoundExpression::Binary(BoundBinary {
+                left: Box::new(age()),
+                operator: BinaryOperator::Plus(location()),
+                right: Box::new(int(1)),
+                data_type: DataType::Int32,
+            })]
+            .into_boxed_slice(),
+            Schema {
+                name: "stu".into(),
+                columns: vec![Column {
+                    name: "age + 1".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                }]
+                .into_boxed_slice(),
+            },
+        );
+
+        assert_eq!(
+            projection.to_string(),
+            "Projection: (stu.age + 1)\n  Filter: (stu.age > 18)\n    SeqScan: stu [name, age]"
+        );
+
+        projection.open().unwrap();
+        let row = projection.next().unwrap().unwrap();
+        assert_eq!(row.to_string(projection.schema()).unwrap(), "[21]");
+        assert!(projection.next().unwrap().is_none());
+        projection.close().unwrap();
+
+        // 按批执行得到相同的结果
+        projection.open().unwrap();
+        let batch: Batch = projection.batch().unwrap().unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.row(0).to_string(projection.schema()).unwrap(), "[21]");
+        assert!(projection.batch().unwrap().is_none());
+        projection.close().unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::{
+    executor::{
+        error::ExecuteError,
+        expression::BoundExpression,
+        meta::{row::Row, schema::Schema},
+        plan::format_tree,
+        types::OwnValue,
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+use super::{
+    aggregate::GroupKey,
+    join::{evaluate_keys, format_keys, values, Probe},
+    PhysicalPlan,
+};
+
+/// HashJoin 哈希连接
+/// open 时物化右表，按 right_keys 的值建立哈希表；左表的每一行按 left_keys 的值查找
+/// 可能匹配的右表行，再检查 residual。键中有 NULL 的行不会匹配。
+/// left_keys 在左表的行上计算，right_keys 在右表的行上计算，
+/// residual 在拼接后的行上计算
+pub struct HashJoin<'a> {
+    pub left: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub right: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub join_type: JoinType,
+    pub left_keys: Box<[BoundExpression]>,
+    pub right_keys: Box<[BoundExpression]>,
+    pub residual: Option<BoundExpression>,
+    pub schema: Schema,
+
+    table: HashMap<GroupKey, Vec<usize>>,
+    probe: Probe,
+}
+
+impl<'a> HashJoin<'a> {
+    pub fn new(
+        left: Box<dyn PhysicalPlan<'a> + 'a>,
+        right: Box<dyn PhysicalPlan<'a> + 'a>,
+        join_type: JoinType,
+        left_keys: Box<[BoundExpression]>,
+        right_keys: Box<[BoundExpression]>,
+        residual: Option<BoundExpression>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(left_keys.len(), right_keys.len());
+        Self {
+            left,
+            right,
+            join_type,
+            left_keys,
+            right_keys,
+            residual,
+            schema,
+            table: HashMap::new(),
+            probe: Probe::default(),
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for HashJoin<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let mut head: String = format!(
+            "HashJoin: {} keys={}",
+            self.join_type,
+            format_keys(&self.left_keys, &self.right_keys)
+        );
+        if let Some(residual) = &self.residual {
+            head.push_str(&format!(" filter={}", residual));
+        }
+        format_tree(head, vec![self.left.to_string(), self.right.to_string()])
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.left.open()?;
+        self.right.open()?;
+        let width: usize = self.right.schema().columns.len();
+        let mut rows: Vec<Vec<OwnValue>> = vec![];
+        self.table.clear();
+        while let Some(row) = self.right.next()? {
+            if let Some(key) = evaluate_keys(&self.right_keys, row.as_ref())? {
+                self.table
+                    .entry(GroupKey::new(key))
+                    .or_default()
+                    .push(rows.len());
+            }
+            rows.push(values(row.as_ref(), width)?);
+        }
+        self.probe
+            .build(self.left.schema().columns.len(), width, rows);
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        let width: usize = self.left.schema().columns.len();
+        let left: &mut Box<dyn PhysicalPlan<'a> + 'a> = &mut self.left;
+        let left_keys: &[BoundExpression] = &self.left_keys;
+        let table: &HashMap<GroupKey, Vec<usize>> = &self.table;
+        let row = self
+            .probe
+            .next(self.join_type, self.residual.as_ref(), || {
+                let Some(row) = left.next()? else {
+                    return Ok(None);
+                };
+                let candidates: Vec<usize> = match evaluate_keys(left_keys, row.as_ref())? {
+                    Some(key) => table.get(&GroupKey::new(key)).cloned().unwrap_or_default(),
+                    None => vec![],
+                };
+                Ok(Some((values(row.as_ref(), width)?, candidates)))
+            })?;
+        Ok(row.map(|row| Box::new(row) as Box<dyn Row + 'a>))
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.table.clear();
+        self.probe.clear();
+        self.left.close()?;
+        self.right.close()
+    }
+}
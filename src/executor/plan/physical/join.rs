@@ -0,0 +1,183 @@
+use crate::{
+    executor::{
+        error::ExecuteError,
+        expression::BoundExpression,
+        meta::row::{Row, SimpleMemoryRow},
+        types::OwnValue,
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+/// 读出一行的前 width 个值
+pub fn values(row: &dyn Row, width: usize) -> Result<Vec<OwnValue>, ExecuteError> {
+    (0..width).map(|index| row.get(index).cloned()).collect()
+}
+
+/// 拼接左右两行，为 None 的一侧补 NULL
+pub fn concat(
+    left: Option<&[OwnValue]>,
+    left_width: usize,
+    right: Option<&[OwnValue]>,
+    right_width: usize,
+) -> SimpleMemoryRow {
+    let mut values: Vec<OwnValue> = Vec::with_capacity(left_width + right_width);
+    match left {
+        Some(left) => values.extend_from_slice(left),
+        None => values.resize(left_width, OwnValue::Null),
+    }
+    match right {
+        Some(right) => values.extend_from_slice(right),
+        None => values.resize(left_width + right_width, OwnValue::Null),
+    }
+    SimpleMemoryRow::new(values)
+}
+
+/// 计算一行的连接键，任一键为 NULL 时返回 None，NULL 与任何值都不相等
+pub fn evaluate_keys(
+    keys: &[BoundExpression],
+    row: &dyn Row,
+) -> Result<Option<Vec<OwnValue>>, ExecuteError> {
+    let mut values: Vec<OwnValue> = Vec::with_capacity(keys.len());
+    for key in keys {
+        match key.evaluate(row)? {
+            OwnValue::Null => return Ok(None),
+            value => values.push(value),
+        }
+    }
+    Ok(Some(values))
+}
+
+/// 等值连接键的描述，如 [a.id = b.id, a.x = b.y]
+pub fn format_keys(left_keys: &[BoundExpression], right_keys: &[BoundExpression]) -> String {
+    let keys: Vec<String> = left_keys
+        .iter()
+        .zip(right_keys.iter())
+        .map(|(left, right)| format!("{} = {}", left, right))
+        .collect();
+    format!("[{}]", keys.join(", "))
+}
+
+/// 连接条件为空或者在拼接后的行上为真
+pub fn satisfied(
+    condition: Option<&BoundExpression>,
+    row: &SimpleMemoryRow,
+) -> Result<bool, ExecuteError> {
+    match condition {
+        Some(condition) => condition.evaluate_predicate(row),
+        None => Ok(true),
+    }
+}
+
+/// Probe 用左表的行逐个探测已经物化的右表
+/// 对左表的每一行，只检查 candidates 中的右表行，满足连接条件的组合依次输出；
+/// 左表的行没有匹配时按连接类型补 NULL 输出，左表读完后再输出右表中没有匹配过的行
+#[derive(Debug, Default)]
+pub struct Probe {
+    left_width: usize,
+    right_width: usize,
+    rows: Vec<Vec<OwnValue>>,
+    matched: Vec<bool>,
+    current: Option<ProbeRow>,
+    left_exhausted: bool,
+    unmatched: usize, // 下一个检查是否匹配过的右表行
+}
+
+#[derive(Debug)]
+struct ProbeRow {
+    values: Vec<OwnValue>,
+    candidates: Vec<usize>,
+    position: usize,
+    matched: bool,
+}
+
+impl Probe {
+    /// 物化右表，重置探测状态
+    pub fn build(&mut self, left_width: usize, right_width: usize, rows: Vec<Vec<OwnValue>>) {
+        *self = Self {
+            left_width,
+            right_width,
+            matched: vec![false; rows.len()],
+            rows,
+            ..Self::default()
+        };
+    }
+
+    /// 物化的右表行数
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 输出下一行，next_left 返回左表的下一行以及可能与之匹配的右表行下标
+    pub fn next(
+        &mut self,
+        join_type: JoinType,
+        condition: Option<&BoundExpression>,
+        mut next_left: impl FnMut() -> Result<Option<(Vec<OwnValue>, Vec<usize>)>, ExecuteError>,
+    ) -> Result<Option<SimpleMemoryRow>, ExecuteError> {
+        while !self.left_exhausted {
+            let current: &mut ProbeRow = match &mut self.current {
+                Some(current) => current,
+                None => match next_left()? {
+                    Some((values, candidates)) => self.current.insert(ProbeRow {
+                        values,
+                        candidates,
+                        position: 0,
+                        matched: false,
+                    }),
+                    None => {
+                        self.left_exhausted = true;
+                        break;
+                    }
+                },
+            };
+            while current.position < current.candidates.len() {
+                let index: usize = current.candidates[current.position];
+                current.position += 1;
+                let row: SimpleMemoryRow = concat(
+                    Some(&current.values),
+                    self.left_width,
+                    Some(&self.rows[index]),
+                    self.right_width,
+                );
+                if satisfied(condition, &row)? {
+                    current.matched = true;
+                    self.matched[index] = true;
+                    return Ok(Some(row));
+                }
+            }
+            let current: ProbeRow = self.current.take().unwrap();
+            if !current.matched && join_type.keeps_left() {
+                return Ok(Some(concat(
+                    Some(&current.values),
+                    self.left_width,
+                    None,
+                    self.right_width,
+                )));
+            }
+        }
+
+        if join_type.keeps_right() {
+            while self.unmatched < self.rows.len() {
+                let index: usize = self.unmatched;
+                self.unmatched += 1;
+                if !self.matched[index] {
+                    return Ok(Some(concat(
+                        None,
+                        self.left_width,
+                        Some(&self.rows[index]),
+                        self.right_width,
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,191 @@
+use crate::executor::{
+    error::ExecuteError,
+    meta::{row::Row, schema::Schema},
+    plan::format_tree,
+};
+
+use super::PhysicalPlan;
+
+/// Limit 跳过前 offset 行，最多输出 limit 行
+/// 输出满 limit 行后不再从子计划拉取数据
+pub struct Limit<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub limit: Option<u64>,
+    pub offset: u64,
+
+    skipped: u64,
+    produced: u64,
+}
+
+impl<'a> Limit<'a> {
+    pub fn new(input: Box<dyn PhysicalPlan<'a> + 'a>, limit: Option<u64>, offset: u64) -> Self {
+        Self {
+            input,
+            limit,
+            offset,
+            skipped: 0,
+            produced: 0,
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for Limit<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        let limit: String = match self.limit {
+            Some(limit) => limit.to_string(),
+            None => "ALL".to_string(),
+        };
+        format_tree(
+            format!("Limit: limit={} offset={}", limit, self.offset),
+            vec![self.input.to_string()],
+        )
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.skipped = 0;
+        self.produced = 0;
+        self.input.open()
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        if self.limit.is_some_and(|limit| self.produced >= limit) {
+            return Ok(None);
+        }
+        while self.skipped < self.offset {
+            if self.input.next()?.is_none() {
+                return Ok(None);
+            }
+            self.skipped += 1;
+        }
+        let row: Option<Box<dyn Row + 'a>> = self.input.next()?;
+        if row.is_some() {
+            self.produced += 1;
+        }
+        Ok(row)
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.input.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::{
+            expression::{BoundBinary, BoundExpression, BoundLiteral, ColumnRef},
+            meta::{
+                column::Column,
+                data_source::{DataSource, SimpleMemoryDataSource},
+                row::SimpleMemoryRow,
+                schema::Schema,
+            },
+            plan::physical::{limit::Limit, projection::Projection, scan::SeqScan, PhysicalPlan},
+            test_util::collect,
+            types::{int32::Int32, DataType, OwnValue},
+        },
+        sql::parser::ast::{expression::BinaryOperator, leaf::Location},
+    };
+
+    fn source() -> SimpleMemoryDataSource {
+        let schema: Schema = Schema {
+            name: "num".into(),
+            columns: vec![Column {
+                name: "n".into(),
+                data_type: DataType::Int32,
+                nullable: false,
+                default: None,
+            }]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        for n in 0..10 {
+            source.push_row(SimpleMemoryRow::new(vec![OwnValue::Int32(Int32::new(n))]));
+        }
+        source
+    }
+
+    #[test]
+    fn test_limit_offset() {
+        let source: SimpleMemoryDataSource = source();
+        let scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone());
+        let mut limit: Limit = Limit::new(Box::new(scan), Some(3), 4);
+        assert_eq!(collect(&mut limit), vec!["[4]", "[5]", "[6]"]);
+        assert_eq!(
+            limit.to_string(),
+            "Limit: limit=3 offset=4\n  SeqScan: num [n]"
+        );
+    }
+
+    #[test]
+    fn test_offset_only() {
+        let source: SimpleMemoryDataSource = source();
+        let scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone());
+        let mut limit: Limit = Limit::new(Box::new(scan), None, 8);
+        assert_eq!(collect(&mut limit), vec!["[8]", "[9]"]);
+    }
+
+    #[test]
+    fn test_offset_past_end() {
+        let source: SimpleMemoryDataSource = source();
+        let scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone());
+        let mut limit: Limit = Limit::new(Box::new(scan), Some(0), 20);
+        assert!(collect(&mut limit).is_empty());
+    }
+
+    #[test]
+    fn test_stop_pulling() {
+        let source: SimpleMemoryDataSource = source();
+        let scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone());
+        // 10 / (n - 5)，第 6 行除零报错
+        let n: BoundExpression = BoundExpression::Column(ColumnRef {
+            table: "num".into(),
+            name: "n".into(),
+            index: 0,
+            data_type: DataType::Int32,
+            nullable: false,
+        });
+        let int = |v: i32| {
+            Box::new(BoundExpression::Literal(BoundLiteral {
+                value: OwnValue::Int32(Int32::new(v)),
+                data_type: DataType::Int32,
+            }))
+        };
+        let location: Location = Location {
+            line_number: 1,
+            column_number: 1,
+            offset: 0,
+        };
+        let division: BoundExpression = BoundExpression::Binary(BoundBinary {
+            left: int(10),
+            operator: BinaryOperator::Divide(location.clone()),
+            right: Box::new(BoundExpression::Binary(BoundBinary {
+                left: Box::new(n),
+                operator: BinaryOperator::Minus(location),
+                right: int(5),
+                data_type: DataType::Int32,
+            })),
+            data_type: DataType::Int32,
+        });
+        let projection: Projection = Projection::new(
+            Box::new(scan),
+            vec![division].into_boxed_slice(),
+            source.schema().clone(),
+        );
+
+        let mut limit: Limit = Limit::new(Box::new(projection), Some(3), 2);
+        assert_eq!(collect(&mut limit), vec!["[-3]", "[-5]", "[-10]"]);
+    }
+}
@@ -1,18 +1,74 @@
 use crate::executor::{
     error::ExecuteError,
-    meta::{row::Row, rows::Rows, schema::Schema},
+    meta::{
+        batch::{Batch, BATCH_SIZE},
+        row::Row,
+        schema::Schema,
+    },
 };
 
 pub mod scan;
 
+/// 输出一行空行，用于没有 FROM 子句的 SELECT
+pub mod one_row;
+
+/// 过滤
+pub mod filter;
+
+/// 投影，计算输出列
+pub mod projection;
+
+/// 分页
+pub mod limit;
+
+/// 哈希聚合
+pub mod aggregate;
+
+/// 外部归并排序
+pub mod sort;
+
+/// 溢写临时文件
+pub mod spill;
+
+/// 连接算子共用的工具
+pub mod join;
+
+/// 嵌套循环连接
+pub mod nested_loop_join;
+
+/// 哈希连接
+pub mod hash_join;
+
+/// 排序归并连接
+pub mod sort_merge_join;
+
+/// 统计算子的执行情况，用于 EXPLAIN ANALYZE
+pub mod analyze;
+
 /// 物理计划
-pub trait PhysicalPlan {
-    fn children(&self) -> &[&dyn PhysicalPlan];
+/// 可以用 next 逐行拉取，也可以用 batch 按列一批批拉取，同一次执行中只能使用其中一种
+pub trait PhysicalPlan<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>>;
     fn schema(&self) -> &Schema;
     fn to_string(&self) -> String;
 
     fn open(&mut self) -> Result<(), ExecuteError>;
-    fn next(&mut self) -> Result<Option<Box<dyn Row>>, ExecuteError>;
-    fn batch(&mut self) -> Result<Option<Box<dyn Rows>>, ExecuteError>;
+
+    /// 输出下一行，行可以借用生命周期为 'a 的数据，例如扫描直接借用表中的行
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError>;
+
+    /// 输出下一批行，没有更多行时返回 None，返回的批不为空
+    /// 默认逐行调用 next 凑成一批，扫描、过滤、投影和聚合按列计算
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        let mut batch: Batch = Batch::new(self.schema());
+        while batch.len() < BATCH_SIZE {
+            match self.next()? {
+                Some(row) => batch.push_row(row.as_ref())?,
+                None => break,
+            }
+        }
+        Ok(if batch.is_empty() { None } else { Some(batch) })
+    }
+
     fn close(&mut self) -> Result<(), ExecuteError>;
 }
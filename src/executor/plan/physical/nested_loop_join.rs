@@ -0,0 +1,98 @@
+use crate::{
+    executor::{
+        error::ExecuteError,
+        expression::BoundExpression,
+        meta::{row::Row, schema::Schema},
+        plan::format_tree,
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+use super::{
+    join::{values, Probe},
+    PhysicalPlan,
+};
+
+/// NestedLoopJoin 嵌套循环连接
+/// open 时物化右表，左表的每一行与右表的所有行拼接后检查 condition，
+/// 支持任意连接条件，condition 为 None 时输出笛卡尔积
+pub struct NestedLoopJoin<'a> {
+    pub left: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub right: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub join_type: JoinType,
+    pub condition: Option<BoundExpression>,
+    pub schema: Schema,
+
+    probe: Probe,
+}
+
+impl<'a> NestedLoopJoin<'a> {
+    pub fn new(
+        left: Box<dyn PhysicalPlan<'a> + 'a>,
+        right: Box<dyn PhysicalPlan<'a> + 'a>,
+        join_type: JoinType,
+        condition: Option<BoundExpression>,
+        schema: Schema,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            join_type,
+            condition,
+            schema,
+            probe: Probe::default(),
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for NestedLoopJoin<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let head: String = match &self.condition {
+            Some(condition) => format!("NestedLoopJoin: {} ON {}", self.join_type, condition),
+            None => format!("NestedLoopJoin: {}", self.join_type),
+        };
+        format_tree(head, vec![self.left.to_string(), self.right.to_string()])
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.left.open()?;
+        self.right.open()?;
+        let width: usize = self.right.schema().columns.len();
+        let mut rows: Vec<Vec<_>> = vec![];
+        while let Some(row) = self.right.next()? {
+            rows.push(values(row.as_ref(), width)?);
+        }
+        self.probe
+            .build(self.left.schema().columns.len(), width, rows);
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        let width: usize = self.left.schema().columns.len();
+        let count: usize = self.probe.len();
+        let left: &mut Box<dyn PhysicalPlan<'a> + 'a> = &mut self.left;
+        let row = self
+            .probe
+            .next(self.join_type, self.condition.as_ref(), || {
+                match left.next()? {
+                    Some(row) => Ok(Some((values(row.as_ref(), width)?, (0..count).collect()))),
+                    None => Ok(None),
+                }
+            })?;
+        Ok(row.map(|row| Box::new(row) as Box<dyn Row + 'a>))
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.probe.clear();
+        self.left.close()?;
+        self.right.close()
+    }
+}
@@ -0,0 +1,56 @@
+use crate::executor::{
+    error::ExecuteError,
+    meta::{
+        row::{Row, SimpleMemoryRow},
+        schema::Schema,
+    },
+};
+
+use super::PhysicalPlan;
+
+/// OneRow 只输出一行没有列的空行
+pub struct OneRow {
+    pub schema: Schema,
+
+    done: bool,
+}
+
+impl OneRow {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema,
+            done: false,
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for OneRow {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        "OneRow".to_string()
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.done = false;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+        Ok(Some(Box::new(SimpleMemoryRow::new(vec![]))))
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        Ok(())
+    }
+}
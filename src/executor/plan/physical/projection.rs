@@ -0,0 +1,87 @@
+use crate::executor::{
+    error::ExecuteError,
+    expression::BoundExpression,
+    meta::{
+        batch::{Batch, ColumnVector},
+        row::{Row, SimpleMemoryRow},
+        schema::Schema,
+    },
+    plan::format_tree,
+    types::OwnValue,
+};
+
+use super::PhysicalPlan;
+
+/// Projection 对子计划的每一行计算 expressions，输出新的一行
+pub struct Projection<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub expressions: Box<[BoundExpression]>,
+    pub schema: Schema,
+}
+
+impl<'a> Projection<'a> {
+    pub fn new(
+        input: Box<dyn PhysicalPlan<'a> + 'a>,
+        expressions: Box<[BoundExpression]>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(expressions.len(), schema.columns.len());
+        Self {
+            input,
+            expressions,
+            schema,
+        }
+    }
+}
+
+impl<'a> PhysicalPlan<'a> for Projection<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let expressions: Vec<String> = self.expressions.iter().map(|e| e.to_string()).collect();
+        format_tree(
+            format!("Projection: {}", expressions.join(", ")),
+            vec![self.input.to_string()],
+        )
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.input.open()
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        match self.input.next()? {
+            Some(row) => {
+                let mut values: Vec<OwnValue> = Vec::with_capacity(self.expressions.len());
+                for expression in self.expressions.iter() {
+                    values.push(expression.evaluate(row.as_ref())?);
+                }
+                Ok(Some(Box::new(SimpleMemoryRow::new(values))))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        match self.input.batch()? {
+            Some(batch) => {
+                let mut columns: Vec<ColumnVector> = Vec::with_capacity(self.expressions.len());
+                for expression in self.expressions.iter() {
+                    columns.push(expression.evaluate_batch(&batch)?.into_owned());
+                }
+                Ok(Some(Batch::from_columns(columns, batch.len())))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.input.close()
+    }
+}
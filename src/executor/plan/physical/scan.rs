@@ -1,34 +1,79 @@
-use crate::executor::{
-    error::ExecuteError,
-    meta::{
-        data_source::DataSource,
-        row::{Row, SimpleMemoryRow},
-        rows::Rows,
-        schema::Schema,
+use std::rc::Rc;
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        expression::{BoundExpression, ColumnRef},
+        meta::{
+            batch::{Batch, ColumnVector},
+            data_source::DataSource,
+            row::{ProjectedRow, Row},
+            schema::Schema,
+        },
     },
 };
 
 use super::PhysicalPlan;
 
+/// SeqScan 顺序读取表中的行，只输出 filter 为真的行中 projection 指定的列
+/// filter 中的列引用指向表中的列，在投影之前求值
+/// 逐行输出时直接借用表中的行，只重新映射列下标，不复制值；按列输出时复制读出的列
 pub struct SeqScan<'a> {
     pub data_source: &'a dyn DataSource,
-    pub projection: Box<[u16]>,
+    pub projection: Rc<[u16]>, // 与输出的行共享
     pub schema: Schema,
+    pub filter: Option<BoundExpression>,
 
     iter: Option<Box<dyn Iterator<Item = &'a dyn Row> + 'a>>,
+    batches: Option<Box<dyn Iterator<Item = Result<Batch, ExecuteError>> + 'a>>,
+    batch_filter: Option<BoundExpression>, // 列下标指向按列读出的 Batch 的 filter
+}
+
+impl<'a> SeqScan<'a> {
+    pub fn new(data_source: &'a dyn DataSource, projection: Box<[u16]>, schema: Schema) -> Self {
+        Self {
+            data_source,
+            projection: projection.into(),
+            schema,
+            filter: None,
+            iter: None,
+            batches: None,
+            batch_filter: None,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: Option<BoundExpression>) -> Self {
+        self.filter = filter;
+        self
+    }
 }
 
-impl<'a> PhysicalPlan for SeqScan<'a> {
+impl<'a> PhysicalPlan<'a> for SeqScan<'a> {
     fn schema(&self) -> &Schema {
         &self.schema
     }
 
-    fn children(&self) -> &[&dyn PhysicalPlan] {
-        &[]
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![]
     }
 
     fn to_string(&self) -> String {
-        format!("{}", self.schema)
+        let table: &Schema = self.data_source.schema();
+        let columns: Vec<&str> = self
+            .projection
+            .iter()
+            .map(|index| table.columns[*index as usize].name.as_ref())
+            .collect();
+        let head: String = format!(
+            "SeqScan: {} [{}]",
+            self.data_source.name(),
+            columns.join(", ")
+        );
+        match &self.filter {
+            Some(filter) => format!("{} filter={}", head, filter),
+            None => head,
+        }
     }
 
     fn open(&mut self) -> Result<(), ExecuteError> {
@@ -37,43 +82,93 @@ impl<'a> PhysicalPlan for SeqScan<'a> {
         Ok(())
     }
 
-    fn next(&mut self) -> Result<Option<Box<dyn Row>>, ExecuteError> {
-        let iter: &mut Box<dyn Iterator<Item = &dyn Row>> = self.iter.as_mut().unwrap();
-        let row: Option<&dyn Row> = iter.next();
-        match row {
-            Some(row) => {
-                let mut values = Vec::with_capacity(self.projection.len());
-                for index in self.projection.iter() {
-                    values.push(row.get(*index as usize).clone());
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        let Some(iter) = self.iter.as_mut() else {
+            return Err(ExecuteError::new(
+                ErrorCode::InternalError,
+                "SeqScan is not opened",
+            ));
+        };
+        for row in iter.by_ref() {
+            if let Some(filter) = &self.filter {
+                if !filter.evaluate_predicate(row)? {
+                    continue;
                 }
-                let row = SimpleMemoryRow::new(values);
-                Ok(Some(Box::new(row)))
             }
-            None => Ok(None),
+            return Ok(Some(Box::new(ProjectedRow::new(
+                row,
+                self.projection.clone(),
+            ))));
         }
+        Ok(None)
     }
 
-    fn batch(&mut self) -> Result<Option<Box<dyn Rows>>, ExecuteError> {
-        panic!("not implemented")
+    /// 按列读取 projection 中的列和只在 filter 中出现的列，过滤后只输出 projection 中的列
+    fn batch(&mut self) -> Result<Option<Batch>, ExecuteError> {
+        if self.batches.is_none() {
+            let mut columns: Vec<u16> = self.projection.to_vec();
+            if let Some(filter) = &self.filter {
+                let mut used: Vec<usize> = vec![];
+                filter.collect_columns(&mut used);
+                for index in used {
+                    if !columns.contains(&(index as u16)) {
+                        columns.push(index as u16);
+                    }
+                }
+                let position = |index: usize| columns.iter().position(|c| *c as usize == index);
+                self.batch_filter = Some(filter.map_columns(&|column| ColumnRef {
+                    index: position(column.index).unwrap(),
+                    ..column.clone()
+                }));
+            }
+            self.batches = Some(self.data_source.batch_read(&columns));
+        }
+        let batches = self.batches.as_mut().unwrap();
+        for batch in batches.by_ref() {
+            let batch: Batch = batch?;
+            let Some(filter) = &self.batch_filter else {
+                return Ok(Some(batch));
+            };
+            let rows: Vec<usize> = filter.evaluate_predicate_batch(&batch)?.ones();
+            if rows.is_empty() {
+                continue;
+            }
+            let columns: Vec<ColumnVector> = batch.columns[..self.projection.len()]
+                .iter()
+                .map(|column| column.take(&rows))
+                .collect();
+            return Ok(Some(Batch::from_columns(columns, rows.len())));
+        }
+        Ok(None)
     }
 
     fn close(&mut self) -> Result<(), ExecuteError> {
-        // self.iter = None;
+        self.iter = None;
+        self.batches = None;
+        self.batch_filter = None;
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::executor::{
-        meta::{
-            column::Column,
-            data_source::{DataSource, SimpleMemoryDataSource},
-            row::SimpleMemoryRow,
-            schema::Schema,
+    use std::cell::RefCell;
+
+    use crate::{
+        executor::{
+            error::ExecuteError,
+            expression::{BoundBinary, BoundExpression, BoundLiteral, ColumnRef},
+            meta::{
+                batch::{Batch, BATCH_SIZE},
+                column::Column,
+                data_source::{DataSource, SimpleMemoryDataSource},
+                row::{Row, SimpleMemoryRow},
+                schema::Schema,
+            },
+            plan::physical::{scan::SeqScan, PhysicalPlan},
+            types::{int32::Int32, DataType, OwnValue},
         },
-        plan::physical::{scan::SeqScan, PhysicalPlan},
-        types::{int32::Int32, DataType, OwnValue},
+        sql::parser::ast::{expression::BinaryOperator, leaf::Location},
     };
 
     #[test]
@@ -82,6 +177,7 @@ mod tests {
             name: "name".into(),
             data_type: DataType::Varchar(32),
             nullable: true,
+            default: None,
         };
         println!("column_name = {:?}", column_name);
 
@@ -89,6 +185,7 @@ mod tests {
             name: "age".into(),
             data_type: DataType::Int32,
             nullable: true,
+            default: None,
         };
         println!("column_age = {:?}", column_age);
 
@@ -115,9 +212,12 @@ mod tests {
 
         let mut scan: Box<SeqScan<'_>> = Box::new(SeqScan {
             data_source: &source,
-            projection: vec![1].into_boxed_slice(),
+            projection: vec![1].into(),
             schema: source.schema().clone(),
+            filter: None,
             iter: None,
+            batches: None,
+            batch_filter: None,
         });
 
         scan.open().unwrap();
@@ -128,4 +228,174 @@ mod tests {
 
         scan.close().unwrap();
     }
+
+    /// stu(name, age)，第 i 行为 (i, i)
+    fn source(count: i32) -> SimpleMemoryDataSource {
+        let schema: Schema = Schema {
+            name: "stu".into(),
+            columns: vec![
+                Column {
+                    name: "name".into(),
+                    data_type: DataType::String,
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "age".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(schema);
+        for age in 0..count {
+            source.push_row(SimpleMemoryRow::new(vec![
+                OwnValue::String(age.to_string()),
+                OwnValue::Int32(Int32::new(age)),
+            ]));
+        }
+        source
+    }
+
+    #[test]
+    fn test_scan_borrow() {
+        let source: SimpleMemoryDataSource = source(2);
+        let mut scan: SeqScan = SeqScan::new(
+            &source,
+            vec![1, 0].into_boxed_slice(),
+            source.schema().clone(),
+        );
+        assert_eq!(
+            scan.next().unwrap_err().to_string(),
+            "SeqScan is not opened"
+        );
+        scan.open().unwrap();
+        let first: Box<dyn Row> = scan.next().unwrap().unwrap();
+        let second: Box<dyn Row> = scan.next().unwrap().unwrap();
+        assert!(scan.next().unwrap().is_none());
+        scan.close().unwrap();
+
+        assert_eq!(first.get_int32(0).unwrap(), Int32::new(0));
+        assert_eq!(second.get(1).unwrap(), &OwnValue::String("1".into()));
+        // 输出的行直接借用表中的字符串
+        let table: &dyn Row = source.read().nth(1).unwrap();
+        assert!(std::ptr::eq(
+            second.get_string(1).unwrap(),
+            table.get_string(0).unwrap()
+        ));
+        // 投影之外的下标返回错误而不是 panic
+        assert_eq!(
+            second.get(2).unwrap_err().to_string(),
+            "column #2 out of range, rows have 2 columns"
+        );
+    }
+
+    #[test]
+    fn test_scan_batch() {
+        let source: SimpleMemoryDataSource = source(1500);
+        let batches = |scan: &mut SeqScan| -> Vec<Batch> {
+            scan.open().unwrap();
+            let mut batches: Vec<Batch> = vec![];
+            while let Some(batch) = scan.batch().unwrap() {
+                batches.push(batch);
+            }
+            scan.close().unwrap();
+            batches
+        };
+
+        let mut scan: SeqScan =
+            SeqScan::new(&source, vec![1].into_boxed_slice(), source.schema().clone());
+        let all: Vec<Batch> = batches(&mut scan);
+        assert_eq!(
+            all.iter().map(|b| b.len()).collect::<Vec<usize>>(),
+            vec![BATCH_SIZE, 1500 - BATCH_SIZE]
+        );
+        assert_eq!(all[1].columns.len(), 1);
+
+        // WHERE age >= 1000，只输出 name 列
+        let location: Location = Location {
+            line_number: 1,
+            column_number: 1,
+            offset: 0,
+        };
+        let mut scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone()).with_filter(
+                Some(BoundExpression::Binary(BoundBinary {
+                    left: Box::new(BoundExpression::Column(ColumnRef {
+                        table: "stu".into(),
+                        name: "age".into(),
+                        index: 1,
+                        data_type: DataType::Int32,
+                        nullable: true,
+                    })),
+                    operator: BinaryOperator::GreaterThanOrEqual(location),
+                    right: Box::new(BoundExpression::Literal(BoundLiteral {
+                        value: OwnValue::Int32(Int32::new(1000)),
+                        data_type: DataType::Int32,
+                    })),
+                    data_type: DataType::Int32,
+                })),
+            );
+        let filtered: Vec<Batch> = batches(&mut scan);
+        assert_eq!(
+            filtered.iter().map(|b| b.len()).collect::<Vec<usize>>(),
+            vec![BATCH_SIZE - 1000, 1500 - BATCH_SIZE]
+        );
+        assert_eq!(
+            filtered[0].columns[0].get(0),
+            OwnValue::String("1000".into())
+        );
+
+        // 只读取 projection 和 filter 用到的列
+        let recording: Recording = Recording {
+            source: source.clone(),
+            projections: RefCell::new(vec![]),
+        };
+        let mut scan: SeqScan = SeqScan::new(
+            &recording,
+            vec![1].into_boxed_slice(),
+            recording.schema().clone(),
+        )
+        .with_filter(scan.filter.clone());
+        assert_eq!(batches(&mut scan).len(), 2);
+        let mut scan: SeqScan = SeqScan::new(
+            &recording,
+            vec![0].into_boxed_slice(),
+            recording.schema().clone(),
+        )
+        .with_filter(scan.filter.clone());
+        assert_eq!(batches(&mut scan)[1].columns.len(), 1);
+        assert_eq!(*recording.projections.borrow(), vec![vec![1], vec![0, 1]]);
+    }
+
+    /// 记录每次按列读取的列
+    #[derive(Debug)]
+    struct Recording {
+        source: SimpleMemoryDataSource,
+        projections: RefCell<Vec<Vec<u16>>>,
+    }
+
+    impl DataSource for Recording {
+        fn name(&self) -> String {
+            self.source.name()
+        }
+
+        fn schema(&self) -> &Schema {
+            self.source.schema()
+        }
+
+        fn read<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn Row> + 'a> {
+            self.source.read()
+        }
+
+        fn batch_read<'a>(
+            &'a self,
+            projection: &[u16],
+        ) -> Box<dyn Iterator<Item = Result<Batch, ExecuteError>> + 'a> {
+            self.projections.borrow_mut().push(projection.to_vec());
+            self.source.batch_read(projection)
+        }
+    }
 }
@@ -0,0 +1,444 @@
+use std::cmp::Ordering;
+
+use crate::executor::{
+    binder::bound::BoundOrderBy,
+    error::ExecuteError,
+    expression::eval::compare,
+    meta::{
+        row::{Row, SimpleMemoryRow},
+        schema::Schema,
+    },
+    plan::format_tree,
+    types::OwnValue,
+};
+
+use super::{
+    spill::{SpillReader, SpillWriter},
+    PhysicalPlan,
+};
+
+/// Sort 外部归并排序
+/// 输入行在内存中排序，估算占用超过 memory_budget 字节时，
+/// 将已排序的数据作为一个有序段写入临时文件，最后对所有有序段做 k 路归并。
+/// 排序是稳定的，排序键相同的行保持输入顺序
+pub struct Sort<'a> {
+    pub input: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub order_by: Box<[BoundOrderBy]>,
+    pub memory_budget: usize,
+
+    output: SortOutput,
+    spilled_runs: usize,
+}
+
+/// SortRecord 待排序的一行，keys 为 order_by 在该行上的值
+struct SortRecord {
+    keys: Vec<OwnValue>,
+    values: Vec<OwnValue>,
+}
+
+enum SortOutput {
+    Memory(std::vec::IntoIter<SortRecord>),
+    Merge(Vec<SortRun>),
+}
+
+/// SortRun 一个溢写到磁盘的有序段，head 为该段尚未输出的第一行
+struct SortRun {
+    reader: SpillReader,
+    head: Option<SortRecord>,
+}
+
+impl<'a> Sort<'a> {
+    pub fn new(
+        input: Box<dyn PhysicalPlan<'a> + 'a>,
+        order_by: Box<[BoundOrderBy]>,
+        memory_budget: usize,
+    ) -> Self {
+        Self {
+            input,
+            order_by,
+            memory_budget,
+            output: SortOutput::Memory(vec![].into_iter()),
+            spilled_runs: 0,
+        }
+    }
+
+    /// 最近一次执行溢写到临时文件的有序段数目
+    pub fn spilled_runs(&self) -> usize {
+        self.spilled_runs
+    }
+
+    fn sort(&mut self) -> Result<SortOutput, ExecuteError> {
+        let width: usize = self.input.schema().columns.len();
+        let mut buffer: Vec<SortRecord> = vec![];
+        let mut buffer_size: usize = 0;
+        let mut runs: Vec<SortRun> = vec![];
+
+        while let Some(row) = self.input.next()? {
+            let mut keys: Vec<OwnValue> = Vec::with_capacity(self.order_by.len());
+            for order_by in self.order_by.iter() {
+                keys.push(order_by.expression.evaluate(row.as_ref())?);
+            }
+            let values: Vec<OwnValue> = (0..width)
+                .map(|i| row.get(i).cloned())
+                .collect::<Result<_, _>>()?;
+            let record: SortRecord = SortRecord { keys, values };
+            buffer_size += record.estimated_size();
+            buffer.push(record);
+
+            if buffer_size > self.memory_budget {
+                runs.push(self.spill(std::mem::take(&mut buffer))?);
+                buffer_size = 0;
+            }
+        }
+
+        if runs.is_empty() {
+            self.sort_records(&mut buffer);
+            return Ok(SortOutput::Memory(buffer.into_iter()));
+        }
+        if !buffer.is_empty() {
+            runs.push(self.spill(buffer)?);
+        }
+        Ok(SortOutput::Merge(runs))
+    }
+
+    /// 排序后写入临时文件，每行依次写入排序键和列值
+    fn spill(&mut self, mut records: Vec<SortRecord>) -> Result<SortRun, ExecuteError> {
+        self.sort_records(&mut records);
+        let mut writer: SpillWriter = SpillWriter::create()?;
+        for record in records {
+            let mut values: Vec<OwnValue> = record.keys;
+            values.extend(record.values);
+            writer.write(&values)?;
+        }
+        self.spilled_runs += 1;
+
+        let mut run: SortRun = SortRun {
+            reader: writer.finish()?,
+            head: None,
+        };
+        run.advance(self.order_by.len())?;
+        Ok(run)
+    }
+
+    fn sort_records(&self, records: &mut [SortRecord]) {
+        records.sort_by(|l, r| compare_keys(&self.order_by, &l.keys, &r.keys));
+    }
+
+    /// 从所有有序段的首行中取出最小的一行，排序键相同时取序号小的段以保证稳定
+    /// 有序段通常不多，直接线性查找
+    fn merge_next(&self, runs: &mut [SortRun]) -> Result<Option<SortRecord>, ExecuteError> {
+        let mut min: Option<usize> = None;
+        for (index, run) in runs.iter().enumerate() {
+            let Some(head) = &run.head else {
+                continue;
+            };
+            let smaller: bool = match min.and_then(|m| runs[m].head.as_ref()) {
+                Some(current) => {
+                    compare_keys(&self.order_by, &head.keys, &current.keys) == Ordering::Less
+                }
+                None => true,
+            };
+            if smaller {
+                min = Some(index);
+            }
+        }
+        match min {
+            Some(index) => {
+                let record: Option<SortRecord> = runs[index].head.take();
+                runs[index].advance(self.order_by.len())?;
+                Ok(record)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl SortRecord {
+    /// 估算一行占用的内存
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<SortRecord>()
+            + self
+                .keys
+                .iter()
+                .chain(self.values.iter())
+                .map(|value| match value {
+                    OwnValue::String(s) => std::mem::size_of::<OwnValue>() + s.len(),
+                    OwnValue::Blob(b) => std::mem::size_of::<OwnValue>() + b.len(),
+                    _ => std::mem::size_of::<OwnValue>(),
+                })
+                .sum::<usize>()
+    }
+}
+
+impl SortRun {
+    fn advance(&mut self, key_count: usize) -> Result<(), ExecuteError> {
+        self.head = match self.reader.read()? {
+            Some(mut keys) => {
+                let values: Vec<OwnValue> = keys.split_off(key_count);
+                Some(SortRecord { keys, values })
+            }
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+/// 按 order_by 比较两组排序键
+fn compare_keys(order_by: &[BoundOrderBy], left: &[OwnValue], right: &[OwnValue]) -> Ordering {
+    for ((order_by, l), r) in order_by.iter().zip(left.iter()).zip(right.iter()) {
+        let ordering: Ordering = match (l, r) {
+            (OwnValue::Null, OwnValue::Null) => Ordering::Equal,
+            (OwnValue::Null, _) if order_by.nulls_first => Ordering::Less,
+            (OwnValue::Null, _) => Ordering::Greater,
+            (_, OwnValue::Null) if order_by.nulls_first => Ordering::Greater,
+            (_, OwnValue::Null) => Ordering::Less,
+            _ => {
+                let ordering: Ordering = compare_values(l, r);
+                if order_by.asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// 比较两个非 NULL 的值，NaN 比其他数值都大，类型不兼容时视为相等
+fn compare_values(left: &OwnValue, right: &OwnValue) -> Ordering {
+    compare(left, right).unwrap_or(Ordering::Equal)
+}
+
+impl<'a> PhysicalPlan<'a> for Sort<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        self.input.schema()
+    }
+
+    fn to_string(&self) -> String {
+        let order_by: Vec<String> = self.order_by.iter().map(|o| o.to_string()).collect();
+        format_tree(
+            format!("Sort: {}", order_by.join(", ")),
+            vec![self.input.to_string()],
+        )
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.spilled_runs = 0;
+        self.input.open()?;
+        self.output = self.sort()?;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        let record: Option<SortRecord> = match &mut self.output {
+            SortOutput::Memory(records) => records.next(),
+            SortOutput::Merge(runs) => {
+                let mut runs: Vec<SortRun> = std::mem::take(runs);
+                let record: Result<Option<SortRecord>, ExecuteError> = self.merge_next(&mut runs);
+                self.output = SortOutput::Merge(runs);
+                record?
+            }
+        };
+        Ok(record.map(|record| Box::new(SimpleMemoryRow::new(record.values)) as Box<dyn Row + 'a>))
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        // 释放内存中的数据，并删除临时文件
+        self.output = SortOutput::Memory(vec![].into_iter());
+        self.input.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::{
+        binder::bound::BoundOrderBy,
+        expression::{BoundExpression, ColumnRef},
+        meta::{
+            column::Column,
+            data_source::{DataSource, SimpleMemoryDataSource},
+            row::SimpleMemoryRow,
+            schema::Schema,
+        },
+        plan::physical::{
+            scan::SeqScan,
+            sort::{Sort, SortRecord},
+            PhysicalPlan,
+        },
+        test_util::collect,
+        types::{flout64::Float64, int32::Int32, DataType, OwnValue},
+    };
+
+    fn order_by(index: usize, asc: bool, nulls_first: bool) -> BoundOrderBy {
+        BoundOrderBy {
+            expression: BoundExpression::Column(ColumnRef {
+                table: "t".into(),
+                name: ["a", "b"][index].into(),
+                index,
+                data_type: DataType::Int32,
+                nullable: true,
+            }),
+            asc,
+            nulls_first,
+        }
+    }
+
+    fn source(rows: &[(Option<i32>, i32)]) -> SimpleMemoryDataSource {
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(Schema {
+            name: "t".into(),
+            columns: vec![
+                Column {
+                    name: "a".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+                Column {
+                    name: "b".into(),
+                    data_type: DataType::Int32,
+                    nullable: true,
+                    default: None,
+                },
+            ]
+            .into_boxed_slice(),
+        });
+        for (a, b) in rows {
+            source.push_row(SimpleMemoryRow::new(vec![
+                match a {
+                    Some(a) => OwnValue::Int32(Int32::new(*a)),
+                    None => OwnValue::Null,
+                },
+                OwnValue::Int32(Int32::new(*b)),
+            ]));
+        }
+        source
+    }
+
+    fn sort<'a>(
+        source: &'a SimpleMemoryDataSource,
+        order_by: Vec<BoundOrderBy>,
+        memory_budget: usize,
+    ) -> Sort<'a> {
+        let scan: SeqScan = SeqScan::new(
+            source,
+            vec![0, 1].into_boxed_slice(),
+            source.schema().clone(),
+        );
+        Sort::new(Box::new(scan), order_by.into_boxed_slice(), memory_budget)
+    }
+
+    #[test]
+    fn test_sort_nulls() {
+        let source: SimpleMemoryDataSource =
+            source(&[(Some(2), 1), (None, 2), (Some(1), 3), (Some(2), 4)]);
+
+        let mut plan: Sort = sort(&source, vec![order_by(0, true, false)], usize::MAX);
+        assert_eq!(
+            plan.to_string(),
+            "Sort: t.a ASC NULLS LAST\n  SeqScan: t [a, b]"
+        );
+        assert_eq!(
+            collect(&mut plan),
+            vec!["[1, 3]", "[2, 1]", "[2, 4]", "[NULL, 2]"]
+        );
+        assert_eq!(plan.spilled_runs(), 0);
+
+        let mut plan: Sort = sort(
+            &source,
+            vec![order_by(0, false, false), order_by(1, false, false)],
+            usize::MAX,
+        );
+        assert_eq!(
+            collect(&mut plan),
+            vec!["[2, 4]", "[2, 1]", "[1, 3]", "[NULL, 2]"]
+        );
+
+        let mut plan: Sort = sort(&source, vec![order_by(0, true, true)], usize::MAX);
+        assert_eq!(
+            collect(&mut plan),
+            vec!["[NULL, 2]", "[1, 3]", "[2, 1]", "[2, 4]"]
+        );
+    }
+
+    #[test]
+    fn test_external_sort() {
+        // 伪随机的输入，a 有大量重复以检查排序的稳定性
+        let rows: Vec<(Option<i32>, i32)> = (0..500)
+            .map(|i| {
+                let a: i32 = (i * 7919) % 37;
+                (if a == 0 { None } else { Some(a) }, i)
+            })
+            .collect();
+        let source: SimpleMemoryDataSource = source(&rows);
+
+        let mut expected: Vec<(Option<i32>, i32)> = rows.clone();
+        expected.sort_by_key(|(a, _)| (a.is_none(), *a));
+        let expected: Vec<String> = expected
+            .iter()
+            .map(|(a, b)| match a {
+                Some(a) => format!("[{}, {}]", a, b),
+                None => format!("[NULL, {}]", b),
+            })
+            .collect();
+
+        let mut plan: Sort = sort(&source, vec![order_by(0, true, false)], 1024);
+        assert_eq!(collect(&mut plan), expected);
+        assert!(plan.spilled_runs() > 10);
+
+        // 再次执行结果相同
+        assert_eq!(collect(&mut plan), expected);
+    }
+
+    #[test]
+    fn test_estimated_size() {
+        let record = |value: OwnValue| SortRecord {
+            keys: vec![OwnValue::Int32(Int32::new(1))],
+            values: vec![value],
+        };
+        let base: usize = record(OwnValue::Null).estimated_size();
+        assert_eq!(
+            record(OwnValue::String("a".repeat(100))).estimated_size(),
+            base + 100
+        );
+        assert_eq!(
+            record(OwnValue::Blob(vec![0; 4096])).estimated_size(),
+            base + 4096
+        );
+    }
+
+    #[test]
+    fn test_sort_float_nan() {
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(Schema {
+            name: "t".into(),
+            columns: vec![Column {
+                name: "a".into(),
+                data_type: DataType::Float64,
+                nullable: true,
+                default: None,
+            }]
+            .into_boxed_slice(),
+        });
+        for value in [1.5, f64::NAN, -2.0] {
+            source.push_row(SimpleMemoryRow::new(vec![OwnValue::Float64(Float64::new(
+                value,
+            ))]));
+        }
+        let scan: SeqScan =
+            SeqScan::new(&source, vec![0].into_boxed_slice(), source.schema().clone());
+        let mut plan: Sort = Sort::new(
+            Box::new(scan),
+            vec![order_by(0, true, false)].into_boxed_slice(),
+            0,
+        );
+        assert_eq!(collect(&mut plan), vec!["[-2]", "[1.5]", "[NaN]"]);
+        assert_eq!(plan.spilled_runs(), 3);
+    }
+}
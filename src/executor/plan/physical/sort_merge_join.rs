@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+
+use crate::{
+    executor::{
+        error::ExecuteError,
+        expression::{eval::compare, BoundExpression},
+        meta::{
+            row::{Row, SimpleMemoryRow},
+            schema::Schema,
+        },
+        plan::format_tree,
+        types::OwnValue,
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+use super::{
+    join::{concat, evaluate_keys, format_keys, satisfied, values},
+    PhysicalPlan,
+};
+
+/// SortMergeJoin 排序归并连接
+/// open 时读完左右两个输入，分别在内存中按连接键排序，再顺序归并键相等的分组，
+/// 分组内的每一对行再检查 residual。键中有 NULL 的行不会匹配。
+/// left_keys 在左表的行上计算，right_keys 在右表的行上计算，
+/// residual 在拼接后的行上计算
+pub struct SortMergeJoin<'a> {
+    pub left: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub right: Box<dyn PhysicalPlan<'a> + 'a>,
+    pub join_type: JoinType,
+    pub left_keys: Box<[BoundExpression]>,
+    pub right_keys: Box<[BoundExpression]>,
+    pub residual: Option<BoundExpression>,
+    pub schema: Schema,
+
+    output: std::vec::IntoIter<SimpleMemoryRow>,
+}
+
+/// MergeInput 排好序的一侧输入，null_rows 为连接键中有 NULL 的行
+struct MergeInput {
+    rows: Vec<(Vec<OwnValue>, Vec<OwnValue>)>, // (连接键, 行)
+    null_rows: Vec<Vec<OwnValue>>,
+}
+
+impl<'a> SortMergeJoin<'a> {
+    pub fn new(
+        left: Box<dyn PhysicalPlan<'a> + 'a>,
+        right: Box<dyn PhysicalPlan<'a> + 'a>,
+        join_type: JoinType,
+        left_keys: Box<[BoundExpression]>,
+        right_keys: Box<[BoundExpression]>,
+        residual: Option<BoundExpression>,
+        schema: Schema,
+    ) -> Self {
+        debug_assert_eq!(left_keys.len(), right_keys.len());
+        Self {
+            left,
+            right,
+            join_type,
+            left_keys,
+            right_keys,
+            residual,
+            schema,
+            output: vec![].into_iter(),
+        }
+    }
+
+    fn sorted(
+        input: &mut dyn PhysicalPlan,
+        keys: &[BoundExpression],
+    ) -> Result<MergeInput, ExecuteError> {
+        let width: usize = input.schema().columns.len();
+        let mut sorted: MergeInput = MergeInput {
+            rows: vec![],
+            null_rows: vec![],
+        };
+        while let Some(row) = input.next()? {
+            match evaluate_keys(keys, row.as_ref())? {
+                Some(key) => sorted.rows.push((key, values(row.as_ref(), width)?)),
+                None => sorted.null_rows.push(values(row.as_ref(), width)?),
+            }
+        }
+        sorted.rows.sort_by(|l, r| compare_keys(&l.0, &r.0));
+        Ok(sorted)
+    }
+
+    fn join(&mut self) -> Result<Vec<SimpleMemoryRow>, ExecuteError> {
+        let left: MergeInput = Self::sorted(self.left.as_mut(), &self.left_keys)?;
+        let right: MergeInput = Self::sorted(self.right.as_mut(), &self.right_keys)?;
+        let left_width: usize = self.left.schema().columns.len();
+        let right_width: usize = self.right.schema().columns.len();
+        let residual: Option<&BoundExpression> = self.residual.as_ref();
+
+        let mut output: Vec<SimpleMemoryRow> = vec![];
+        let mut right_matched: Vec<bool> = vec![false; right.rows.len()];
+        let (mut i, mut j) = (0, 0);
+        while i < left.rows.len() {
+            let key: &[OwnValue] = &left.rows[i].0;
+            while j < right.rows.len() && compare_keys(&right.rows[j].0, key).is_lt() {
+                j += 1;
+            }
+            let mut end: usize = j;
+            while end < right.rows.len() && compare_keys(&right.rows[end].0, key).is_eq() {
+                end += 1;
+            }
+            // 左表中键相等的每一行与右表的分组 [j, end) 逐对匹配
+            while i < left.rows.len() && compare_keys(&left.rows[i].0, key).is_eq() {
+                let values: &[OwnValue] = &left.rows[i].1;
+                let mut matched: bool = false;
+                for (index, (_, right_values)) in right.rows[j..end].iter().enumerate() {
+                    let row: SimpleMemoryRow =
+                        concat(Some(values), left_width, Some(right_values), right_width);
+                    if satisfied(residual, &row)? {
+                        matched = true;
+                        right_matched[j + index] = true;
+                        output.push(row);
+                    }
+                }
+                if !matched && self.join_type.keeps_left() {
+                    output.push(concat(Some(values), left_width, None, right_width));
+                }
+                i += 1;
+            }
+            j = end;
+        }
+
+        if self.join_type.keeps_left() {
+            for values in left.null_rows.iter() {
+                output.push(concat(Some(values), left_width, None, right_width));
+            }
+        }
+        if self.join_type.keeps_right() {
+            let unmatched = right
+                .rows
+                .iter()
+                .zip(right_matched)
+                .filter(|(_, matched)| !matched)
+                .map(|((_, values), _)| values)
+                .chain(right.null_rows.iter());
+            for values in unmatched {
+                output.push(concat(None, left_width, Some(values), right_width));
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// 按字典序比较两组连接键，类型不兼容的值视为相等
+fn compare_keys(left: &[OwnValue], right: &[OwnValue]) -> Ordering {
+    for (l, r) in left.iter().zip(right.iter()) {
+        match compare(l, r).unwrap_or(Ordering::Equal) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    Ordering::Equal
+}
+
+impl<'a> PhysicalPlan<'a> for SortMergeJoin<'a> {
+    fn children(&self) -> Vec<&dyn PhysicalPlan<'a>> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_string(&self) -> String {
+        let mut head: String = format!(
+            "SortMergeJoin: {} keys={}",
+            self.join_type,
+            format_keys(&self.left_keys, &self.right_keys)
+        );
+        if let Some(residual) = &self.residual {
+            head.push_str(&format!(" filter={}", residual));
+        }
+        format_tree(head, vec![self.left.to_string(), self.right.to_string()])
+    }
+
+    fn open(&mut self) -> Result<(), ExecuteError> {
+        self.left.open()?;
+        self.right.open()?;
+        self.output = self.join()?.into_iter();
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Box<dyn Row + 'a>>, ExecuteError> {
+        Ok(self
+            .output
+            .next()
+            .map(|row| Box::new(row) as Box<dyn Row + 'a>))
+    }
+
+    fn close(&mut self) -> Result<(), ExecuteError> {
+        self.output = vec![].into_iter();
+        self.left.close()?;
+        self.right.close()
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        types::{
+            datetime::{Date, Interval, Time, Timestamp},
+            decimal::Decimal,
+            flout64::Float64,
+            int32::Int32,
+            int64::Int64,
+            OwnValue,
+        },
+    },
+};
+
+/// 临时文件序号，保证同一进程内文件名不重复
+static SPILL_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// 值的类型标记
+const TAG_NULL: u8 = 0;
+const TAG_INT32: u8 = 1;
+const TAG_FLOAT64: u8 = 2;
+const TAG_STRING: u8 = 3;
+const TAG_BOOLEAN: u8 = 4;
+const TAG_INT64: u8 = 5;
+const TAG_DECIMAL: u8 = 6;
+const TAG_DATE: u8 = 7;
+const TAG_TIME: u8 = 8;
+const TAG_TIMESTAMP: u8 = 9;
+const TAG_INTERVAL: u8 = 10;
+const TAG_BLOB: u8 = 11;
+
+/// TempPath 临时文件路径，drop 时删除文件
+#[derive(Debug)]
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new() -> Self {
+        let sequence: u64 = SPILL_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        Self(std::env::temp_dir().join(format!(
+            "simple_db-{}-{}.spill",
+            std::process::id(),
+            sequence
+        )))
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// SpillWriter 将一组组的值顺序写入临时文件
+/// 每组的格式为：值的个数(u32) + 各个值，值的格式为：类型标记(u8) + 小端序的数据，
+/// 字符串为长度(u32) + UTF-8 字节，Blob 为长度(u32) + 字节，Decimal 为值(i128) + 小数位数(u8)，
+/// Interval 为月数(i32) + 天数(i32) + 微秒数(i64)
+#[derive(Debug)]
+pub struct SpillWriter {
+    path: TempPath,
+    writer: BufWriter<File>,
+}
+
+impl SpillWriter {
+    pub fn create() -> Result<Self, ExecuteError> {
+        let path: TempPath = TempPath::new();
+        let file: File = File::create(&path.0).map_err(io_error)?;
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+
+    pub fn write(&mut self, values: &[OwnValue]) -> Result<(), ExecuteError> {
+        self.write_u32(values.len())?;
+        for value in values {
+            match value {
+                OwnValue::Null => self.write_bytes(&[TAG_NULL])?,
+                OwnValue::Int32(v) => {
+                    self.write_bytes(&[TAG_INT32])?;
+                    self.write_bytes(&v.value().to_le_bytes())?;
+                }
+                OwnValue::Float64(v) => {
+                    self.write_bytes(&[TAG_FLOAT64])?;
+                    self.write_bytes(&v.value().to_le_bytes())?;
+                }
+                OwnValue::String(s) => {
+                    self.write_bytes(&[TAG_STRING])?;
+                    self.write_u32(s.len())?;
+                    self.write_bytes(s.as_bytes())?;
+                }
+                OwnValue::Boolean(v) => self.write_bytes(&[TAG_BOOLEAN, *v as u8])?,
+                OwnValue::Int64(v) => {
+                    self.write_bytes(&[TAG_INT64])?;
+                    self.write_bytes(&v.value().to_le_bytes())?;
+                }
+                OwnValue::Decimal(v) => {
+                    self.write_bytes(&[TAG_DECIMAL])?;
+                    self.write_bytes(&v.value().to_le_bytes())?;
+                    self.write_bytes(&[v.scale()])?;
+                }
+                OwnValue::Date(v) => {
+                    self.write_bytes(&[TAG_DATE])?;
+                    self.write_bytes(&v.days().to_le_bytes())?;
+                }
+                OwnValue::Time(v) => {
+                    self.write_bytes(&[TAG_TIME])?;
+                    self.write_bytes(&v.micros().to_le_bytes())?;
+                }
+                OwnValue::Timestamp(v) => {
+                    self.write_bytes(&[TAG_TIMESTAMP])?;
+                    self.write_bytes(&v.micros().to_le_bytes())?;
+                }
+                OwnValue::Interval(v) => {
+                    self.write_bytes(&[TAG_INTERVAL])?;
+                    self.write_bytes(&v.months().to_le_bytes())?;
+                    self.write_bytes(&v.days().to_le_bytes())?;
+                    self.write_bytes(&v.micros().to_le_bytes())?;
+                }
+                OwnValue::Blob(bytes) => {
+                    self.write_bytes(&[TAG_BLOB])?;
+                    self.write_u32(bytes.len())?;
+                    self.write_bytes(bytes)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 写入完成，返回从头读取该文件的 SpillReader
+    pub fn finish(self) -> Result<SpillReader, ExecuteError> {
+        let SpillWriter { path, mut writer } = self;
+        writer.flush().map_err(io_error)?;
+        drop(writer);
+        let file: File = File::open(&path.0).map_err(io_error)?;
+        Ok(SpillReader {
+            _path: path,
+            reader: BufReader::new(file),
+        })
+    }
+
+    fn write_u32(&mut self, len: usize) -> Result<(), ExecuteError> {
+        match u32::try_from(len) {
+            Ok(len) => self.write_bytes(&len.to_le_bytes()),
+            Err(_) => Err(ExecuteError::new(
+                ErrorCode::DataCorrupted,
+                format!("spill length {} too large", len),
+            )),
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ExecuteError> {
+        self.writer.write_all(bytes).map_err(io_error)
+    }
+}
+
+/// SpillReader 按写入顺序读取 SpillWriter 写入的值，drop 时删除临时文件
+#[derive(Debug)]
+pub struct SpillReader {
+    _path: TempPath, // drop 时删除临时文件
+    reader: BufReader<File>,
+}
+
+impl SpillReader {
+    /// 读取下一组值，文件结束时返回 None
+    pub fn read(&mut self) -> Result<Option<Vec<OwnValue>>, ExecuteError> {
+        let mut len: [u8; 4] = [0; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        }
+        let len: usize = u32::from_le_bytes(len) as usize;
+        let mut values: Vec<OwnValue> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let value: OwnValue = match self.read_array::<1>()?[0] {
+                TAG_NULL => OwnValue::Null,
+                TAG_INT32 => OwnValue::Int32(Int32::new(i32::from_le_bytes(self.read_array()?))),
+                TAG_FLOAT64 => {
+                    OwnValue::Float64(Float64::new(f64::from_le_bytes(self.read_array()?)))
+                }
+                TAG_STRING => match String::from_utf8(self.read_bytes()?) {
+                    Ok(s) => OwnValue::String(s),
+                    Err(_) => return Err(corrupted()),
+                },
+                TAG_BOOLEAN => match self.read_array::<1>()?[0] {
+                    0 => OwnValue::Boolean(false),
+                    1 => OwnValue::Boolean(true),
+                    _ => return Err(corrupted()),
+                },
+                TAG_INT64 => OwnValue::Int64(Int64::new(i64::from_le_bytes(self.read_array()?))),
+                TAG_DECIMAL => {
+                    let value: i128 = i128::from_le_bytes(self.read_array()?);
+                    let scale: u8 = self.read_array::<1>()?[0];
+                    OwnValue::Decimal(Decimal::new(value, scale).map_err(|_| corrupted())?)
+                }
+                TAG_DATE => OwnValue::Date(Date::new(i32::from_le_bytes(self.read_array()?))),
+                TAG_TIME => OwnValue::Time(Time::new(i64::from_le_bytes(self.read_array()?))),
+                TAG_TIMESTAMP => {
+                    OwnValue::Timestamp(Timestamp::new(i64::from_le_bytes(self.read_array()?)))
+                }
+                TAG_INTERVAL => OwnValue::Interval(Interval::new(
+                    i32::from_le_bytes(self.read_array()?),
+                    i32::from_le_bytes(self.read_array()?),
+                    i64::from_le_bytes(self.read_array()?),
+                )),
+                TAG_BLOB => OwnValue::Blob(self.read_bytes()?),
+                _ => return Err(corrupted()),
+            };
+            values.push(value);
+        }
+        Ok(Some(values))
+    }
+
+    /// 读取长度(u32) + 字节
+    fn read_bytes(&mut self) -> Result<Vec<u8>, ExecuteError> {
+        let len: usize = u32::from_le_bytes(self.read_array()?) as usize;
+        let mut bytes: Vec<u8> = vec![0; len];
+        self.reader.read_exact(&mut bytes).map_err(io_error)?;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ExecuteError> {
+        let mut buf: [u8; N] = [0; N];
+        self.reader.read_exact(&mut buf).map_err(io_error)?;
+        Ok(buf)
+    }
+}
+
+fn corrupted() -> ExecuteError {
+    ExecuteError::new(ErrorCode::DataCorrupted, "corrupted spill file")
+}
+
+fn io_error(error: std::io::Error) -> ExecuteError {
+    ExecuteError::new(ErrorCode::IoError, format!("spill file error: {}", error)).with_source(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::types::{
+        datetime::{Date, Interval, Time, Timestamp},
+        decimal::Decimal,
+        flout64::Float64,
+        int32::Int32,
+        int64::Int64,
+        OwnValue,
+    };
+
+    use super::{SpillReader, SpillWriter};
+
+    #[test]
+    fn test_round_trip() {
+        let rows: Vec<Vec<OwnValue>> = vec![
+            vec![
+                OwnValue::Int32(Int32::new(-7)),
+                OwnValue::Float64(Float64::new(1.5)),
+                OwnValue::String("张三".into()),
+                OwnValue::Null,
+            ],
+            vec![],
+            vec![OwnValue::String("".into())],
+            vec![
+                OwnValue::Boolean(true),
+                OwnValue::Int64(Int64::new(-5_000_000_000)),
+                OwnValue::Decimal(Decimal::parse("-12.345").unwrap()),
+                OwnValue::Date(Date::parse("2024-02-29").unwrap()),
+                OwnValue::Time(Time::parse("23:59:59.5").unwrap()),
+                OwnValue::Timestamp(Timestamp::parse("1969-12-31 23:00:00").unwrap()),
+                OwnValue::Interval(Interval::new(14, -3, 7_000_000)),
+                OwnValue::Blob(vec![0, 0xff, 0x10]),
+            ],
+        ];
+        let mut writer: SpillWriter = SpillWriter::create().unwrap();
+        for row in rows.iter() {
+            writer.write(row).unwrap();
+        }
+        let path = writer.path.0.clone();
+        let mut reader: SpillReader = writer.finish().unwrap();
+        for row in rows.iter() {
+            assert_eq!(reader.read().unwrap().as_ref(), Some(row));
+        }
+        assert!(reader.read().unwrap().is_none());
+
+        assert!(path.exists());
+        drop(reader);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,447 @@
+use crate::{
+    executor::{
+        expression::{eval::truth, BoundExpression},
+        meta::statistics::{ColumnStatistics, Statistics},
+        plan::logic::{join::Join, LogicNode},
+        types::OwnValue,
+    },
+    sql::parser::ast::{
+        expression::{BinaryOperator, UnaryOperator},
+        select::JoinType,
+    },
+};
+
+use super::physical::JoinAlgorithm;
+
+/// 无法估算的等值条件的选择率
+const EQUAL_SELECTIVITY: f64 = 0.1;
+
+/// 无法估算的范围条件的选择率
+const RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// 其余无法估算的谓词的选择率
+const DEFAULT_SELECTIVITY: f64 = 0.5;
+
+/// 哈希连接中把一行插入哈希表的代价，以处理一行的代价为单位
+const HASH_BUILD_COST: f64 = 2.0;
+
+/// Estimate 计划输出的估算，行数以及每一列的分布
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub rows: f64,
+    pub columns: Vec<ColumnEstimate>,
+}
+
+/// ColumnEstimate 列的分布，min、max 未知时为 NULL
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnEstimate {
+    pub distinct: f64,
+    pub null_fraction: f64,
+    pub min: OwnValue,
+    pub max: OwnValue,
+}
+
+impl ColumnEstimate {
+    /// 没有统计信息的列，假设每一行的值都不同
+    fn unknown(rows: f64) -> Self {
+        Self {
+            distinct: rows,
+            null_fraction: 0.0,
+            min: OwnValue::Null,
+            max: OwnValue::Null,
+        }
+    }
+
+    fn from_statistics(statistics: &ColumnStatistics, rows: usize) -> Self {
+        Self {
+            distinct: statistics.distinct_count as f64,
+            null_fraction: match rows {
+                0 => 0.0,
+                rows => statistics.null_count as f64 / rows as f64,
+            },
+            min: statistics.min.clone(),
+            max: statistics.max.clone(),
+        }
+    }
+}
+
+impl Estimate {
+    /// 行数变为 rows，不同值的个数不超过行数
+    fn with_rows(mut self, rows: f64) -> Self {
+        self.rows = rows.max(0.0);
+        for column in self.columns.iter_mut() {
+            column.distinct = column.distinct.min(self.rows);
+        }
+        self
+    }
+}
+
+/// 估算逻辑计划输出的行数和列的分布，表的统计信息来自 DataSource::statistics
+pub fn estimate(plan: &LogicNode) -> Estimate {
+    match plan {
+        LogicNode::Scan(scan) => {
+            let statistics: Statistics = scan.data_source.statistics();
+            let table = scan.data_source.schema();
+            let columns: Vec<ColumnEstimate> = scan
+                .projection
+                .iter()
+                .map(|name| match table.column_index(name) {
+                    Some(index) => ColumnEstimate::from_statistics(
+                        &statistics.columns[index],
+                        statistics.row_count,
+                    ),
+                    None => ColumnEstimate::unknown(statistics.row_count as f64),
+                })
+                .collect();
+            let estimate: Estimate = Estimate {
+                rows: statistics.row_count as f64,
+                columns,
+            };
+            match &scan.filter {
+                Some(filter) => {
+                    let rows: f64 = estimate.rows * selectivity(filter, &estimate.columns);
+                    estimate.with_rows(rows)
+                }
+                None => estimate,
+            }
+        }
+        LogicNode::OneRow(_) => Estimate {
+            rows: 1.0,
+            columns: vec![],
+        },
+        LogicNode::Filter(filter) => {
+            let input: Estimate = estimate(&filter.input);
+            let rows: f64 = input.rows * selectivity(&filter.predicate, &input.columns);
+            input.with_rows(rows)
+        }
+        LogicNode::Projection(projection) => {
+            let input: Estimate = estimate(&projection.input);
+            let columns: Vec<ColumnEstimate> = projection
+                .expressions
+                .iter()
+                .map(|expression| column_estimate(expression, &input))
+                .collect();
+            Estimate {
+                rows: input.rows,
+                columns,
+            }
+        }
+        LogicNode::Aggregate(aggregate) => {
+            let input: Estimate = estimate(&aggregate.input);
+            let mut columns: Vec<ColumnEstimate> = aggregate
+                .group_by
+                .iter()
+                .map(|expression| column_estimate(expression, &input))
+                .collect();
+            // 分组数不超过各分组列不同值个数的乘积，也不超过输入的行数；没有 GROUP BY 时只有一组
+            let rows: f64 = if aggregate.group_by.is_empty() {
+                1.0
+            } else {
+                columns
+                    .iter()
+                    .map(|column| column.distinct.max(1.0))
+                    .product::<f64>()
+                    .min(input.rows)
+            };
+            for _ in aggregate.aggregates.iter() {
+                columns.push(ColumnEstimate::unknown(rows));
+            }
+            Estimate { rows, columns }.with_rows(rows)
+        }
+        LogicNode::Sort(sort) => estimate(&sort.input),
+        LogicNode::SubqueryAlias(alias) => estimate(&alias.input),
+        LogicNode::Limit(limit) => {
+            let input: Estimate = estimate(&limit.input);
+            let mut rows: f64 = input.rows - limit.offset as f64;
+            if let Some(limit) = limit.limit {
+                rows = rows.min(limit as f64);
+            }
+            input.with_rows(rows)
+        }
+        LogicNode::Join(join) => estimate_join(join),
+    }
+}
+
+/// 估算连接的输出，输出的列为左右两侧的列依次拼接
+pub fn estimate_join(join: &Join) -> Estimate {
+    let left: Estimate = estimate(&join.left);
+    let right: Estimate = estimate(&join.right);
+    let (left_rows, right_rows) = (left.rows, right.rows);
+    let mut columns: Vec<ColumnEstimate> = left.columns;
+    columns.extend(right.columns);
+    let selectivity: f64 = match &join.condition {
+        Some(condition) => selectivity(condition, &columns),
+        None => 1.0,
+    };
+    let rows: f64 = join_rows(join.join_type, left_rows, right_rows, selectivity);
+    Estimate { rows, columns }.with_rows(rows)
+}
+
+/// 连接输出的行数，外连接至少输出保留一侧的所有行
+pub fn join_rows(join_type: JoinType, left_rows: f64, right_rows: f64, selectivity: f64) -> f64 {
+    let mut rows: f64 = left_rows * right_rows * selectivity;
+    if join_type.keeps_left() {
+        rows = rows.max(left_rows);
+    }
+    if join_type.keeps_right() {
+        rows = rows.max(right_rows);
+    }
+    rows
+}
+
+/// 连接的代价，以处理一行的代价为单位
+/// 嵌套循环对每一对行计算连接条件；哈希连接用右表建哈希表，左表逐行探测；
+/// 排序归并连接先对两侧排序，再顺序归并
+pub fn join_cost(
+    algorithm: JoinAlgorithm,
+    left_rows: f64,
+    right_rows: f64,
+    output_rows: f64,
+) -> f64 {
+    let sort = |rows: f64| rows * rows.max(2.0).log2();
+    match algorithm {
+        JoinAlgorithm::NestedLoop => left_rows * right_rows + left_rows + right_rows + output_rows,
+        JoinAlgorithm::Hash => HASH_BUILD_COST * right_rows + left_rows + output_rows,
+        JoinAlgorithm::SortMerge => {
+            sort(left_rows) + sort(right_rows) + left_rows + right_rows + output_rows
+        }
+    }
+}
+
+/// 估算谓词为真的行所占的比例，columns 为谓词输入行中各列的分布
+pub fn selectivity(predicate: &BoundExpression, columns: &[ColumnEstimate]) -> f64 {
+    let selectivity: f64 = match predicate {
+        BoundExpression::Literal(literal) => match truth(&literal.value) {
+            Some(true) => 1.0,
+            _ => 0.0,
+        },
+        BoundExpression::Unary(unary) if matches!(unary.operator, UnaryOperator::NOT(_)) => {
+            1.0 - selectivity(&unary.expression, columns)
+        }
+        BoundExpression::Binary(binary) => {
+            let (left, right) = (binary.left.as_ref(), binary.right.as_ref());
+            match &binary.operator {
+                BinaryOperator::AND(_) => selectivity(left, columns) * selectivity(right, columns),
+                BinaryOperator::OR(_) => {
+                    let (l, r) = (selectivity(left, columns), selectivity(right, columns));
+                    l + r - l * r
+                }
+                BinaryOperator::Equal(_) => equal_selectivity(left, right, columns),
+                BinaryOperator::NotEqual(_) => {
+                    match (column(left, columns), column(right, columns)) {
+                        (Some(c), None) | (None, Some(c)) => {
+                            (1.0 - c.null_fraction) - equal_selectivity(left, right, columns)
+                        }
+                        _ => 1.0 - EQUAL_SELECTIVITY,
+                    }
+                }
+                BinaryOperator::LessThan(_) | BinaryOperator::LessThanOrEqual(_) => {
+                    range_selectivity(left, right, columns)
+                }
+                BinaryOperator::GreaterThan(_) | BinaryOperator::GreaterThanOrEqual(_) => {
+                    range_selectivity(right, left, columns)
+                }
+                _ => DEFAULT_SELECTIVITY,
+            }
+        }
+        _ => DEFAULT_SELECTIVITY,
+    };
+    selectivity.clamp(0.0, 1.0)
+}
+
+/// 列引用在输入中的分布
+fn column<'c>(
+    expression: &BoundExpression,
+    columns: &'c [ColumnEstimate],
+) -> Option<&'c ColumnEstimate> {
+    match expression {
+        BoundExpression::Column(column) => columns.get(column.index),
+        _ => None,
+    }
+}
+
+/// 列与列相等时，假设不同值较少的一列的值都出现在另一列中；列与常量相等时，假设值均匀分布
+fn equal_selectivity(
+    left: &BoundExpression,
+    right: &BoundExpression,
+    columns: &[ColumnEstimate],
+) -> f64 {
+    match (column(left, columns), column(right, columns)) {
+        (Some(l), Some(r)) => {
+            (1.0 - l.null_fraction) * (1.0 - r.null_fraction) / l.distinct.max(r.distinct).max(1.0)
+        }
+        (Some(c), None) | (None, Some(c)) => {
+            let constant: &BoundExpression = if column(left, columns).is_some() {
+                right
+            } else {
+                left
+            };
+            match constant {
+                BoundExpression::Literal(literal) if literal.value == OwnValue::Null => 0.0,
+                _ => (1.0 - c.null_fraction) / c.distinct.max(1.0),
+            }
+        }
+        (None, None) => EQUAL_SELECTIVITY,
+    }
+}
+
+/// less < greater 的选择率，列与数值常量比较时按 min、max 之间均匀分布估算
+fn range_selectivity(
+    less: &BoundExpression,
+    greater: &BoundExpression,
+    columns: &[ColumnEstimate],
+) -> f64 {
+    let (c, value, column_is_less) = match (less, greater) {
+        (BoundExpression::Column(_), BoundExpression::Literal(literal)) => {
+            (column(less, columns), &literal.value, true)
+        }
+        (BoundExpression::Literal(literal), BoundExpression::Column(_)) => {
+            (column(greater, columns), &literal.value, false)
+        }
+        _ => return RANGE_SELECTIVITY,
+    };
+    let Some(c) = c else {
+        return RANGE_SELECTIVITY;
+    };
+    let (Some(min), Some(max), Some(value)) = (number(&c.min), number(&c.max), number(value))
+    else {
+        return RANGE_SELECTIVITY;
+    };
+    // 小于 value 的非 NULL 值所占的比例
+    let below: f64 = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else if value > min {
+        1.0
+    } else {
+        0.0
+    };
+    let fraction: f64 = if column_is_less { below } else { 1.0 - below };
+    fraction * (1.0 - c.null_fraction)
+}
+
+fn number(value: &OwnValue) -> Option<f64> {
+    value.to_float64().map(|v| v.value())
+}
+
+/// 表达式输出列的分布，列引用沿用输入列的分布
+fn column_estimate(expression: &BoundExpression, input: &Estimate) -> ColumnEstimate {
+    match column(expression, &input.columns) {
+        Some(column) => column.clone(),
+        None => ColumnEstimate::unknown(input.rows),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        executor::{
+            context::SimpleMemoryContext,
+            meta::{
+                column::Column, data_source::SimpleMemoryDataSource, row::SimpleMemoryRow,
+                schema::Schema,
+            },
+            planner::logic::LogicPlanner,
+            types::{int32::Int32, DataType},
+        },
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    /// stu(id, age)，id 为 0..100，age 为 10..20 循环，其中 10 行 age 为 NULL
+    fn context() -> SimpleMemoryContext {
+        let column = |name: &str| Column {
+            name: name.into(),
+            data_type: DataType::Int32,
+            nullable: true,
+            default: None,
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(Schema {
+            name: "stu".into(),
+            columns: vec![column("id"), column("age")].into_boxed_slice(),
+        });
+        for id in 0..100 {
+            let age: OwnValue = match id % 10 {
+                0 => OwnValue::Null,
+                _ => OwnValue::Int32(Int32::new(10 + id % 10)),
+            };
+            source.push_row(SimpleMemoryRow::new(vec![
+                OwnValue::Int32(Int32::new(id)),
+                age,
+            ]));
+        }
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        context.add_table(Box::new(source));
+        context
+    }
+
+    fn rows(context: &SimpleMemoryContext, sql: &str) -> f64 {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        let Statement::Select(select) = &statements.statements[0] else {
+            panic!("expect select");
+        };
+        let plan: LogicNode = LogicPlanner::new(context, sql).plan_select(select).unwrap();
+        estimate(&plan).rows
+    }
+
+    fn assert_rows(context: &SimpleMemoryContext, sql: &str, expected: f64) {
+        let actual: f64 = rows(context, sql);
+        assert!((actual - expected).abs() < 1e-6, "{}: {}", sql, actual);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let context: SimpleMemoryContext = context();
+        assert_rows(&context, "SELECT id FROM stu", 100.0);
+        // age 有 9 个不同值，10% 为 NULL
+        assert_rows(&context, "SELECT id FROM stu WHERE age = 15", 10.0);
+        assert_rows(&context, "SELECT id FROM stu WHERE id = 1 OR id = 2", 1.99);
+        assert_rows(&context, "SELECT id FROM stu WHERE age <> 15", 80.0);
+        // id 在 [0, 99] 中均匀分布
+        assert_rows(&context, "SELECT id FROM stu WHERE id < 33", 100.0 / 3.0);
+        assert_rows(
+            &context,
+            "SELECT id FROM stu WHERE 33 < id AND id < 1000",
+            200.0 / 3.0,
+        );
+        assert_rows(
+            &context,
+            "SELECT id FROM stu WHERE NOT id >= 33",
+            100.0 / 3.0,
+        );
+        assert_rows(&context, "SELECT age FROM stu GROUP BY age", 9.0);
+        assert_rows(&context, "SELECT count(*) FROM stu", 1.0);
+        assert_rows(&context, "SELECT id FROM stu LIMIT 10 OFFSET 95", 5.0);
+        assert_rows(
+            &context,
+            "SELECT a.id FROM stu AS a JOIN stu AS b ON a.id = b.id",
+            100.0,
+        );
+        assert_rows(
+            &context,
+            "SELECT a.id FROM stu AS a LEFT JOIN stu AS b ON a.id = b.id AND b.id < 0",
+            100.0,
+        );
+        assert_rows(&context, "SELECT a.id FROM stu AS a, stu AS b", 10000.0);
+    }
+
+    #[test]
+    fn test_join_cost() {
+        // 哈希连接用较小的表建哈希表更便宜
+        assert!(
+            join_cost(JoinAlgorithm::Hash, 1000.0, 10.0, 10.0)
+                < join_cost(JoinAlgorithm::Hash, 10.0, 1000.0, 10.0)
+        );
+        assert!(
+            join_cost(JoinAlgorithm::Hash, 1000.0, 1000.0, 1000.0)
+                < join_cost(JoinAlgorithm::SortMerge, 1000.0, 1000.0, 1000.0)
+        );
+        assert!(
+            join_cost(JoinAlgorithm::SortMerge, 1000.0, 1000.0, 1000.0)
+                < join_cost(JoinAlgorithm::NestedLoop, 1000.0, 1000.0, 1000.0)
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{
+    error::ErrorCode,
+    executor::{
+        binder::{
+            bound::{BoundFrom, BoundSelect},
+            Binder,
+        },
+        context::Context,
+        error::ExecuteError,
+        expression::{
+            AggregateCall, BoundBinary, BoundExpression, BoundUnary, ColumnRef, FunctionCall,
+        },
+        meta::{column::Column, schema::Schema},
+        plan::logic::{
+            aggregate::Aggregate, filter::Filter, join::Join, limit::Limit, one_row::OneRow,
+            projection::Projection, scan::Scan, sort::Sort, subquery_alias::SubqueryAlias,
+            LogicNode,
+        },
+        types::OwnValue,
+    },
+    sql::parser::ast::{select::JoinType, Select},
+};
+
+/// LogicPlanner 绑定 SELECT 语句，并将其转换为逻辑计划树
+///
+/// 生成的计划自底向上依次为
+/// Scan / SubqueryAlias / Join / OneRow -> Filter(WHERE) -> Aggregate(GROUP BY, 聚合函数) -> Filter(HAVING)
+/// -> Sort(ORDER BY) -> Projection(SELECT) -> Limit(LIMIT, OFFSET)
+pub struct LogicPlanner<'a, 's> {
+    context: &'a dyn Context, // 计划借用 context 中的表，SQL 文本和参数只在生成计划时使用
+    raw_sql: &'s str,
+    params: &'s [OwnValue],
+}
+
+impl<'a, 's> LogicPlanner<'a, 's> {
+    pub fn new(context: &'a dyn Context, raw_sql: &'s str) -> Self {
+        Self {
+            context,
+            raw_sql,
+            params: &[],
+        }
+    }
+
+    /// 设置参数占位符的值
+    pub fn with_params(mut self, params: &'s [OwnValue]) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn plan_select(&self, select: &Select) -> Result<LogicNode<'a>, ExecuteError> {
+        let bound: BoundSelect<'a> = Binder::new(self.context, self.raw_sql)
+            .with_params(self.params)
+            .bind_select(select)?;
+        Self::plan_bound(bound)
+    }
+
+    /// 将绑定后的 SELECT 语句转换为逻辑计划
+    pub fn plan_bound(bound: BoundSelect<'a>) -> Result<LogicNode<'a>, ExecuteError> {
+        let BoundSelect {
+            from,
+            mut items,
+            wheres,
+            group_by,
+            mut having,
+            mut order_by,
+            limit,
+            offset,
+            schema,
+        } = bound;
+
+        // FROM 中逗号分隔的多项依次做笛卡尔积，没有 FROM 时从一行空行计算
+        let mut plan: LogicNode<'a> = match from
+            .into_vec()
+            .into_iter()
+            .reduce(|left, right| BoundFrom::join(left, right, JoinType::Cross, None))
+        {
+            Some(from) => Self::plan_from(from)?,
+            None => LogicNode::OneRow(OneRow::new()),
+        };
+
+        if let Some(predicate) = wheres {
+            plan = LogicNode::Filter(Filter::new(plan, predicate));
+        }
+
+        let mut aggregates: Vec<AggregateCall> = vec![];
+        for item in items.iter() {
+            item.collect_aggregates(&mut aggregates);
+        }
+        if let Some(predicate) = having.as_ref() {
+            predicate.collect_aggregates(&mut aggregates);
+        }
+        for item in order_by.iter() {
+            item.expression.collect_aggregates(&mut aggregates);
+        }
+
+        if !group_by.is_empty() || having.is_some() || !aggregates.is_empty() {
+            let mut columns: Vec<Column> = group_by
+                .iter()
+                .map(|expression| Column {
+                    name: match expression {
+                        BoundExpression::Column(column) => column.name.clone(),
+                        _ => expression.to_string().into_boxed_str(),
+                    },
+                    data_type: expression.data_type().clone(),
+                    nullable: expression.nullable(),
+                    default: None,
+                })
+                .collect();
+            for aggregate in aggregates.iter() {
+                let expression: BoundExpression = BoundExpression::Aggregate(aggregate.clone());
+                columns.push(Column {
+                    name: expression.to_string().into_boxed_str(),
+                    data_type: expression.data_type().clone(),
+                    nullable: expression.nullable(),
+                    default: None,
+                });
+            }
+            let aggregate: Aggregate<'a> = Aggregate::new(
+                plan,
+                group_by,
+                aggregates.into_boxed_slice(),
+                Schema {
+                    name: schema.name.clone(),
+                    columns: columns.into_boxed_slice(),
+                },
+            );
+
+            // 聚合之后的表达式只能引用分组列和聚合函数
+            for item in items.iter_mut() {
+                *item = Self::rewrite_grouped(item, &aggregate)?;
+            }
+            if let Some(predicate) = having.as_mut() {
+                *predicate = Self::rewrite_grouped(predicate, &aggregate)?;
+            }
+            for item in order_by.iter_mut() {
+                item.expression = Self::rewrite_grouped(&item.expression, &aggregate)?;
+            }
+            plan = LogicNode::Aggregate(aggregate);
+        }
+
+        if let Some(predicate) = having {
+            plan = LogicNode::Filter(Filter::new(plan, predicate));
+        }
+
+        if !order_by.is_empty() {
+            plan = LogicNode::Sort(Sort::new(plan, order_by));
+        }
+
+        plan = LogicNode::Projection(Projection::new(plan, items, schema));
+
+        if limit.is_some() || offset.is_some() {
+            plan = LogicNode::Limit(Limit::new(plan, limit, offset.unwrap_or(0)));
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_from(from: BoundFrom<'a>) -> Result<LogicNode<'a>, ExecuteError> {
+        match from {
+            BoundFrom::Table {
+                data_source,
+                schema,
+                ..
+            } => {
+                let projection: Box<[Box<str>]> =
+                    schema.columns.iter().map(|c| c.name.clone()).collect();
+                Ok(LogicNode::Scan(Scan::new(data_source, projection, schema)))
+            }
+            BoundFrom::SubQuery { select, schema } => {
+                let input: LogicNode<'a> = Self::plan_bound(*select)?;
+                Ok(LogicNode::SubqueryAlias(SubqueryAlias::new(input, schema)))
+            }
+            BoundFrom::Join {
+                left,
+                right,
+                join_type,
+                condition,
+                schema,
+            } => Ok(LogicNode::Join(Join::new(
+                Self::plan_from(*left)?,
+                Self::plan_from(*right)?,
+                join_type,
+                condition,
+                schema,
+            ))),
+        }
+    }
+
+    /// 将聚合之上的表达式改写为引用聚合的输出列
+    fn rewrite_grouped(
+        expression: &BoundExpression,
+        aggregate: &Aggregate,
+    ) -> Result<BoundExpression, ExecuteError> {
+        let index: Option<usize> = match expression {
+            BoundExpression::Aggregate(call) => aggregate
+                .aggregates
+                .iter()
+                .position(|a| a == call)
+                .map(|index| aggregate.group_by.len() + index),
+            _ => aggregate.group_by.iter().position(|g| g == expression),
+        };
+        if let Some(index) = index {
+            let column: &Column = &aggregate.schema.columns[index];
+            let table: Box<str> = match expression {
+                BoundExpression::Column(c) => c.table.clone(),
+                _ => "".into(),
+            };
+            return Ok(BoundExpression::Column(ColumnRef {
+                table,
+                name: column.name.clone(),
+                index,
+                data_type: column.data_type.clone(),
+                nullable: column.nullable,
+            }));
+        }
+        match expression {
+            BoundExpression::Literal(_) => Ok(expression.clone()),
+            BoundExpression::Aggregate(call) => Err(ExecuteError::new(
+                ErrorCode::InternalError,
+                format!("aggregate function {} not found in aggregate plan", call),
+            )),
+            BoundExpression::Column(column) => Err(ExecuteError::new(
+                ErrorCode::GroupingError,
+                format!(
+                "column {} must appear in the GROUP BY clause or be used in an aggregate function",
+                column
+            ),
+            )),
+            BoundExpression::Binary(binary) => Ok(BoundExpression::Binary(BoundBinary {
+                left: Box::new(Self::rewrite_grouped(&binary.left, aggregate)?),
+                operator: binary.operator.clone(),
+                right: Box::new(Self::rewrite_grouped(&binary.right, aggregate)?),
+                data_type: binary.data_type.clone(),
+            })),
+            BoundExpression::Unary(unary) => Ok(BoundExpression::Unary(BoundUnary {
+                operator: unary.operator.clone(),
+                expression: Box::new(Self::rewrite_grouped(&unary.expression, aggregate)?),
+                data_type: unary.data_type.clone(),
+            })),
+            BoundExpression::Function(call) => Ok(BoundExpression::Function(FunctionCall {
+                function: call.function,
+                arguments: call
+                    .arguments
+                    .iter()
+                    .map(|argument| Self::rewrite_grouped(argument, aggregate))
+                    .collect::<Result<_, _>>()?,
+                data_type: call.data_type.clone(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        executor::{context::SimpleMemoryContext, plan::logic::LogicPlan, test_util::context},
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn plan<'a>(
+        context: &'a SimpleMemoryContext,
+        sql: &'a str,
+    ) -> Result<LogicNode<'a>, ExecuteError> {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        match &statements.statements[0] {
+            Statement::Select(select) => LogicPlanner::new(context, sql).plan_select(select),
+            _ => panic!("expect select"),
+        }
+    }
+
+    #[test]
+    fn plan_scan() {
+        let context: SimpleMemoryContext = context();
+        let plan: LogicNode = plan(&context, "SELECT name FROM stu").unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "Projection: stu.name\n  Scan: stu [name, age]"
+        );
+        assert_eq!(plan.children().len(), 1);
+        assert_eq!(plan.children()[0].children().len(), 0);
+        assert_eq!(
+            format!("{:?}", plan.schema()),
+            "stu(name Varchar(32) NULLABLE)"
+        );
+    }
+
+    #[test]
+    fn plan_without_from() {
+        let context: SimpleMemoryContext = context();
+        let plan: LogicNode = plan(&context, "SELECT 1 + 2 WHERE TRUE").unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "Projection: (1 + 2)\n  Filter: TRUE\n    OneRow"
+        );
+    }
+
+    #[test]
+    fn plan_all_clauses() {
+        let context: SimpleMemoryContext = context();
+        let plan: LogicNode = plan(
+            &context,
+            "SELECT age + 1 AS a FROM stu WHERE age > 2 GROUP BY age HAVING age < 30 ORDER BY a DESC",
+        )
+        .unwrap();
+        assert_eq!(
+            plan.to_string(),
+            [
+                "Projection: (stu.age + 1)",
+                "  Sort: (stu.age + 1) DESC NULLS FIRST",
+                "    Filter: (stu.age < 30)",
+                "      Aggregate: group_by=[stu.age] aggregates=[]",
+                "        Filter: (stu.age > 2)",
+                "          Scan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+        match &plan {
+            LogicNode::Projection(projection) => match &projection.expressions[0] {
+                BoundExpression::Binary(binary) => match binary.left.as_ref() {
+                    BoundExpression::Column(column) => assert_eq!(column.index, 0),
+                    _ => panic!("expect column"),
+                },
+                _ => panic!("expect binary"),
+            },
+            _ => panic!("expect projection"),
+        }
+    }
+
+    #[test]
+    fn plan_subquery_alias() {
+        let context: SimpleMemoryContext = context();
+        let plan: LogicNode = plan(
+            &context,
+            "SELECT t.n FROM (SELECT name AS n FROM stu AS s) AS t",
+        )
+        .unwrap();
+        assert_eq!(
+            plan.to_string(),
+            [
+                "Projection: t.n",
+                "  SubqueryAlias: t",
+                "    Projection: s.name",
+                "      Scan: s [name, age]",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn plan_aggregate() {
+        let context: SimpleMemoryContext = context();
+        let grouped: LogicNode = plan(
+            &context,
+            "SELECT age, count(*) AS c FROM stu GROUP BY age HAVING sum(age) > 1 ORDER BY c",
+        )
+        .unwrap();
+        assert_eq!(
+            grouped.to_string(),
+            [
+                "Projection: stu.age, COUNT(*)",
+                "  Sort: COUNT(*) ASC NULLS LAST",
+                "    Filter: (SUM(stu.age) > 1)",
+                "      Aggregate: group_by=[stu.age] aggregates=[COUNT(*), SUM(stu.age)]",
+                "        Scan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+
+        // 没有 GROUP BY 的聚合函数也需要聚合
+        let global: LogicNode = plan(&context, "SELECT count(name) FROM stu").unwrap();
+        assert_eq!(
+            global.to_string(),
+            [
+                "Projection: COUNT(stu.name)",
+                "  Aggregate: group_by=[] aggregates=[COUNT(stu.name)]",
+                "    Scan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn plan_not_grouped() {
+        let context: SimpleMemoryContext = context();
+        let error: ExecuteError = plan(&context, "SELECT name FROM stu GROUP BY age").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "column stu.name must appear in the GROUP BY clause or be used in an aggregate function"
+        );
+    }
+
+    #[test]
+    fn plan_join() {
+        let context: SimpleMemoryContext = context();
+        let plan: LogicNode = plan(
+            &context,
+            "SELECT a.name FROM stu AS a JOIN stu AS b ON a.age = b.age, stu",
+        )
+        .unwrap();
+        assert_eq!(
+            plan.to_string(),
+            [
+                "Projection: a.name",
+                "  Join: CROSS",
+                "    Join: INNER ON (a.age = b.age)",
+                "      Scan: a [name, age]",
+                "      Scan: b [name, age]",
+                "    Scan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(plan.children()[0].schema().columns.len(), 6);
+    }
+}
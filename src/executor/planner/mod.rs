@@ -0,0 +1,11 @@
+/// 逻辑计划生成，将 AST 转换为逻辑计划
+pub mod logic;
+
+/// 物理计划生成，将逻辑计划转换为可执行的物理计划
+pub mod physical;
+
+/// 代价模型，根据表的统计信息估算计划输出的行数和连接的代价
+pub mod cost;
+
+/// 基于规则的逻辑计划优化
+pub mod optimizer;
@@ -0,0 +1,255 @@
+use crate::executor::{
+    expression::{BoundExpression, ColumnRef},
+    plan::logic::{LogicNode, LogicPlan},
+};
+
+use super::Rule;
+
+/// ColumnPruning 自顶向下计算每个节点需要的输出列：
+/// Scan 只读取需要的列，Projection 只计算需要的表达式，其余节点按新的列下标改写表达式。
+/// 根节点的输出列保持不变
+pub struct ColumnPruning;
+
+impl Rule for ColumnPruning {
+    fn name(&self) -> &'static str {
+        "column_pruning"
+    }
+
+    fn rewrite<'a>(&self, plan: LogicNode<'a>) -> (LogicNode<'a>, bool) {
+        let required: Vec<bool> = vec![true; plan.schema().columns.len()];
+        let mut changed: bool = false;
+        let (plan, _) = prune(plan, &required, &mut changed);
+        (plan, changed)
+    }
+}
+
+/// 裁剪 plan 中 required 为 false 的输出列，返回改写后的计划和原输出列到新输出列的下标映射
+/// 被裁剪的列映射为 None，保留的列保持原有的顺序
+fn prune<'a>(
+    plan: LogicNode<'a>,
+    required: &[bool],
+    changed: &mut bool,
+) -> (LogicNode<'a>, Vec<Option<usize>>) {
+    match plan {
+        LogicNode::OneRow(_) => (plan, vec![]),
+        LogicNode::Scan(mut scan) => {
+            let mut required: Vec<bool> = required.to_vec();
+            if let Some(filter) = &scan.filter {
+                require(&mut required, filter);
+            }
+            let kept: Vec<usize> = kept(&required);
+            let mapping: Vec<Option<usize>> = mapping(required.len(), &kept);
+            if kept.len() < scan.projection.len() {
+                *changed = true;
+                scan.projection = kept.iter().map(|i| scan.projection[*i].clone()).collect();
+                scan.schema.columns = kept
+                    .iter()
+                    .map(|i| scan.schema.columns[*i].clone())
+                    .collect();
+                scan.filter = scan.filter.map(|filter| remap(&filter, &mapping));
+            }
+            (LogicNode::Scan(scan), mapping)
+        }
+        LogicNode::Filter(mut filter) => {
+            let mut required: Vec<bool> = required.to_vec();
+            require(&mut required, &filter.predicate);
+            let (input, mapping) = prune(*filter.input, &required, changed);
+            filter.input = Box::new(input);
+            filter.predicate = remap(&filter.predicate, &mapping);
+            (LogicNode::Filter(filter), mapping)
+        }
+        LogicNode::Projection(mut projection) => {
+            let kept: Vec<usize> = kept(required);
+            let mut input_required: Vec<bool> =
+                vec![false; projection.input.schema().columns.len()];
+            for index in kept.iter() {
+                require(&mut input_required, &projection.expressions[*index]);
+            }
+            let (input, input_mapping) = prune(*projection.input, &input_required, changed);
+            if kept.len() < projection.expressions.len() {
+                *changed = true;
+            }
+            projection.input = Box::new(input);
+            projection.expressions = kept
+                .iter()
+                .map(|i| remap(&projection.expressions[*i], &input_mapping))
+                .collect();
+            projection.schema.columns = kept
+                .iter()
+                .map(|i| projection.schema.columns[*i].clone())
+                .collect();
+            (
+                LogicNode::Projection(projection),
+                mapping(required.len(), &kept),
+            )
+        }
+        // 聚合的输出列不变，只裁剪输入
+        LogicNode::Aggregate(mut aggregate) => {
+            let mut input_required: Vec<bool> = vec![false; aggregate.input.schema().columns.len()];
+            for expression in aggregate.group_by.iter() {
+                require(&mut input_required, expression);
+            }
+            for call in aggregate.aggregates.iter() {
+                if let Some(argument) = &call.argument {
+                    require(&mut input_required, argument);
+                }
+            }
+            let (input, input_mapping) = prune(*aggregate.input, &input_required, changed);
+            aggregate.input = Box::new(input);
+            for expression in aggregate.group_by.iter_mut() {
+                *expression = remap(expression, &input_mapping);
+            }
+            for call in aggregate.aggregates.iter_mut() {
+                if let Some(argument) = call.argument.as_mut() {
+                    **argument = remap(argument, &input_mapping);
+                }
+            }
+            (
+                LogicNode::Aggregate(aggregate),
+                (0..required.len()).map(Some).collect(),
+            )
+        }
+        LogicNode::Sort(mut sort) => {
+            let mut required: Vec<bool> = required.to_vec();
+            for item in sort.order_by.iter() {
+                require(&mut required, &item.expression);
+            }
+            let (input, mapping) = prune(*sort.input, &required, changed);
+            sort.input = Box::new(input);
+            for item in sort.order_by.iter_mut() {
+                item.expression = remap(&item.expression, &mapping);
+            }
+            (LogicNode::Sort(sort), mapping)
+        }
+        LogicNode::Limit(mut limit) => {
+            let (input, mapping) = prune(*limit.input, required, changed);
+            limit.input = Box::new(input);
+            (LogicNode::Limit(limit), mapping)
+        }
+        LogicNode::SubqueryAlias(mut alias) => {
+            let (input, mapping) = prune(*alias.input, required, changed);
+            alias.input = Box::new(input);
+            alias.schema.columns = retain(&alias.schema.columns, &mapping);
+            (LogicNode::SubqueryAlias(alias), mapping)
+        }
+        LogicNode::Join(mut join) => {
+            let width: usize = join.left.schema().columns.len();
+            let mut required: Vec<bool> = required.to_vec();
+            if let Some(condition) = &join.condition {
+                require(&mut required, condition);
+            }
+            let (left, left_mapping) = prune(*join.left, &required[..width], changed);
+            let (right, right_mapping) = prune(*join.right, &required[width..], changed);
+            let offset: usize = left.schema().columns.len();
+            let mapping: Vec<Option<usize>> = left_mapping
+                .into_iter()
+                .chain(right_mapping.into_iter().map(|i| i.map(|i| i + offset)))
+                .collect();
+            join.left = Box::new(left);
+            join.right = Box::new(right);
+            join.condition = join.condition.map(|c| remap(&c, &mapping));
+            join.schema.columns = retain(&join.schema.columns, &mapping);
+            (LogicNode::Join(join), mapping)
+        }
+    }
+}
+
+fn require(required: &mut [bool], expression: &BoundExpression) {
+    let mut columns: Vec<usize> = vec![];
+    expression.collect_columns(&mut columns);
+    for index in columns {
+        required[index] = true;
+    }
+}
+
+/// 保留的列下标，一列都不需要时保留第一列，保证输出的行不为空
+fn kept(required: &[bool]) -> Vec<usize> {
+    let kept: Vec<usize> = (0..required.len()).filter(|i| required[*i]).collect();
+    if kept.is_empty() && !required.is_empty() {
+        return vec![0];
+    }
+    kept
+}
+
+fn mapping(width: usize, kept: &[usize]) -> Vec<Option<usize>> {
+    let mut mapping: Vec<Option<usize>> = vec![None; width];
+    for (new, old) in kept.iter().enumerate() {
+        mapping[*old] = Some(new);
+    }
+    mapping
+}
+
+fn retain<T: Clone>(items: &[T], mapping: &[Option<usize>]) -> Box<[T]> {
+    items
+        .iter()
+        .zip(mapping)
+        .filter(|(_, index)| index.is_some())
+        .map(|(item, _)| item.clone())
+        .collect()
+}
+
+fn remap(expression: &BoundExpression, mapping: &[Option<usize>]) -> BoundExpression {
+    expression.map_columns(&|column| ColumnRef {
+        index: mapping[column.index].expect("required column pruned"),
+        ..column.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColumnPruning;
+    use crate::executor::planner::optimizer::tests::rewrite;
+
+    #[test]
+    fn prune_scan() {
+        assert_eq!(
+            rewrite(
+                ColumnPruning,
+                "SELECT age FROM stu WHERE name = 'x' ORDER BY class"
+            ),
+            [
+                "Projection: stu.age",
+                "  Sort: stu.class ASC NULLS LAST",
+                "    Filter: (stu.name = 'x')",
+                "      Scan: stu [name, age, class]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            rewrite(ColumnPruning, "SELECT COUNT(*) FROM stu"),
+            "Projection: COUNT(*)\n  Aggregate: group_by=[] aggregates=[COUNT(*)]\n    Scan: stu [name]"
+        );
+        assert_eq!(
+            rewrite(
+                ColumnPruning,
+                "SELECT class, MAX(age) FROM stu GROUP BY class"
+            ),
+            [
+                "Projection: stu.class, MAX(stu.age)",
+                "  Aggregate: group_by=[stu.class] aggregates=[MAX(stu.age)]",
+                "    Scan: stu [age, class]",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn prune_subquery_and_join() {
+        assert_eq!(
+            rewrite(
+                ColumnPruning,
+                "SELECT t.a FROM (SELECT name AS n, age + 1 AS a, class FROM stu) AS t \
+                 JOIN class AS c ON t.class = c.id"
+            ),
+            [
+                "Projection: t.a",
+                "  Join: INNER ON (t.class = c.id)",
+                "    SubqueryAlias: t",
+                "      Projection: (stu.age + 1), stu.class",
+                "        Scan: stu [age, class]",
+                "    Scan: c [id]",
+            ]
+            .join("\n")
+        );
+    }
+}
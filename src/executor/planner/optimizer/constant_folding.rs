@@ -0,0 +1,265 @@
+use crate::{
+    executor::{
+        expression::{
+            eval::truth, AggregateCall, BoundBinary, BoundExpression, BoundLiteral, BoundUnary,
+            FunctionCall,
+        },
+        meta::row::SimpleMemoryRow,
+        plan::logic::LogicNode,
+        types::OwnValue,
+    },
+    sql::parser::ast::{expression::BinaryOperator, select::JoinType},
+};
+
+use super::{transform_down, Rule};
+
+/// ConstantFolding 将只包含常量的表达式替换为它的值
+/// FALSE AND x 折叠为 FALSE，TRUE OR x 折叠为 TRUE
+/// 谓词中恒为真的合取项被删除，谓词恒为真的 Filter 被删除
+pub struct ConstantFolding;
+
+impl Rule for ConstantFolding {
+    fn name(&self) -> &'static str {
+        "constant_folding"
+    }
+
+    fn rewrite<'a>(&self, plan: LogicNode<'a>) -> (LogicNode<'a>, bool) {
+        let mut changed: bool = false;
+        let plan: LogicNode<'a> = transform_down(plan, &fold_node, &mut changed);
+        (plan, changed)
+    }
+}
+
+fn fold_node(plan: LogicNode<'_>) -> (LogicNode<'_>, bool) {
+    let mut changed: bool = false;
+    let plan: LogicNode = match plan {
+        LogicNode::Scan(mut scan) => {
+            scan.filter = scan
+                .filter
+                .and_then(|filter| fold_predicate(&filter, &mut changed));
+            LogicNode::Scan(scan)
+        }
+        LogicNode::Filter(mut filter) => match fold_predicate(&filter.predicate, &mut changed) {
+            Some(predicate) => {
+                filter.predicate = predicate;
+                LogicNode::Filter(filter)
+            }
+            None => *filter.input,
+        },
+        LogicNode::Projection(mut projection) => {
+            for expression in projection.expressions.iter_mut() {
+                *expression = fold(expression, &mut changed);
+            }
+            LogicNode::Projection(projection)
+        }
+        LogicNode::Aggregate(mut aggregate) => {
+            for expression in aggregate.group_by.iter_mut() {
+                *expression = fold(expression, &mut changed);
+            }
+            for call in aggregate.aggregates.iter_mut() {
+                if let Some(argument) = call.argument.as_mut() {
+                    **argument = fold(argument, &mut changed);
+                }
+            }
+            LogicNode::Aggregate(aggregate)
+        }
+        LogicNode::Sort(mut sort) => {
+            for item in sort.order_by.iter_mut() {
+                item.expression = fold(&item.expression, &mut changed);
+            }
+            LogicNode::Sort(sort)
+        }
+        LogicNode::Join(mut join) => {
+            join.condition = join
+                .condition
+                .and_then(|condition| fold_predicate(&condition, &mut changed));
+            if join.condition.is_none() && join.join_type == JoinType::Inner {
+                join.join_type = JoinType::Cross;
+            }
+            LogicNode::Join(join)
+        }
+        LogicNode::OneRow(_) | LogicNode::Limit(_) | LogicNode::SubqueryAlias(_) => plan,
+    };
+    (plan, changed)
+}
+
+/// 折叠谓词并删除恒为真的合取项，整个谓词恒为真时返回 None
+fn fold_predicate(predicate: &BoundExpression, changed: &mut bool) -> Option<BoundExpression> {
+    let predicate: BoundExpression = fold(predicate, changed);
+    let conjuncts: Vec<BoundExpression> = predicate.clone().split_conjunction();
+    if !conjuncts
+        .iter()
+        .any(|c| literal_truth(c) == Some(Some(true)))
+    {
+        return Some(predicate);
+    }
+    *changed = true;
+    BoundExpression::conjunction(
+        conjuncts
+            .into_iter()
+            .filter(|c| literal_truth(c) != Some(Some(true)))
+            .collect(),
+    )
+}
+
+/// 自底向上折叠表达式，求值出错的常量表达式保持不变，留到执行时报错
+fn fold(expression: &BoundExpression, changed: &mut bool) -> BoundExpression {
+    match expression {
+        BoundExpression::Literal(_) | BoundExpression::Column(_) => expression.clone(),
+        BoundExpression::Binary(binary) => {
+            let left: BoundExpression = fold(&binary.left, changed);
+            let right: BoundExpression = fold(&binary.right, changed);
+            let absorbing: Option<bool> = match binary.operator {
+                BinaryOperator::AND(_) => Some(false),
+                BinaryOperator::OR(_) => Some(true),
+                _ => None,
+            };
+            if let Some(value) = absorbing {
+                if literal_truth(&left) == Some(Some(value))
+                    || literal_truth(&right) == Some(Some(value))
+                {
+                    *changed = true;
+                    return BoundExpression::Literal(BoundLiteral {
+                        value: OwnValue::Boolean(value),
+                        data_type: binary.data_type.clone(),
+                    });
+                }
+            }
+            evaluate(
+                BoundExpression::Binary(BoundBinary {
+                    left: Box::new(left),
+                    operator: binary.operator.clone(),
+                    right: Box::new(right),
+                    data_type: binary.data_type.clone(),
+                }),
+                changed,
+            )
+        }
+        BoundExpression::Unary(unary) => evaluate(
+            BoundExpression::Unary(BoundUnary {
+                operator: unary.operator.clone(),
+                expression: Box::new(fold(&unary.expression, changed)),
+                data_type: unary.data_type.clone(),
+            }),
+            changed,
+        ),
+        BoundExpression::Aggregate(call) => BoundExpression::Aggregate(AggregateCall {
+            function: call.function,
+            argument: call
+                .argument
+                .as_ref()
+                .map(|argument| Box::new(fold(argument, changed))),
+            data_type: call.data_type.clone(),
+        }),
+        BoundExpression::Function(call) => evaluate(
+            BoundExpression::Function(FunctionCall {
+                function: call.function,
+                arguments: call
+                    .arguments
+                    .iter()
+                    .map(|argument| fold(argument, changed))
+                    .collect(),
+                data_type: call.data_type.clone(),
+            }),
+            changed,
+        ),
+    }
+}
+
+/// 操作数都是常量时计算表达式的值
+fn evaluate(expression: BoundExpression, changed: &mut bool) -> BoundExpression {
+    let constant: bool = match &expression {
+        BoundExpression::Binary(binary) => is_literal(&binary.left) && is_literal(&binary.right),
+        BoundExpression::Unary(unary) => is_literal(&unary.expression),
+        BoundExpression::Function(call) => call.arguments.iter().all(is_literal),
+        _ => false,
+    };
+    if !constant {
+        return expression;
+    }
+    match expression.evaluate(&SimpleMemoryRow::new(vec![])) {
+        Ok(value) => {
+            *changed = true;
+            BoundExpression::Literal(BoundLiteral {
+                value,
+                data_type: expression.data_type().clone(),
+            })
+        }
+        Err(_) => expression,
+    }
+}
+
+fn is_literal(expression: &BoundExpression) -> bool {
+    matches!(expression, BoundExpression::Literal(_))
+}
+
+/// 常量的真值，不是常量时返回 None
+fn literal_truth(expression: &BoundExpression) -> Option<Option<bool>> {
+    match expression {
+        BoundExpression::Literal(literal) => Some(truth(&literal.value)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstantFolding;
+    use crate::executor::planner::optimizer::tests::rewrite;
+
+    #[test]
+    fn fold_constants() {
+        assert_eq!(
+            rewrite(
+                ConstantFolding,
+                "SELECT age + (1 + 2) * 3, -(4 - 5) FROM stu"
+            ),
+            "Projection: (stu.age + 9), 1\n  Scan: stu [name, age, class]"
+        );
+        assert_eq!(
+            rewrite(
+                ConstantFolding,
+                "SELECT name FROM stu WHERE age > 2 * 10 AND 1 < 2 AND (name = 'a' OR 1 = 1)"
+            ),
+            "Projection: stu.name\n  Filter: (stu.age > 20)\n    Scan: stu [name, age, class]"
+        );
+        assert_eq!(
+            rewrite(ConstantFolding, "SELECT name FROM stu WHERE 1 = 1"),
+            "Projection: stu.name\n  Scan: stu [name, age, class]"
+        );
+        // FALSE AND x 恒为 FALSE，谓词保留
+        assert_eq!(
+            rewrite(
+                ConstantFolding,
+                "SELECT name FROM stu WHERE age > 1 AND 1 > 2"
+            ),
+            "Projection: stu.name\n  Filter: FALSE\n    Scan: stu [name, age, class]"
+        );
+        assert_eq!(
+            rewrite(
+                ConstantFolding,
+                "SELECT COALESCE(NULL, 2) FROM stu WHERE NULL IS NULL AND age IS NOT NULL"
+            ),
+            "Projection: 2\n  Filter: (stu.age IS NOT NULL)\n    Scan: stu [name, age, class]"
+        );
+        // 求值出错的常量表达式保持不变
+        assert_eq!(
+            rewrite(ConstantFolding, "SELECT 1 / 0 FROM stu"),
+            "Projection: (1 / 0)\n  Scan: stu [name, age, class]"
+        );
+        assert_eq!(
+            rewrite(
+                ConstantFolding,
+                "SELECT s.name FROM stu AS s JOIN class AS c ON 2 > 1 GROUP BY s.name ORDER BY s.name"
+            ),
+            [
+                "Projection: s.name",
+                "  Sort: s.name ASC NULLS LAST",
+                "    Aggregate: group_by=[s.name] aggregates=[]",
+                "      Join: CROSS",
+                "        Scan: s [name, age, class]",
+                "        Scan: c [id, title, grade]",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::executor::plan::logic::LogicNode;
+
+use super::{transform_down, Rule};
+
+/// EliminateSortLimit 删除不影响结果的排序和分页：
+/// 不限制行数的 Limit，最多输出一行的输入上的 Sort 和 Limit，
+/// 聚合、连接和外层排序会打乱顺序，它们输入中的 Sort 也被删除。
+/// 两个 Limit 之间只有 Projection 和 SubqueryAlias 时合并为一个
+pub struct EliminateSortLimit;
+
+impl Rule for EliminateSortLimit {
+    fn name(&self) -> &'static str {
+        "eliminate_sort_limit"
+    }
+
+    fn rewrite<'a>(&self, plan: LogicNode<'a>) -> (LogicNode<'a>, bool) {
+        let mut changed: bool = false;
+        let plan: LogicNode<'a> = transform_down(plan, &eliminate, &mut changed);
+        (plan, changed)
+    }
+}
+
+fn eliminate(plan: LogicNode<'_>) -> (LogicNode<'_>, bool) {
+    match plan {
+        LogicNode::Limit(limit) if limit.limit.is_none() && limit.offset == 0 => {
+            (*limit.input, true)
+        }
+        LogicNode::Limit(limit) if limit_below(&limit.input) => {
+            (merge_limit(*limit.input, limit.limit, limit.offset), true)
+        }
+        LogicNode::Limit(limit)
+            if limit.offset == 0 && limit.limit != Some(0) && at_most_one_row(&limit.input) =>
+        {
+            (*limit.input, true)
+        }
+        LogicNode::Sort(sort) if at_most_one_row(&sort.input) => (*sort.input, true),
+        LogicNode::Sort(mut sort) => {
+            let (input, changed) = strip_sort(*sort.input);
+            sort.input = Box::new(input);
+            (LogicNode::Sort(sort), changed)
+        }
+        LogicNode::Aggregate(mut aggregate) => {
+            let (input, changed) = strip_sort(*aggregate.input);
+            aggregate.input = Box::new(input);
+            (LogicNode::Aggregate(aggregate), changed)
+        }
+        LogicNode::Join(mut join) => {
+            let (left, left_changed) = strip_sort(*join.left);
+            let (right, right_changed) = strip_sort(*join.right);
+            join.left = Box::new(left);
+            join.right = Box::new(right);
+            (LogicNode::Join(join), left_changed || right_changed)
+        }
+        _ => (plan, false),
+    }
+}
+
+/// plan 中是否有可以和之上的 Limit 合并的 Limit，中间只能有不改变行数的 Projection 和 SubqueryAlias
+fn limit_below(plan: &LogicNode) -> bool {
+    match plan {
+        LogicNode::Limit(_) => true,
+        LogicNode::Projection(projection) => limit_below(&projection.input),
+        LogicNode::SubqueryAlias(alias) => limit_below(&alias.input),
+        _ => false,
+    }
+}
+
+/// 将 plan 之上的 Limit 合并到 limit_below 找到的 Limit 中
+fn merge_limit(plan: LogicNode<'_>, rows: Option<u64>, offset: u64) -> LogicNode<'_> {
+    match plan {
+        LogicNode::Limit(mut inner) => {
+            // 外层在内层的输出上再跳过 offset 行
+            inner.limit = match (inner.limit, rows) {
+                (Some(inner), Some(rows)) => Some(inner.saturating_sub(offset).min(rows)),
+                (Some(inner), None) => Some(inner.saturating_sub(offset)),
+                (None, rows) => rows,
+            };
+            inner.offset = inner.offset.saturating_add(offset);
+            LogicNode::Limit(inner)
+        }
+        _ => plan.map_children(&mut |input| merge_limit(input, rows, offset)),
+    }
+}
+
+/// 删除输出顺序无关紧要的 Sort，穿过不改变行顺序的 Filter、Projection 和 SubqueryAlias
+fn strip_sort(plan: LogicNode<'_>) -> (LogicNode<'_>, bool) {
+    match plan {
+        LogicNode::Sort(sort) => (strip_sort(*sort.input).0, true),
+        LogicNode::Filter(mut filter) => {
+            let (input, changed) = strip_sort(*filter.input);
+            filter.input = Box::new(input);
+            (LogicNode::Filter(filter), changed)
+        }
+        LogicNode::Projection(mut projection) => {
+            let (input, changed) = strip_sort(*projection.input);
+            projection.input = Box::new(input);
+            (LogicNode::Projection(projection), changed)
+        }
+        LogicNode::SubqueryAlias(mut alias) => {
+            let (input, changed) = strip_sort(*alias.input);
+            alias.input = Box::new(input);
+            (LogicNode::SubqueryAlias(alias), changed)
+        }
+        _ => (plan, false),
+    }
+}
+
+/// 计划是否最多输出一行
+fn at_most_one_row(plan: &LogicNode) -> bool {
+    match plan {
+        LogicNode::OneRow(_) => true,
+        LogicNode::Aggregate(aggregate) => aggregate.group_by.is_empty(),
+        LogicNode::Limit(limit) => {
+            limit.limit.is_some_and(|rows| rows <= 1) || at_most_one_row(&limit.input)
+        }
+        LogicNode::Filter(filter) => at_most_one_row(&filter.input),
+        LogicNode::Projection(projection) => at_most_one_row(&projection.input),
+        LogicNode::Sort(sort) => at_most_one_row(&sort.input),
+        LogicNode::SubqueryAlias(alias) => at_most_one_row(&alias.input),
+        LogicNode::Scan(_) | LogicNode::Join(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EliminateSortLimit;
+    use crate::executor::planner::optimizer::tests::rewrite;
+
+    #[test]
+    fn eliminate_limit() {
+        assert_eq!(
+            rewrite(EliminateSortLimit, "SELECT name FROM stu OFFSET 0"),
+            "Projection: stu.name\n  Scan: stu [name, age, class]"
+        );
+        assert_eq!(
+            rewrite(
+                EliminateSortLimit,
+                "SELECT n FROM (SELECT name AS n FROM stu LIMIT 10 OFFSET 2) AS t LIMIT 5 OFFSET 7"
+            ),
+            [
+                "Projection: t.n",
+                "  SubqueryAlias: t",
+                "    Limit: limit=3 offset=9",
+                "      Projection: stu.name",
+                "        Scan: stu [name, age, class]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            rewrite(EliminateSortLimit, "SELECT COUNT(*) AS c FROM stu ORDER BY c LIMIT 3"),
+            "Projection: COUNT(*)\n  Aggregate: group_by=[] aggregates=[COUNT(*)]\n    Scan: stu [name, age, class]"
+        );
+    }
+
+    #[test]
+    fn eliminate_sort() {
+        assert_eq!(
+            rewrite(
+                EliminateSortLimit,
+                "SELECT n, COUNT(*) FROM (SELECT name AS n FROM stu ORDER BY age) AS t GROUP BY n ORDER BY n"
+            ),
+            [
+                "Projection: t.n, COUNT(*)",
+                "  Sort: t.n ASC NULLS LAST",
+                "    Aggregate: group_by=[t.n] aggregates=[COUNT(*)]",
+                "      SubqueryAlias: t",
+                "        Projection: stu.name",
+                "          Scan: stu [name, age, class]",
+            ]
+            .join("\n")
+        );
+        // 带 LIMIT 的子查询中的排序决定了输出哪些行，不能删除
+        assert_eq!(
+            rewrite(
+                EliminateSortLimit,
+                "SELECT t.n FROM (SELECT name AS n FROM stu ORDER BY age LIMIT 2) AS t ORDER BY t.n"
+            ),
+            [
+                "Projection: t.n",
+                "  Sort: t.n ASC NULLS LAST",
+                "    SubqueryAlias: t",
+                "      Limit: limit=2 offset=0",
+                "        Projection: stu.name",
+                "          Sort: stu.age ASC NULLS LAST",
+                "            Scan: stu [name, age, class]",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::executor::plan::logic::LogicNode;
+
+use column_pruning::ColumnPruning;
+use constant_folding::ConstantFolding;
+use eliminate_sort_limit::EliminateSortLimit;
+use predicate_pushdown::PredicatePushdown;
+
+/// 常量折叠，计算只包含常量的表达式
+pub mod constant_folding;
+
+/// 谓词下推，将过滤条件推到扫描和连接的输入中
+pub mod predicate_pushdown;
+
+/// 列裁剪，扫描只读取计划用到的列
+pub mod column_pruning;
+
+/// 删除不影响结果的排序和分页
+pub mod eliminate_sort_limit;
+
+/// Rule 逻辑计划的改写规则，改写前后的计划输出相同的结果
+pub trait Rule {
+    fn name(&self) -> &'static str;
+
+    /// 改写以 plan 为根的计划树，返回改写后的计划和是否发生了改写
+    fn rewrite<'a>(&self, plan: LogicNode<'a>) -> (LogicNode<'a>, bool);
+}
+
+/// Optimizer 依次应用各条规则，直到没有规则发生改写或者达到最大轮数
+pub struct Optimizer {
+    pub rules: Vec<Box<dyn Rule>>,
+    pub max_iterations: usize,
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Optimizer {
+    pub fn new() -> Self {
+        Self::with_rules(vec![
+            Box::new(ConstantFolding),
+            Box::new(EliminateSortLimit),
+            Box::new(PredicatePushdown),
+            Box::new(ColumnPruning),
+        ])
+    }
+
+    pub fn with_rules(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self {
+            rules,
+            max_iterations: 16,
+        }
+    }
+
+    pub fn optimize<'a>(&self, mut plan: LogicNode<'a>) -> LogicNode<'a> {
+        for _ in 0..self.max_iterations {
+            let mut changed: bool = false;
+            for rule in self.rules.iter() {
+                let rewritten: bool;
+                (plan, rewritten) = rule.rewrite(plan);
+                changed |= rewritten;
+            }
+            if !changed {
+                break;
+            }
+        }
+        plan
+    }
+}
+
+/// 自顶向下改写计划树：先对节点调用 f，再改写 f 返回的节点的各个子节点
+/// f 返回改写后的节点和是否发生了改写
+pub fn transform_down<'a>(
+    plan: LogicNode<'a>,
+    f: &dyn Fn(LogicNode<'a>) -> (LogicNode<'a>, bool),
+    changed: &mut bool,
+) -> LogicNode<'a> {
+    let (plan, rewritten) = f(plan);
+    *changed |= rewritten;
+    plan.map_children(&mut |child| transform_down(child, f, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::{
+            context::SimpleMemoryContext,
+            meta::{column::Column, data_source::SimpleMemoryDataSource, schema::Schema},
+            plan::logic::LogicPlan,
+            planner::logic::LogicPlanner,
+            types::DataType,
+        },
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn context() -> SimpleMemoryContext {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        let tables = [
+            (
+                "stu",
+                [
+                    ("name", DataType::String),
+                    ("age", DataType::Int32),
+                    ("class", DataType::Int32),
+                ],
+            ),
+            (
+                "class",
+                [
+                    ("id", DataType::Int32),
+                    ("title", DataType::String),
+                    ("grade", DataType::String),
+                ],
+            ),
+        ];
+        for (name, columns) in tables {
+            let schema: Schema = Schema {
+                name: name.into(),
+                columns: columns
+                    .into_iter()
+                    .map(|(name, data_type)| Column {
+                        name: name.into(),
+                        data_type,
+                        nullable: true,
+                        default: None,
+                    })
+                    .collect(),
+            };
+            context.add_table(Box::new(SimpleMemoryDataSource::new(schema)));
+        }
+        context
+    }
+
+    /// 用 optimizer 优化 sql 的逻辑计划
+    pub(super) fn optimize(optimizer: &Optimizer, sql: &str) -> String {
+        let context: SimpleMemoryContext = context();
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        let Statement::Select(select) = &statements.statements[0] else {
+            panic!("expect select");
+        };
+        let plan: LogicNode = LogicPlanner::new(&context, sql)
+            .plan_select(select)
+            .unwrap();
+        let width: usize = plan.schema().columns.len();
+        let plan: LogicNode = optimizer.optimize(plan);
+        assert_eq!(plan.schema().columns.len(), width);
+        plan.to_string()
+    }
+
+    /// 只应用一条规则
+    pub(super) fn rewrite(rule: impl Rule + 'static, sql: &str) -> String {
+        optimize(&Optimizer::with_rules(vec![Box::new(rule)]), sql)
+    }
+
+    #[test]
+    fn optimize_all_rules() {
+        let optimizer: Optimizer = Optimizer::new();
+        assert_eq!(
+            optimize(
+                &optimizer,
+                "SELECT name FROM stu WHERE age > 10 + 8 AND 1 = 1"
+            ),
+            "Projection: stu.name\n  Scan: stu [name, age] filter=(stu.age > 18)"
+        );
+        assert_eq!(
+            optimize(
+                &optimizer,
+                "SELECT s.name, c.title FROM stu AS s, class AS c \
+                 WHERE s.class = c.id AND c.grade = 'one' AND s.age > 2 * 9 \
+                 ORDER BY s.name OFFSET 0",
+            ),
+            [
+                "Projection: s.name, c.title",
+                "  Sort: s.name ASC NULLS LAST",
+                "    Join: INNER ON (s.class = c.id)",
+                "      Scan: s [name, age, class] filter=(s.age > 18)",
+                "      Scan: c [id, title, grade] filter=(c.grade = 'one')",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            optimize(
+                &optimizer,
+                "SELECT t.n FROM (SELECT name AS n, age FROM stu ORDER BY age) AS t \
+                 WHERE t.age < 20 LIMIT 5 OFFSET 1",
+            ),
+            [
+                "Limit: limit=5 offset=1",
+                "  Projection: t.n",
+                "    SubqueryAlias: t",
+                "      Projection: stu.name",
+                "        Sort: stu.age ASC NULLS LAST",
+                "          Scan: stu [name, age] filter=(stu.age < 20)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn optimize_fixpoint() {
+        // 谓词下推后才出现可以折叠的常量，需要再执行一轮常量折叠
+        let mut optimizer: Optimizer = Optimizer::new();
+        let sql: &str = "SELECT * FROM (SELECT 1 + 1 AS two, name FROM stu) AS t WHERE two = 2";
+        assert_eq!(
+            optimize(&optimizer, sql),
+            [
+                "Projection: t.two, t.name",
+                "  SubqueryAlias: t",
+                "    Projection: 2, stu.name",
+                "      Scan: stu [name]",
+            ]
+            .join("\n")
+        );
+        optimizer.max_iterations = 1;
+        assert_eq!(
+            optimize(&optimizer, sql),
+            [
+                "Projection: t.two, t.name",
+                "  SubqueryAlias: t",
+                "    Projection: 2, stu.name",
+                "      Scan: stu [name] filter=(2 = 2)",
+            ]
+            .join("\n")
+        );
+        optimizer.max_iterations = 0;
+        assert!(optimize(&optimizer, sql).contains("Filter: (t.two = 2)"));
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    executor::{
+        expression::{BoundExpression, ColumnRef},
+        plan::logic::{filter::Filter, join::Join, LogicNode, LogicPlan},
+    },
+    sql::parser::ast::select::JoinType,
+};
+
+use super::{transform_down, Rule};
+
+/// PredicatePushdown 将 Filter 的合取项尽量向下推：
+/// 合并相邻的 Filter，穿过 Sort、SubqueryAlias、Projection 和只引用分组列的 Aggregate，
+/// 推入 Scan 的 filter，推到连接的输入中或者并入内连接的连接条件。
+/// 外连接中会补 NULL 的一侧不能接收 WHERE 中的谓词，保留所有行的一侧不能接收 ON 中的谓词
+pub struct PredicatePushdown;
+
+impl Rule for PredicatePushdown {
+    fn name(&self) -> &'static str {
+        "predicate_pushdown"
+    }
+
+    fn rewrite<'a>(&self, plan: LogicNode<'a>) -> (LogicNode<'a>, bool) {
+        let mut changed: bool = false;
+        let plan: LogicNode<'a> = transform_down(plan, &push_down, &mut changed);
+        (plan, changed)
+    }
+}
+
+fn push_down(plan: LogicNode<'_>) -> (LogicNode<'_>, bool) {
+    match plan {
+        LogicNode::Filter(filter) => push_filter(*filter.input, filter.predicate),
+        LogicNode::Join(join) => push_join_condition(join),
+        _ => (plan, false),
+    }
+}
+
+/// 将 input 之上的谓词 predicate 推到 input 中
+fn push_filter(input: LogicNode<'_>, predicate: BoundExpression) -> (LogicNode<'_>, bool) {
+    match input {
+        LogicNode::Filter(filter) => {
+            let mut conjuncts: Vec<BoundExpression> = filter.predicate.split_conjunction();
+            conjuncts.extend(predicate.split_conjunction());
+            (filtered(*filter.input, conjuncts), true)
+        }
+        LogicNode::Scan(mut scan) => {
+            let mut conjuncts: Vec<BoundExpression> = match scan.filter.take() {
+                Some(filter) => filter.split_conjunction(),
+                None => vec![],
+            };
+            conjuncts.extend(predicate.split_conjunction());
+            scan.filter = BoundExpression::conjunction(conjuncts);
+            (LogicNode::Scan(scan), true)
+        }
+        LogicNode::Sort(mut sort) => {
+            sort.input = Box::new(LogicNode::Filter(Filter::new(*sort.input, predicate)));
+            (LogicNode::Sort(sort), true)
+        }
+        // 子查询别名不改变行的布局，列下标不变
+        LogicNode::SubqueryAlias(mut alias) => {
+            alias.input = Box::new(LogicNode::Filter(Filter::new(*alias.input, predicate)));
+            (LogicNode::SubqueryAlias(alias), true)
+        }
+        // 将谓词中的列替换为投影中对应的表达式
+        LogicNode::Projection(mut projection) => {
+            let predicate: BoundExpression =
+                predicate.replace_columns(&|column| projection.expressions[column.index].clone());
+            projection.input =
+                Box::new(LogicNode::Filter(Filter::new(*projection.input, predicate)));
+            (LogicNode::Projection(projection), true)
+        }
+        // 只引用分组列的谓词可以在分组之前过滤，替换为分组表达式
+        LogicNode::Aggregate(mut aggregate) => {
+            let groups: usize = aggregate.group_by.len();
+            let (below, above): (Vec<BoundExpression>, Vec<BoundExpression>) = predicate
+                .clone()
+                .split_conjunction()
+                .into_iter()
+                .partition(|conjunct| {
+                    let columns: Vec<usize> = columns(conjunct);
+                    !columns.is_empty() && columns.iter().all(|index| *index < groups)
+                });
+            if below.is_empty() {
+                return (
+                    LogicNode::Filter(Filter::new(LogicNode::Aggregate(aggregate), predicate)),
+                    false,
+                );
+            }
+            let below: Vec<BoundExpression> = below
+                .iter()
+                .map(|conjunct| {
+                    conjunct.replace_columns(&|column| aggregate.group_by[column.index].clone())
+                })
+                .collect();
+            aggregate.input = Box::new(filtered(*aggregate.input, below));
+            (filtered(LogicNode::Aggregate(aggregate), above), true)
+        }
+        LogicNode::Join(mut join) => {
+            let width: usize = join.left.schema().columns.len();
+            let join_type: JoinType = join.join_type;
+            let mut left: Vec<BoundExpression> = vec![];
+            let mut right: Vec<BoundExpression> = vec![];
+            let mut on: Vec<BoundExpression> = vec![];
+            let mut above: Vec<BoundExpression> = vec![];
+            for conjunct in predicate.clone().split_conjunction() {
+                let columns: Vec<usize> = columns(&conjunct);
+                if columns.is_empty() {
+                    above.push(conjunct);
+                } else if columns.iter().all(|index| *index < width) && !join_type.keeps_right() {
+                    left.push(conjunct);
+                } else if columns.iter().all(|index| *index >= width) && !join_type.keeps_left() {
+                    right.push(shift(&conjunct, width));
+                } else if matches!(join_type, JoinType::Inner | JoinType::Cross) {
+                    on.push(conjunct);
+                } else {
+                    above.push(conjunct);
+                }
+            }
+            if left.is_empty() && right.is_empty() && on.is_empty() {
+                return (
+                    LogicNode::Filter(Filter::new(LogicNode::Join(join), predicate)),
+                    false,
+                );
+            }
+            join.left = Box::new(filtered(*join.left, left));
+            join.right = Box::new(filtered(*join.right, right));
+            if !on.is_empty() {
+                let mut conjuncts: Vec<BoundExpression> = match join.condition.take() {
+                    Some(condition) => condition.split_conjunction(),
+                    None => vec![],
+                };
+                conjuncts.extend(on);
+                join.condition = BoundExpression::conjunction(conjuncts);
+                join.join_type = JoinType::Inner;
+            }
+            (filtered(LogicNode::Join(join), above), true)
+        }
+        // Limit 之上的谓词不能下推，OneRow 没有可以下推的位置
+        LogicNode::Limit(_) | LogicNode::OneRow(_) => {
+            (LogicNode::Filter(Filter::new(input, predicate)), false)
+        }
+    }
+}
+
+/// 将连接条件中只引用一侧的合取项推到该侧的输入中
+fn push_join_condition(mut join: Join<'_>) -> (LogicNode<'_>, bool) {
+    let Some(condition) = join.condition.take() else {
+        return (LogicNode::Join(join), false);
+    };
+    let width: usize = join.left.schema().columns.len();
+    let mut left: Vec<BoundExpression> = vec![];
+    let mut right: Vec<BoundExpression> = vec![];
+    let mut remain: Vec<BoundExpression> = vec![];
+    for conjunct in condition.clone().split_conjunction() {
+        let columns: Vec<usize> = columns(&conjunct);
+        if columns.is_empty() {
+            remain.push(conjunct);
+        } else if columns.iter().all(|index| *index < width) && !join.join_type.keeps_left() {
+            left.push(conjunct);
+        } else if columns.iter().all(|index| *index >= width) && !join.join_type.keeps_right() {
+            right.push(shift(&conjunct, width));
+        } else {
+            remain.push(conjunct);
+        }
+    }
+    if left.is_empty() && right.is_empty() {
+        join.condition = Some(condition);
+        return (LogicNode::Join(join), false);
+    }
+    join.left = Box::new(filtered(*join.left, left));
+    join.right = Box::new(filtered(*join.right, right));
+    join.condition = BoundExpression::conjunction(remain);
+    if join.condition.is_none() && join.join_type == JoinType::Inner {
+        join.join_type = JoinType::Cross;
+    }
+    (LogicNode::Join(join), true)
+}
+
+/// 在 input 之上加一个 Filter，没有合取项时直接返回 input
+fn filtered(input: LogicNode<'_>, conjuncts: Vec<BoundExpression>) -> LogicNode<'_> {
+    match BoundExpression::conjunction(conjuncts) {
+        Some(predicate) => LogicNode::Filter(Filter::new(input, predicate)),
+        None => input,
+    }
+}
+
+fn columns(expression: &BoundExpression) -> Vec<usize> {
+    let mut columns: Vec<usize> = vec![];
+    expression.collect_columns(&mut columns);
+    columns
+}
+
+/// 连接右侧的列下标减去左侧的宽度，改为指向右侧输入的行
+fn shift(expression: &BoundExpression, width: usize) -> BoundExpression {
+    expression.map_columns(&|column| ColumnRef {
+        index: column.index - width,
+        ..column.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PredicatePushdown;
+    use crate::executor::planner::optimizer::tests::rewrite;
+
+    #[test]
+    fn push_into_scan() {
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT n FROM (SELECT name AS n, age + 1 AS a FROM stu WHERE age > 1 ORDER BY age) AS t \
+                 WHERE a < 20 AND n = 'x'"
+            ),
+            [
+                "Projection: t.n",
+                "  SubqueryAlias: t",
+                "    Projection: stu.name, (stu.age + 1)",
+                "      Sort: stu.age ASC NULLS LAST",
+                "        Scan: stu [name, age, class] filter=(((stu.age > 1) AND ((stu.age + 1) < 20)) AND (stu.name = 'x'))",
+            ]
+            .join("\n")
+        );
+        // Limit 之上的谓词不能下推
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT n FROM (SELECT name AS n FROM stu LIMIT 3) AS t WHERE n = 'x'"
+            ),
+            [
+                "Projection: t.n",
+                "  SubqueryAlias: t",
+                "    Filter: (t.n = 'x')",
+                "      Limit: limit=3 offset=0",
+                "        Projection: stu.name",
+                "          Scan: stu [name, age, class]",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn push_below_aggregate() {
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT class, COUNT(*) FROM stu GROUP BY class HAVING class > 1 AND COUNT(*) > 2"
+            ),
+            [
+                "Projection: stu.class, COUNT(*)",
+                "  Filter: (COUNT(*) > 2)",
+                "    Aggregate: group_by=[stu.class] aggregates=[COUNT(*)]",
+                "      Scan: stu [name, age, class] filter=(stu.class > 1)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn push_below_join() {
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT s.name FROM stu AS s, class AS c WHERE s.class = c.id AND s.age > 1 AND c.grade = 'one'"
+            ),
+            [
+                "Projection: s.name",
+                "  Join: INNER ON (s.class = c.id)",
+                "    Scan: s [name, age, class] filter=(s.age > 1)",
+                "    Scan: c [id, title, grade] filter=(c.grade = 'one')",
+            ]
+            .join("\n")
+        );
+        // LEFT JOIN 的右侧会补 NULL，WHERE 中只引用右侧的谓词不能下推；ON 中只引用左侧的谓词也不能下推
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT s.name FROM stu AS s LEFT JOIN class AS c ON s.class = c.id AND c.grade = 'one' AND s.age > 1 \
+                 WHERE s.age < 20 AND c.title = 'x'"
+            ),
+            [
+                "Projection: s.name",
+                "  Filter: (c.title = 'x')",
+                "    Join: LEFT ON ((s.class = c.id) AND (s.age > 1))",
+                "      Scan: s [name, age, class] filter=(s.age < 20)",
+                "      Scan: c [id, title, grade] filter=(c.grade = 'one')",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            rewrite(
+                PredicatePushdown,
+                "SELECT s.name FROM stu AS s FULL JOIN class AS c ON s.class = c.id WHERE s.age < 20"
+            ),
+            [
+                "Projection: s.name",
+                "  Filter: (s.age < 20)",
+                "    Join: FULL ON (s.class = c.id)",
+                "      Scan: s [name, age, class]",
+                "      Scan: c [id, title, grade]",
+            ]
+            .join("\n")
+        );
+    }
+}
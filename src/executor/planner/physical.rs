@@ -0,0 +1,840 @@
+use crate::{
+    error::ErrorCode,
+    executor::{
+        error::ExecuteError,
+        expression::{BoundExpression, ColumnRef},
+        meta::schema::Schema,
+        plan::{
+            logic::{join::Join, projection::Projection, scan::Scan, LogicNode, LogicPlan},
+            physical::{
+                self, analyze::Analyze, hash_join::HashJoin, nested_loop_join::NestedLoopJoin,
+                scan::SeqScan, sort_merge_join::SortMergeJoin, PhysicalPlan,
+            },
+        },
+    },
+    sql::parser::ast::{expression::BinaryOperator, select::JoinType},
+};
+
+use super::cost::{self, ColumnEstimate, Estimate};
+
+/// 排序默认可以使用的内存，64 MiB
+pub const DEFAULT_SORT_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// 连接算法
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JoinAlgorithm {
+    NestedLoop,
+    Hash,
+    SortMerge,
+}
+
+/// PhysicalPlanner 将逻辑计划转换为物理计划
+#[derive(Clone)]
+pub struct PhysicalPlanner {
+    /// 排序可以使用的内存，超过后溢写到临时文件
+    pub sort_memory_budget: usize,
+
+    /// 指定连接算法，None 时按代价模型选择。没有等值连接键的连接总是使用嵌套循环连接
+    pub join_algorithm: Option<JoinAlgorithm>,
+
+    /// 用 Analyze 包装每个算子，统计执行情况
+    pub analyze: bool,
+}
+
+impl Default for PhysicalPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicalPlanner {
+    pub fn new() -> Self {
+        Self {
+            sort_memory_budget: DEFAULT_SORT_MEMORY_BUDGET,
+            join_algorithm: None,
+            analyze: false,
+        }
+    }
+
+    pub fn with_sort_memory_budget(sort_memory_budget: usize) -> Self {
+        Self {
+            sort_memory_budget,
+            join_algorithm: None,
+            analyze: false,
+        }
+    }
+
+    pub fn with_join_algorithm(join_algorithm: JoinAlgorithm) -> Self {
+        Self {
+            join_algorithm: Some(join_algorithm),
+            ..Self::new()
+        }
+    }
+
+    pub fn build<'a>(
+        &self,
+        plan: &LogicNode<'a>,
+    ) -> Result<Box<dyn PhysicalPlan<'a> + 'a>, ExecuteError> {
+        let physical: Box<dyn PhysicalPlan<'a> + 'a> = self.build_node(plan)?;
+        match plan {
+            // 子查询别名直接使用输入的算子，连接在构造时已经包装了每个算子
+            LogicNode::SubqueryAlias(_) | LogicNode::Join(_) => Ok(physical),
+            _ => Ok(self.instrument(physical)),
+        }
+    }
+
+    /// 需要统计执行情况时用 Analyze 包装算子
+    fn instrument<'a>(
+        &self,
+        plan: Box<dyn PhysicalPlan<'a> + 'a>,
+    ) -> Box<dyn PhysicalPlan<'a> + 'a> {
+        match self.analyze {
+            true => Box::new(Analyze::new(plan)),
+            false => plan,
+        }
+    }
+
+    fn build_node<'a>(
+        &self,
+        plan: &LogicNode<'a>,
+    ) -> Result<Box<dyn PhysicalPlan<'a> + 'a>, ExecuteError> {
+        match plan {
+            LogicNode::Scan(scan) => self.build_scan(scan),
+            LogicNode::OneRow(one_row) => Ok(Box::new(physical::one_row::OneRow::new(
+                one_row.schema.clone(),
+            ))),
+            LogicNode::Filter(filter) => Ok(Box::new(physical::filter::Filter::new(
+                self.build(&filter.input)?,
+                filter.predicate.clone(),
+            ))),
+            LogicNode::Projection(projection) => match projection.input.as_ref() {
+                // 只包含列引用的投影直接合并到表扫描中
+                LogicNode::Scan(scan) => match self.merge_projection(scan, projection)? {
+                    Some(merged) => Ok(merged),
+                    None => Ok(Box::new(physical::projection::Projection::new(
+                        self.build(&projection.input)?,
+                        projection.expressions.clone(),
+                        projection.schema.clone(),
+                    ))),
+                },
+                // 连接区域的列顺序改变时，投影直接引用连接后的列，不再另加恢复列顺序的投影
+                LogicNode::Join(join) if is_join_region(join) => {
+                    let (plan, columns) = self.build_join_region(join)?;
+                    let expressions: Box<[BoundExpression]> = projection
+                        .expressions
+                        .iter()
+                        .map(|expression| {
+                            expression.map_columns(&|column| ColumnRef {
+                                index: columns[column.index].index,
+                                ..column.clone()
+                            })
+                        })
+                        .collect();
+                    Ok(Box::new(physical::projection::Projection::new(
+                        plan,
+                        expressions,
+                        projection.schema.clone(),
+                    )))
+                }
+                _ => Ok(Box::new(physical::projection::Projection::new(
+                    self.build(&projection.input)?,
+                    projection.expressions.clone(),
+                    projection.schema.clone(),
+                ))),
+            },
+            LogicNode::Limit(limit) => Ok(Box::new(physical::limit::Limit::new(
+                self.build(&limit.input)?,
+                limit.limit,
+                limit.offset,
+            ))),
+            // 子查询别名只改变名字，不改变行的布局
+            LogicNode::SubqueryAlias(alias) => self.build(&alias.input),
+            LogicNode::Aggregate(aggregate) => {
+                Ok(Box::new(physical::aggregate::HashAggregate::new(
+                    self.build(&aggregate.input)?,
+                    aggregate.group_by.clone(),
+                    aggregate.aggregates.clone(),
+                    aggregate.schema.clone(),
+                )))
+            }
+            LogicNode::Sort(sort) => Ok(Box::new(physical::sort::Sort::new(
+                self.build(&sort.input)?,
+                sort.order_by.clone(),
+                self.sort_memory_budget,
+            ))),
+            LogicNode::Join(join) => self.build_join(join),
+        }
+    }
+
+    /// 外连接保持原有的连接顺序，只按代价选择连接算法
+    fn build_join<'a>(
+        &self,
+        join: &Join<'a>,
+    ) -> Result<Box<dyn PhysicalPlan<'a> + 'a>, ExecuteError> {
+        if is_join_region(join) {
+            let (plan, columns) = self.build_join_region(join)?;
+            return Ok(self.restore_layout(join, plan, columns));
+        }
+        let left_rows: f64 = cost::estimate(&join.left).rows;
+        let right_rows: f64 = cost::estimate(&join.right).rows;
+        let output_rows: f64 = cost::estimate_join(join).rows;
+        self.build_join_pair(
+            self.build(&join.left)?,
+            self.build(&join.right)?,
+            join.join_type,
+            join.condition.clone(),
+            join.schema.clone(),
+            [left_rows, right_rows, output_rows],
+        )
+    }
+
+    /// 内连接和笛卡尔积组成的连接区域展开为一组输入，按代价选择连接顺序后构造左深树。
+    /// 同时返回 join.schema 中每一列在输出的行中的引用
+    fn build_join_region<'a>(
+        &self,
+        join: &Join<'a>,
+    ) -> Result<(Box<dyn PhysicalPlan<'a> + 'a>, Vec<ColumnRef>), ExecuteError> {
+        let mut region: JoinRegion<'_, 'a> = JoinRegion {
+            relations: vec![],
+            conjuncts: vec![],
+        };
+        let mut columns: Vec<ColumnEstimate> = vec![];
+        region.flatten_join(join, 0, &mut columns);
+        for conjunct in region.conjuncts.iter_mut() {
+            conjunct.selectivity = cost::selectivity(&conjunct.expression, &columns);
+        }
+
+        let order: Vec<usize> = self.join_order(&region);
+        let mut joined: Vec<bool> = vec![false; region.relations.len()];
+        let mut applied: Vec<bool> = vec![false; region.conjuncts.len()];
+        let first: &Relation = &region.relations[order[0]];
+        joined[order[0]] = true;
+        let mut layout: Vec<usize> = (first.offset..first.offset + first.width).collect();
+        let mut plan: Box<dyn PhysicalPlan<'a> + 'a> = self.build(first.plan)?;
+        for next in order[1..].iter().copied() {
+            let relation: &Relation = &region.relations[next];
+            let left_rows: f64 = region.rows(|r| joined[r]);
+            joined[next] = true;
+            let output_rows: f64 = region.rows(|r| joined[r]);
+            layout.extend(relation.offset..relation.offset + relation.width);
+
+            // 引用的输入都已经连接的合取项作为本次连接的条件，列下标改为指向当前的行布局
+            let mut conjuncts: Vec<BoundExpression> = vec![];
+            for (index, conjunct) in region.conjuncts.iter().enumerate() {
+                if !applied[index] && conjunct.relations.iter().all(|r| joined[*r]) {
+                    applied[index] = true;
+                    conjuncts.push(conjunct.expression.map_columns(&|column| ColumnRef {
+                        index: layout.iter().position(|i| *i == column.index).unwrap(),
+                        ..column.clone()
+                    }));
+                }
+            }
+            let condition: Option<BoundExpression> = BoundExpression::conjunction(conjuncts);
+            let join_type: JoinType = match condition {
+                Some(_) => JoinType::Inner,
+                None => JoinType::Cross,
+            };
+            let schema: Schema = Schema {
+                name: join.schema.name.clone(),
+                columns: layout
+                    .iter()
+                    .map(|index| join.schema.columns[*index].clone())
+                    .collect(),
+            };
+            plan = self.build_join_pair(
+                plan,
+                self.build(relation.plan)?,
+                join_type,
+                condition,
+                schema,
+                [left_rows, region.rows(|r| r == next), output_rows],
+            )?;
+        }
+
+        let columns: Vec<ColumnRef> = join
+            .schema
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| ColumnRef {
+                table: region.relation_of(index).plan.schema().name.clone(),
+                name: column.name.clone(),
+                index: layout.iter().position(|i| *i == index).unwrap(),
+                data_type: column.data_type.clone(),
+                nullable: column.nullable,
+            })
+            .collect();
+        Ok((plan, columns))
+    }
+
+    /// 连接顺序改变了行布局时，在最上面加一个投影恢复 join.schema 的列顺序
+    fn restore_layout<'a>(
+        &self,
+        join: &Join<'a>,
+        plan: Box<dyn PhysicalPlan<'a> + 'a>,
+        columns: Vec<ColumnRef>,
+    ) -> Box<dyn PhysicalPlan<'a> + 'a> {
+        if columns
+            .iter()
+            .enumerate()
+            .all(|(index, column)| column.index == index)
+        {
+            return plan;
+        }
+        let expressions: Box<[BoundExpression]> =
+            columns.into_iter().map(BoundExpression::Column).collect();
+        self.instrument(Box::new(physical::projection::Projection::new(
+            plan,
+            expressions,
+            join.schema.clone(),
+        )))
+    }
+
+    /// 动态规划搜索代价最小的左深连接顺序，输入过多时保持原有的顺序。
+    /// 代价相同时优先原有的顺序
+    fn join_order(&self, region: &JoinRegion) -> Vec<usize> {
+        let count: usize = region.relations.len();
+        if count > MAX_REORDER_RELATIONS {
+            return (0..count).collect();
+        }
+        let masks: Vec<usize> = region
+            .conjuncts
+            .iter()
+            .map(|conjunct| conjunct.relations.iter().map(|r| 1 << r).sum())
+            .collect();
+        let rows: Vec<f64> = (0..1usize << count)
+            .map(|set| region.rows(|r| set & (1 << r) != 0))
+            .collect();
+        let mut best: Vec<Option<(f64, Vec<usize>)>> = vec![None; 1 << count];
+        for relation in 0..count {
+            best[1 << relation] = Some((0.0, vec![relation]));
+        }
+        for set in 1..1usize << count {
+            if set.count_ones() < 2 {
+                continue;
+            }
+            for next in (0..count).rev() {
+                let bit: usize = 1 << next;
+                if set & bit == 0 {
+                    continue;
+                }
+                let rest: usize = set ^ bit;
+                let Some((rest_cost, rest_order)) = best[rest].clone() else {
+                    continue;
+                };
+                let has_keys: bool = region
+                    .conjuncts
+                    .iter()
+                    .zip(masks.iter())
+                    .any(|(c, m)| c.equi && m & bit != 0 && m & rest != 0 && m & !set == 0);
+                let (_, step) = self.choose_algorithm(has_keys, [rows[rest], rows[bit], rows[set]]);
+                let total: f64 = rest_cost + step;
+                if best[set].as_ref().is_none_or(|(cost, _)| total < *cost) {
+                    let mut order: Vec<usize> = rest_order;
+                    order.push(next);
+                    best[set] = Some((total, order));
+                }
+            }
+        }
+        best[(1 << count) - 1].take().unwrap().1
+    }
+
+    /// 选择代价最小的连接算法，rows 为估算的左表、右表和输出的行数。
+    /// 没有等值连接键时只能使用嵌套循环连接，指定了连接算法时使用指定的算法
+    fn choose_algorithm(&self, has_keys: bool, rows: [f64; 3]) -> (JoinAlgorithm, f64) {
+        let cost = |algorithm: JoinAlgorithm| {
+            (
+                algorithm,
+                cost::join_cost(algorithm, rows[0], rows[1], rows[2]),
+            )
+        };
+        if !has_keys {
+            return cost(JoinAlgorithm::NestedLoop);
+        }
+        if let Some(algorithm) = self.join_algorithm {
+            return cost(algorithm);
+        }
+        [
+            JoinAlgorithm::Hash,
+            JoinAlgorithm::SortMerge,
+            JoinAlgorithm::NestedLoop,
+        ]
+        .into_iter()
+        .map(cost)
+        .reduce(|best, candidate| {
+            if candidate.1 < best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .unwrap()
+    }
+
+    /// 构造两个输入的连接，rows 为估算的左表、右表和输出的行数
+    fn build_join_pair<'a>(
+        &self,
+        left: Box<dyn PhysicalPlan<'a> + 'a>,
+        right: Box<dyn PhysicalPlan<'a> + 'a>,
+        join_type: JoinType,
+        condition: Option<BoundExpression>,
+        schema: Schema,
+        rows: [f64; 3],
+    ) -> Result<Box<dyn PhysicalPlan<'a> + 'a>, ExecuteError> {
+        let (left_keys, right_keys, residual) = match &condition {
+            Some(condition) => equi_keys(condition, left.schema().columns.len()),
+            None => (vec![], vec![], None),
+        };
+        let (algorithm, _) = self.choose_algorithm(!left_keys.is_empty(), rows);
+        Ok(self.instrument(match algorithm {
+            JoinAlgorithm::NestedLoop => Box::new(NestedLoopJoin::new(
+                left, right, join_type, condition, schema,
+            )),
+            JoinAlgorithm::Hash => Box::new(HashJoin::new(
+                left,
+                right,
+                join_type,
+                left_keys.into_boxed_slice(),
+                right_keys.into_boxed_slice(),
+                residual,
+                schema,
+            )),
+            JoinAlgorithm::SortMerge => Box::new(SortMergeJoin::new(
+                left,
+                right,
+                join_type,
+                left_keys.into_boxed_slice(),
+                right_keys.into_boxed_slice(),
+                residual,
+                schema,
+            )),
+        }))
+    }
+
+    fn merge_projection<'a>(
+        &self,
+        scan: &Scan<'a>,
+        projection: &Projection<'a>,
+    ) -> Result<Option<Box<dyn PhysicalPlan<'a> + 'a>>, ExecuteError> {
+        let mut indices: Vec<u16> = Vec::with_capacity(projection.expressions.len());
+        for expression in projection.expressions.iter() {
+            match expression {
+                BoundExpression::Column(column) => {
+                    indices.push(self.scan_index(scan, column.index)?)
+                }
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(Box::new(
+            SeqScan::new(
+                scan.data_source,
+                indices.into_boxed_slice(),
+                projection.schema.clone(),
+            )
+            .with_filter(self.scan_filter(scan)?),
+        )))
+    }
+
+    fn build_scan<'a>(
+        &self,
+        scan: &Scan<'a>,
+    ) -> Result<Box<dyn PhysicalPlan<'a> + 'a>, ExecuteError> {
+        let mut projection: Vec<u16> = Vec::with_capacity(scan.projection.len());
+        for index in 0..scan.projection.len() {
+            projection.push(self.scan_index(scan, index)?);
+        }
+        Ok(Box::new(
+            SeqScan::new(
+                scan.data_source,
+                projection.into_boxed_slice(),
+                scan.schema.clone(),
+            )
+            .with_filter(self.scan_filter(scan)?),
+        ))
+    }
+
+    /// 将 Scan 的谓词改写为引用表中的列，在投影之前求值
+    fn scan_filter(&self, scan: &Scan) -> Result<Option<BoundExpression>, ExecuteError> {
+        let Some(filter) = &scan.filter else {
+            return Ok(None);
+        };
+        let mut indices: Vec<usize> = Vec::with_capacity(scan.projection.len());
+        for index in 0..scan.projection.len() {
+            indices.push(self.scan_index(scan, index)? as usize);
+        }
+        Ok(Some(filter.map_columns(&|column| ColumnRef {
+            index: indices[column.index],
+            ..column.clone()
+        })))
+    }
+
+    /// Scan 第 index 个输出列在表中的下标
+    fn scan_index(&self, scan: &Scan, index: usize) -> Result<u16, ExecuteError> {
+        let name: &str = &scan.projection[index];
+        let table = scan.data_source.schema();
+        match table.column_index(name) {
+            Some(index) => Ok(index as u16),
+            None => Err(ExecuteError::new(
+                ErrorCode::InternalError,
+                format!("column {} not found in table {}", name, table.name),
+            )),
+        }
+    }
+}
+
+/// 连接重排序时搜索的最大输入个数，超过时保持原有的顺序
+const MAX_REORDER_RELATIONS: usize = 10;
+
+/// JoinRegion 由内连接和笛卡尔积组成的连接树，展开为一组输入和连接条件的合取项。
+/// 合取项中的列下标指向连接树原有的输出行
+struct JoinRegion<'p, 'a> {
+    relations: Vec<Relation<'p, 'a>>,
+    conjuncts: Vec<Conjunct>,
+}
+
+/// Relation 连接区域的一个输入，offset 为其第一列在原有输出行中的下标
+struct Relation<'p, 'a> {
+    plan: &'p LogicNode<'a>,
+    offset: usize,
+    width: usize,
+    rows: f64,
+}
+
+/// Conjunct 连接条件的一个合取项，relations 为其引用的输入
+struct Conjunct {
+    expression: BoundExpression,
+    relations: Vec<usize>,
+    selectivity: f64,
+    equi: bool, // 是否为等值条件，可以作为连接键
+}
+
+impl<'p, 'a> JoinRegion<'p, 'a> {
+    fn flatten_join(
+        &mut self,
+        join: &'p Join<'a>,
+        offset: usize,
+        columns: &mut Vec<ColumnEstimate>,
+    ) {
+        let left_width: usize = join.left.schema().columns.len();
+        self.flatten(&join.left, offset, columns);
+        self.flatten(&join.right, offset + left_width, columns);
+        let Some(condition) = &join.condition else {
+            return;
+        };
+        for conjunct in condition.clone().split_conjunction() {
+            let expression: BoundExpression = conjunct.map_columns(&|column| ColumnRef {
+                index: column.index + offset,
+                ..column.clone()
+            });
+            let mut indices: Vec<usize> = vec![];
+            expression.collect_columns(&mut indices);
+            let mut relations: Vec<usize> = indices
+                .iter()
+                .map(|index| self.relation_index(*index))
+                .collect();
+            relations.sort();
+            relations.dedup();
+            let equi: bool = matches!(
+                &expression,
+                BoundExpression::Binary(binary) if matches!(binary.operator, BinaryOperator::Equal(_))
+            );
+            self.conjuncts.push(Conjunct {
+                expression,
+                relations,
+                selectivity: 1.0,
+                equi,
+            });
+        }
+    }
+
+    fn flatten(
+        &mut self,
+        plan: &'p LogicNode<'a>,
+        offset: usize,
+        columns: &mut Vec<ColumnEstimate>,
+    ) {
+        match plan {
+            LogicNode::Join(join)
+                if !join.join_type.keeps_left() && !join.join_type.keeps_right() =>
+            {
+                self.flatten_join(join, offset, columns)
+            }
+            _ => {
+                let estimate: Estimate = cost::estimate(plan);
+                self.relations.push(Relation {
+                    plan,
+                    offset,
+                    width: plan.schema().columns.len(),
+                    rows: estimate.rows,
+                });
+                columns.extend(estimate.columns);
+            }
+        }
+    }
+
+    /// 原有输出行中下标为 index 的列所在的输入
+    fn relation_index(&self, index: usize) -> usize {
+        self.relations
+            .iter()
+            .rposition(|relation| relation.offset <= index)
+            .unwrap()
+    }
+
+    fn relation_of(&self, index: usize) -> &Relation<'p, 'a> {
+        &self.relations[self.relation_index(index)]
+    }
+
+    /// 估算连接 contains 中的输入后的行数，只引用这些输入的合取项都已经计算
+    fn rows(&self, contains: impl Fn(usize) -> bool) -> f64 {
+        let rows: f64 = (0..self.relations.len())
+            .filter(|r| contains(*r))
+            .map(|r| self.relations[r].rows)
+            .product();
+        self.conjuncts
+            .iter()
+            .filter(|conjunct| conjunct.relations.iter().all(|r| contains(*r)))
+            .map(|conjunct| conjunct.selectivity)
+            .product::<f64>()
+            * rows
+    }
+}
+
+/// 内连接和笛卡尔积可以和相邻的同类连接一起重新排序，外连接保持原有的顺序
+fn is_join_region(join: &Join) -> bool {
+    !join.join_type.keeps_left() && !join.join_type.keeps_right()
+}
+
+/// 从连接条件中提取等值连接键
+/// 形如 l = r 的合取项，l 只引用左表的列、r 只引用右表的列（或者相反），
+/// 且两侧类型相同时作为连接键，其余的合取项留作 residual 在拼接后的行上计算。
+/// 右侧键的列下标减去 left_width，直接在右表的行上计算
+fn equi_keys(
+    condition: &BoundExpression,
+    left_width: usize,
+) -> (
+    Vec<BoundExpression>,
+    Vec<BoundExpression>,
+    Option<BoundExpression>,
+) {
+    // 表达式只引用左表的列时返回 Some(true)，只引用右表的列时返回 Some(false)
+    let side = |expression: &BoundExpression| -> Option<bool> {
+        let mut columns: Vec<usize> = vec![];
+        expression.collect_columns(&mut columns);
+        if columns.is_empty() {
+            None
+        } else if columns.iter().all(|index| *index < left_width) {
+            Some(true)
+        } else if columns.iter().all(|index| *index >= left_width) {
+            Some(false)
+        } else {
+            None
+        }
+    };
+    let shift = |column: &ColumnRef| ColumnRef {
+        index: column.index - left_width,
+        ..column.clone()
+    };
+
+    let mut left_keys: Vec<BoundExpression> = vec![];
+    let mut right_keys: Vec<BoundExpression> = vec![];
+    let mut residual: Vec<BoundExpression> = vec![];
+    for conjunct in condition.clone().split_conjunction() {
+        if let BoundExpression::Binary(binary) = &conjunct {
+            let (l, r) = (binary.left.data_type(), binary.right.data_type());
+            let comparable: bool = l == r || l.is_string() && r.is_string();
+            if matches!(binary.operator, BinaryOperator::Equal(_)) && comparable {
+                match (side(&binary.left), side(&binary.right)) {
+                    (Some(true), Some(false)) => {
+                        left_keys.push(*binary.left.clone());
+                        right_keys.push(binary.right.map_columns(&shift));
+                        continue;
+                    }
+                    (Some(false), Some(true)) => {
+                        left_keys.push(*binary.right.clone());
+                        right_keys.push(binary.left.map_columns(&shift));
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        residual.push(conjunct);
+    }
+    (
+        left_keys,
+        right_keys,
+        BoundExpression::conjunction(residual),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        executor::{
+            context::SimpleMemoryContext,
+            meta::{
+                column::Column, data_source::SimpleMemoryDataSource, row::SimpleMemoryRow,
+                schema::Schema,
+            },
+            planner::{logic::LogicPlanner, optimizer::Optimizer},
+            test_util::context,
+            types::{int32::Int32, DataType, OwnValue},
+        },
+        sql::{
+            parser::{ast::Statement, parser::Parser},
+            tokenizer::{token::ParsedTokens, tokenizer::Tokenizer},
+        },
+    };
+
+    fn build(planner: &PhysicalPlanner, sql: &str) -> String {
+        build_in(&context(), planner, sql)
+    }
+
+    fn build_in(context: &SimpleMemoryContext, planner: &PhysicalPlanner, sql: &str) -> String {
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        let Statement::Select(select) = &statements.statements[0] else {
+            panic!("expect select");
+        };
+        let logic: LogicNode = LogicPlanner::new(context, sql).plan_select(select).unwrap();
+        let physical = planner.build(&logic).unwrap();
+        physical.to_string()
+    }
+
+    #[test]
+    fn build_scan_filter() {
+        let context: SimpleMemoryContext = context();
+        let sql: &str = "SELECT name FROM stu WHERE age > 1";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements = Parser::new(&tokens).parse().unwrap();
+        let Statement::Select(select) = &statements.statements[0] else {
+            panic!("expect select");
+        };
+        let logic: LogicNode = LogicPlanner::new(&context, sql)
+            .plan_select(select)
+            .unwrap();
+        let logic: LogicNode = Optimizer::new().optimize(logic);
+        let physical = PhysicalPlanner::new().build(&logic).unwrap();
+        assert_eq!(
+            physical.to_string(),
+            "SeqScan: stu [name] filter=(stu.age > 1)"
+        );
+    }
+
+    #[test]
+    fn build_join() {
+        let sql: &str = "SELECT a.name FROM stu AS a LEFT JOIN stu AS b ON b.age = a.age + 1 AND a.name = b.name AND a.age < b.age";
+        assert_eq!(
+            build(&PhysicalPlanner::new(), sql),
+            [
+                "Projection: a.name",
+                "  HashJoin: LEFT keys=[(a.age + 1) = b.age, a.name = b.name] filter=(a.age < b.age)",
+                "    SeqScan: stu [name, age]",
+                "    SeqScan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+        assert!(build(
+            &PhysicalPlanner::with_join_algorithm(JoinAlgorithm::SortMerge),
+            sql
+        )
+        .contains("SortMergeJoin: LEFT keys=[(a.age + 1) = b.age, a.name = b.name]"));
+        assert!(
+            build(&PhysicalPlanner::with_join_algorithm(JoinAlgorithm::NestedLoop), sql)
+                .contains("NestedLoopJoin: LEFT ON (((b.age = (a.age + 1)) AND (a.name = b.name)) AND (a.age < b.age))")
+        );
+
+        // 没有等值连接键时总是使用嵌套循环连接
+        let sql: &str = "SELECT a.name FROM stu AS a JOIN stu AS b ON a.age < b.age, stu";
+        assert_eq!(
+            build(
+                &PhysicalPlanner::with_join_algorithm(JoinAlgorithm::Hash),
+                sql
+            ),
+            [
+                "Projection: a.name",
+                "  NestedLoopJoin: CROSS",
+                "    NestedLoopJoin: INNER ON (a.age < b.age)",
+                "      SeqScan: stu [name, age]",
+                "      SeqScan: stu [name, age]",
+                "    SeqScan: stu [name, age]",
+            ]
+            .join("\n")
+        );
+    }
+
+    /// 生成 rows 行的表 name(id, grp)，id 为 0..rows，grp 为 id % 10
+    fn table(name: &str, rows: i32) -> SimpleMemoryDataSource {
+        let column = |name: &str| Column {
+            name: name.into(),
+            data_type: DataType::Int32,
+            nullable: false,
+            default: None,
+        };
+        let mut source: SimpleMemoryDataSource = SimpleMemoryDataSource::new(Schema {
+            name: name.into(),
+            columns: vec![column("id"), column("grp")].into_boxed_slice(),
+        });
+        for id in 0..rows {
+            source.push_row(SimpleMemoryRow::new(vec![
+                OwnValue::Int32(Int32::new(id)),
+                OwnValue::Int32(Int32::new(id % 10)),
+            ]));
+        }
+        source
+    }
+
+    #[test]
+    fn build_join_order() {
+        let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+        context.add_table(Box::new(table("big", 1000)));
+        context.add_table(Box::new(table("small", 10)));
+        context.add_table(Box::new(table("tiny", 2)));
+        let planner: PhysicalPlanner = PhysicalPlanner::new();
+
+        // 较小的表作为哈希连接的构建侧，上层的投影直接引用交换后的列
+        assert_eq!(
+            build_in(
+                &context,
+                &planner,
+                "SELECT small.id FROM small JOIN big ON small.id = big.id"
+            ),
+            [
+                "Projection: small.id",
+                "  HashJoin: INNER keys=[big.id = small.id]",
+                "    SeqScan: big [id, grp]",
+                "    SeqScan: small [id, grp]",
+            ]
+            .join("\n")
+        );
+
+        // 先做选择性高的连接，中间结果更小
+        assert_eq!(
+            build_in(
+                &context,
+                &planner,
+                "SELECT tiny.id FROM big JOIN small ON big.grp = small.grp JOIN tiny ON tiny.id = big.id"
+            ),
+            [
+                "Projection: tiny.id",
+                "  HashJoin: INNER keys=[big.grp = small.grp]",
+                "    HashJoin: INNER keys=[big.id = tiny.id]",
+                "      SeqScan: big [id, grp]",
+                "      SeqScan: tiny [id, grp]",
+                "    SeqScan: small [id, grp]",
+            ]
+            .join("\n")
+        );
+
+        // 外连接不改变连接顺序
+        assert!(build_in(
+            &context,
+            &planner,
+            "SELECT small.id FROM small LEFT JOIN big ON small.id = big.id"
+        )
+        .starts_with(
+            "Projection: small.id\n  HashJoin: LEFT keys=[small.id = big.id]\n    SeqScan: small"
+        ));
+    }
+}
@@ -0,0 +1,55 @@
+use super::{
+    context::SimpleMemoryContext,
+    meta::{column::Column, data_source::SimpleMemoryDataSource, row::Row, schema::Schema},
+    plan::physical::PhysicalPlan,
+    types::DataType,
+};
+
+/// 只有一张空表 stu(name Varchar(32), age Int32) 的上下文
+pub fn context() -> SimpleMemoryContext {
+    let schema: Schema = Schema {
+        name: "stu".into(),
+        columns: vec![
+            Column {
+                name: "name".into(),
+                data_type: DataType::Varchar(32),
+                nullable: true,
+                default: None,
+            },
+            Column {
+                name: "age".into(),
+                data_type: DataType::Int32,
+                nullable: true,
+                default: None,
+            },
+        ]
+        .into_boxed_slice(),
+    };
+    let mut context: SimpleMemoryContext = SimpleMemoryContext::new();
+    context.add_table(Box::new(SimpleMemoryDataSource::new(schema)));
+    context
+}
+
+/// 用 next 逐行读取计划的全部输出
+pub fn collect(plan: &mut dyn PhysicalPlan<'_>) -> Vec<String> {
+    let mut rows: Vec<String> = vec![];
+    plan.open().unwrap();
+    while let Some(row) = plan.next().unwrap() {
+        rows.push(row.to_string(plan.schema()).unwrap());
+    }
+    plan.close().unwrap();
+    rows
+}
+
+/// 用 batch 按批读取计划的全部输出
+pub fn collect_batches(plan: &mut dyn PhysicalPlan<'_>) -> Vec<String> {
+    let mut rows: Vec<String> = vec![];
+    plan.open().unwrap();
+    while let Some(batch) = plan.batch().unwrap() {
+        for index in 0..batch.len() {
+            rows.push(batch.row(index).to_string(plan.schema()).unwrap());
+        }
+    }
+    plan.close().unwrap();
+    rows
+}
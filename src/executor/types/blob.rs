@@ -0,0 +1,49 @@
+use crate::{error::ErrorCode, executor::error::ExecuteError};
+
+/// 解析十六进制串，每两个十六进制数字是一个字节，不区分大小写
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, ExecuteError> {
+    let invalid = || {
+        ExecuteError::new(
+            ErrorCode::InvalidTextRepresentation,
+            format!("invalid hexadecimal data for type Blob: \"{}\"", text),
+        )
+    };
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    Ok(text
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| (hex_digit(pair[0]) << 4) | hex_digit(pair[1]))
+        .collect())
+}
+
+fn hex_digit(b: u8) -> u8 {
+    match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        _ => b - b'A' + 10,
+    }
+}
+
+/// 小写的十六进制串
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_hex, to_hex};
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn hex() {
+        assert_eq!(parse_hex("00fFa1").unwrap(), vec![0x00, 0xff, 0xa1]);
+        assert_eq!(parse_hex("").unwrap(), Vec::<u8>::new());
+        assert_eq!(to_hex(&[0x00, 0xff, 0xa1]), "00ffa1");
+        for text in ["abc", "zz", "+1", "é0"] {
+            let error = parse_hex(text).unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidTextRepresentation, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,520 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
+
+use crate::{error::ErrorCode, executor::error::ExecuteError};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// Date 日期，值为距 1970-01-01 的天数，字面量格式 YYYY-MM-DD，年份为 1 到 9999
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Date {
+    days: i32,
+}
+
+impl Date {
+    pub fn new(days: i32) -> Self {
+        Self { days }
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// 由年月日构造日期，日期不存在时返回 None
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=9999).contains(&year)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return None;
+        }
+        Some(Self::new(days_from_civil(year, month, day)))
+    }
+
+    /// 年、月、日
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        civil_from_days(self.days)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ExecuteError> {
+        let (year, month, day) =
+            parse_ymd(text.trim()).ok_or_else(|| format_error("Date", text))?;
+        Self::from_ymd(year, month, day).ok_or_else(|| overflow_error(text))
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Time 一天中的时间，值为距 00:00:00 的微秒数，字面量格式 HH:MM[:SS[.ffffff]]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Time {
+    micros: i64,
+}
+
+impl Time {
+    pub fn new(micros: i64) -> Self {
+        debug_assert!((0..MICROS_PER_DAY).contains(&micros));
+        Self { micros }
+    }
+
+    pub fn micros(&self) -> i64 {
+        self.micros
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ExecuteError> {
+        let micros: i64 = parse_time(text.trim())
+            .ok_or_else(|| format_error("Time", text))?
+            .ok_or_else(|| overflow_error(text))?;
+        Ok(Self::new(micros))
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_micros(f, self.micros as u64)
+    }
+}
+
+/// Timestamp 不带时区的时间戳，值为距 1970-01-01 00:00:00 的微秒数
+/// 字面量格式 YYYY-MM-DD[ HH:MM[:SS[.ffffff]]]，日期与时间之间也可以用 T 分隔
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Timestamp {
+    micros: i64,
+}
+
+impl Timestamp {
+    pub fn new(micros: i64) -> Self {
+        Self { micros }
+    }
+
+    pub fn micros(&self) -> i64 {
+        self.micros
+    }
+
+    pub fn from_date_time(date: Date, time: Time) -> Self {
+        Self::new(date.days() as i64 * MICROS_PER_DAY + time.micros())
+    }
+
+    pub fn date(&self) -> Date {
+        Date::new(self.micros.div_euclid(MICROS_PER_DAY) as i32)
+    }
+
+    pub fn time(&self) -> Time {
+        Time::new(self.micros.rem_euclid(MICROS_PER_DAY))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ExecuteError> {
+        let trimmed: &str = text.trim();
+        let (date, time) = match trimmed.split_once([' ', 'T']) {
+            Some((date, time)) => (date, Some(time.trim_start())),
+            None => (trimmed, None),
+        };
+        let (year, month, day) = parse_ymd(date).ok_or_else(|| format_error("Timestamp", text))?;
+        let micros: i64 = match time {
+            Some(time) => parse_time(time)
+                .ok_or_else(|| format_error("Timestamp", text))?
+                .ok_or_else(|| overflow_error(text))?,
+            None => 0,
+        };
+        let date: Date = Date::from_ymd(year, month, day).ok_or_else(|| overflow_error(text))?;
+        Ok(Self::from_date_time(date, Time::new(micros)))
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.date(), self.time())
+    }
+}
+
+impl From<Date> for Timestamp {
+    fn from(date: Date) -> Self {
+        Self::new(date.days() as i64 * MICROS_PER_DAY)
+    }
+}
+
+/// Interval 时间间隔，由月数、天数和微秒数组成，三部分可以有不同的符号
+/// 比较和哈希时按一个月 30 天、一天 24 小时换算，1 month 与 30 days 相等
+/// 字面量是若干个 `数量 单位`，最后可以跟一个 [-]HH:MM[:SS[.ffffff]]，例如 '1 year 2 days 03:04:05'
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    micros: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Self {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn micros(&self) -> i64 {
+        self.micros
+    }
+
+    /// 换算成的总微秒数
+    fn total_micros(&self) -> i128 {
+        (self.months as i128 * 30 + self.days as i128) * MICROS_PER_DAY as i128
+            + self.micros as i128
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ExecuteError> {
+        let invalid = || format_error("Interval", text);
+        let mut interval: Interval = Interval::default();
+        let mut words = text.split_whitespace().peekable();
+        if words.peek().is_none() {
+            return Err(invalid());
+        }
+        while let Some(word) = words.next() {
+            // 时间部分只能出现在最后
+            if word.contains(':') {
+                let (negative, time) = match word.strip_prefix('-') {
+                    Some(time) => (true, time),
+                    None => (false, word),
+                };
+                let micros: i64 = parse_time(time)
+                    .ok_or_else(invalid)?
+                    .ok_or_else(|| overflow_error(text))?;
+                interval.micros = interval
+                    .micros
+                    .checked_add(if negative { -micros } else { micros })
+                    .ok_or_else(|| overflow_error(text))?;
+                if words.peek().is_some() {
+                    return Err(invalid());
+                }
+                break;
+            }
+            let number: i64 = word.parse().map_err(|_| invalid())?;
+            let unit: &str = words.next().ok_or_else(invalid)?;
+            interval
+                .add_unit(number, unit)
+                .ok_or_else(invalid)?
+                .ok_or_else(|| overflow_error(text))?;
+        }
+        Ok(interval)
+    }
+
+    /// 加上 number 个 unit，单位不认识时返回 None，溢出时返回 Some(None)
+    fn add_unit(&mut self, number: i64, unit: &str) -> Option<Option<()>> {
+        let (months, days, micros): (i64, i64, i64) = match unit.to_ascii_lowercase().as_str() {
+            "year" | "years" => (12, 0, 0),
+            "mon" | "mons" | "month" | "months" => (1, 0, 0),
+            "week" | "weeks" => (0, 7, 0),
+            "day" | "days" => (0, 1, 0),
+            "hour" | "hours" => (0, 0, MICROS_PER_HOUR),
+            "min" | "mins" | "minute" | "minutes" => (0, 0, MICROS_PER_MINUTE),
+            "sec" | "secs" | "second" | "seconds" => (0, 0, MICROS_PER_SECOND),
+            "ms" | "millisecond" | "milliseconds" => (0, 0, 1_000),
+            "us" | "microsecond" | "microseconds" => (0, 0, 1),
+            _ => return None,
+        };
+        let add_i32 = |field: i32, factor: i64| -> Option<i32> {
+            field.checked_add(number.checked_mul(factor)?.try_into().ok()?)
+        };
+        Some((|| {
+            self.months = add_i32(self.months, months)?;
+            self.days = add_i32(self.days, days)?;
+            self.micros = self.micros.checked_add(number.checked_mul(micros)?)?;
+            Some(())
+        })())
+    }
+}
+
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_micros() == other.total_micros()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_micros().cmp(&other.total_micros())
+    }
+}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_micros().hash(state)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = vec![];
+        let (years, months) = (self.months / 12, self.months % 12);
+        for (value, unit) in [(years, "year"), (months, "month"), (self.days, "day")] {
+            match value {
+                0 => {}
+                1 | -1 => parts.push(format!("{} {}", value, unit)),
+                _ => parts.push(format!("{} {}s", value, unit)),
+            }
+        }
+        if self.micros != 0 || parts.is_empty() {
+            let sign: &str = if self.micros < 0 { "-" } else { "" };
+            let mut time: String = String::new();
+            write_micros(&mut time, self.micros.unsigned_abs())?;
+            parts.push(format!("{}{}", sign, time));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// 第 year 年第 month 月的天数
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// 公历日期到距 1970-01-01 的天数
+fn days_from_civil(year: i32, month: u32, day: u32) -> i32 {
+    let year: i32 = if month <= 2 { year - 1 } else { year };
+    let era: i32 = year.div_euclid(400);
+    let year_of_era: i32 = year.rem_euclid(400);
+    let month: i32 = month as i32;
+    let day_of_year: i32 =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i32 - 1;
+    let day_of_era: i32 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// 距 1970-01-01 的天数到公历日期
+fn civil_from_days(days: i32) -> (i32, u32, u32) {
+    let days: i32 = days + 719468;
+    let era: i32 = days.div_euclid(146097);
+    let day_of_era: i32 = days.rem_euclid(146097);
+    let year_of_era: i32 =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year: i32 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp: i32 = (5 * day_of_year + 2) / 153;
+    let day: u32 = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month: u32 = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year: i32 = year_of_era + era * 400 + (month <= 2) as i32;
+    (year, month, day)
+}
+
+/// 解析 YYYY-MM-DD，格式不对时返回 None，不检查字段的范围
+fn parse_ymd(text: &str) -> Option<(i32, u32, u32)> {
+    let mut fields = text.split('-');
+    let year: &str = fields.next()?;
+    let month: &str = fields.next()?;
+    let day: &str = fields.next()?;
+    if fields.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    Some((digits(year)?, digits(month)?, digits(day)?))
+}
+
+/// 解析 HH:MM[:SS[.ffffff]]，返回距 00:00:00 的微秒数
+/// 格式不对时返回 None，字段超出范围时返回 Some(None)
+fn parse_time(text: &str) -> Option<Option<i64>> {
+    let mut fields = text.split(':');
+    let hour: &str = fields.next()?;
+    let minute: &str = fields.next()?;
+    let second: &str = fields.next().unwrap_or("00");
+    if fields.next().is_some() || hour.len() != 2 || minute.len() != 2 {
+        return None;
+    }
+    let (second, fraction) = second.split_once('.').unwrap_or((second, ""));
+    if second.len() != 2 || fraction.len() > 6 {
+        return None;
+    }
+    let (hour, minute, second): (i64, i64, i64) = (digits(hour)?, digits(minute)?, digits(second)?);
+    let fraction: i64 = match fraction {
+        "" => 0,
+        _ => digits::<i64>(fraction)? * 10i64.pow(6 - fraction.len() as u32),
+    };
+    if hour > 23 || minute > 59 || second > 59 {
+        return Some(None);
+    }
+    Some(Some(
+        hour * MICROS_PER_HOUR + minute * MICROS_PER_MINUTE + second * MICROS_PER_SECOND + fraction,
+    ))
+}
+
+/// 只由 ASCII 数字组成的字符串
+fn digits<T: std::str::FromStr>(text: &str) -> Option<T> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// 按 HH:MM:SS[.ffffff] 格式写出微秒数，小数部分去掉末尾的 0
+fn write_micros(f: &mut impl std::fmt::Write, micros: u64) -> std::fmt::Result {
+    let (seconds, fraction) = (micros / 1_000_000, micros % 1_000_000);
+    write!(
+        f,
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )?;
+    if fraction != 0 {
+        let fraction: String = format!("{:06}", fraction);
+        write!(f, ".{}", fraction.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+fn format_error(type_name: &str, text: &str) -> ExecuteError {
+    ExecuteError::new(
+        ErrorCode::InvalidDatetimeFormat,
+        format!("invalid input syntax for type {}: \"{}\"", type_name, text),
+    )
+}
+
+fn overflow_error(text: &str) -> ExecuteError {
+    ExecuteError::new(
+        ErrorCode::DatetimeFieldOverflow,
+        format!("date/time field value out of range: \"{}\"", text),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{Date, Interval, Time, Timestamp};
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn date() {
+        let date: Date = Date::parse("2024-02-29").unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.ymd(), (2024, 2, 29));
+        assert_eq!(Date::parse("1970-01-01").unwrap().days(), 0);
+        assert_eq!(Date::parse("1969-12-31").unwrap().days(), -1);
+        assert_eq!(Date::parse("0001-01-01").unwrap().to_string(), "0001-01-01");
+        assert_eq!(Date::parse("9999-12-31").unwrap().to_string(), "9999-12-31");
+        assert!(Date::parse("2000-01-01").unwrap() < Date::parse("2000-01-02").unwrap());
+
+        for text in ["2023-02-29", "2024-13-01", "2024-04-31", "0000-01-01"] {
+            let error = Date::parse(text).unwrap_err();
+            assert_eq!(error.code, ErrorCode::DatetimeFieldOverflow, "{}", text);
+        }
+        for text in ["2024-1-01", "2024/01/01", "20240101", "abcd-01-01", ""] {
+            let error = Date::parse(text).unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidDatetimeFormat, "{}", text);
+        }
+        assert_eq!(
+            Date::parse("2024-01-32").unwrap_err().to_string(),
+            "date/time field value out of range: \"2024-01-32\""
+        );
+    }
+
+    #[test]
+    fn time() {
+        assert_eq!(Time::parse("08:30").unwrap().to_string(), "08:30:00");
+        assert_eq!(Time::parse("23:59:59.5").unwrap().to_string(), "23:59:59.5");
+        assert_eq!(Time::parse("00:00:01.000250").unwrap().micros(), 1_000_250);
+        assert_eq!(
+            Time::parse("24:00:00").unwrap_err().code,
+            ErrorCode::DatetimeFieldOverflow
+        );
+        assert_eq!(
+            Time::parse("8:30").unwrap_err().code,
+            ErrorCode::InvalidDatetimeFormat
+        );
+        assert!(Time::parse("12:00:00.1234567").is_err());
+    }
+
+    #[test]
+    fn timestamp() {
+        let timestamp: Timestamp = Timestamp::parse("2024-03-01 12:34:56.789").unwrap();
+        assert_eq!(timestamp.to_string(), "2024-03-01 12:34:56.789");
+        assert_eq!(
+            Timestamp::parse("2024-03-01T01:02").unwrap().to_string(),
+            "2024-03-01 01:02:00"
+        );
+        assert_eq!(
+            Timestamp::parse("1969-12-31 23:59:59").unwrap().micros(),
+            -1_000_000
+        );
+        assert_eq!(
+            Timestamp::parse("2024-03-01").unwrap(),
+            Timestamp::from(Date::parse("2024-03-01").unwrap())
+        );
+        assert_eq!(
+            Timestamp::parse("2024-02-30 00:00:00").unwrap_err().code,
+            ErrorCode::DatetimeFieldOverflow
+        );
+        assert_eq!(
+            Timestamp::parse("2024-02-01 noon").unwrap_err().code,
+            ErrorCode::InvalidDatetimeFormat
+        );
+    }
+
+    #[test]
+    fn interval() {
+        let interval: Interval = Interval::parse("1 year 14 months 3 days 04:05:06.5").unwrap();
+        assert_eq!((interval.months(), interval.days()), (26, 3));
+        assert_eq!(interval.to_string(), "2 years 2 months 3 days 04:05:06.5");
+        assert_eq!(
+            Interval::parse("2 weeks -1 day 90 minutes")
+                .unwrap()
+                .to_string(),
+            "13 days 01:30:00"
+        );
+        assert_eq!(
+            Interval::parse("-00:00:01").unwrap().to_string(),
+            "-00:00:01"
+        );
+        assert_eq!(Interval::parse("0 days").unwrap().to_string(), "00:00:00");
+        assert_eq!(
+            Interval::parse("1 mon").unwrap(),
+            Interval::parse("30 days").unwrap()
+        );
+        assert!(Interval::parse("1 day").unwrap() < Interval::parse("25 hours").unwrap());
+
+        for text in ["", "1", "1 fortnight", "day 1", "01:00 1 day", "x days"] {
+            let error = Interval::parse(text).unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidDatetimeFormat, "{}", text);
+        }
+        assert_eq!(
+            Interval::parse("3000000000 days").unwrap_err().code,
+            ErrorCode::DatetimeFieldOverflow
+        );
+
+        let set: HashSet<Interval> = ["1 month", "30 days", "720 hours", "1 day"]
+            .into_iter()
+            .map(|text| Interval::parse(text).unwrap())
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+}
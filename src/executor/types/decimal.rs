@@ -0,0 +1,422 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use super::{flout64::Float64, int32::Int32, int64::Int64};
+use crate::{error::ErrorCode, executor::error::ExecuteError};
+
+/// 十进制数最多的有效数字位数
+pub const MAX_PRECISION: u8 = 38;
+
+/// 除法结果至少保留的小数位数
+pub const DIVISION_SCALE: u8 = 6;
+
+/// Decimal 定点十进制数，值为 value / 10^scale，有效数字最多 MAX_PRECISION 位
+/// 比较和哈希按数值进行，1.0 与 1.00 相等
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    value: i128,
+    scale: u8,
+}
+
+impl Decimal {
+    /// value 的位数不能超过 MAX_PRECISION，scale 不能超过 MAX_PRECISION
+    pub fn new(value: i128, scale: u8) -> Result<Self, ExecuteError> {
+        if scale > MAX_PRECISION || value.unsigned_abs() >= pow10(MAX_PRECISION) as u128 {
+            return Err(Self::overflow());
+        }
+        Ok(Self { value, scale })
+    }
+
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// 解析 [+-]digits[.digits] 形式的字符串，小数位数即为 scale
+    pub fn parse(text: &str) -> Result<Self, ExecuteError> {
+        let invalid = || {
+            ExecuteError::new(
+                ErrorCode::InvalidTextRepresentation,
+                format!("invalid input syntax for type Decimal: \"{}\"", text),
+            )
+        };
+        let trimmed: &str = text.trim();
+        let (negative, digits) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..]),
+            Some(b'+') => (false, &trimmed[1..]),
+            _ => (false, trimmed),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let scale: u8 = u8::try_from(fraction.len())
+            .ok()
+            .filter(|s| *s <= MAX_PRECISION)
+            .ok_or_else(Self::overflow)?;
+        let mut value: i128 = 0;
+        for b in integer.bytes().chain(fraction.bytes()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((b - b'0') as i128))
+                .ok_or_else(Self::overflow)?;
+        }
+        Self::new(if negative { -value } else { value }, scale)
+    }
+
+    /// 按 scale 四舍五入浮点数，NaN 和无穷大不能转换
+    pub fn from_float64(value: Float64, scale: u8) -> Result<Self, ExecuteError> {
+        let scaled: f64 = (value.value() * pow10(scale) as f64).round();
+        if !scaled.is_finite() || scaled.abs() >= pow10(MAX_PRECISION) as f64 {
+            return Err(ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("cannot convert {} to Decimal", value),
+            ));
+        }
+        Self::new(scaled as i128, scale)
+    }
+
+    pub fn to_float64(&self) -> Float64 {
+        Float64::new(self.value as f64 / pow10(self.scale) as f64)
+    }
+
+    /// 转换为 scale 位小数，减少小数位时四舍五入
+    pub fn rescale(&self, scale: u8) -> Result<Self, ExecuteError> {
+        if scale > MAX_PRECISION {
+            return Err(Self::overflow());
+        }
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Ok(*self),
+            Ordering::Greater => {
+                let value: i128 = self
+                    .value
+                    .checked_mul(pow10(scale - self.scale))
+                    .ok_or_else(Self::overflow)?;
+                Self::new(value, scale)
+            }
+            Ordering::Less => Self::new(round_div(self.value, pow10(self.scale - scale)), scale),
+        }
+    }
+
+    /// 转换为 Decimal(precision, scale)，整数部分的位数超过 precision - scale 时返回错误
+    pub fn check(&self, precision: u8, scale: u8) -> Result<Self, ExecuteError> {
+        let value: Decimal = self.rescale(scale)?;
+        if value.value.unsigned_abs() >= pow10(precision) as u128 {
+            return Err(ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!(
+                    "value {} out of range for type Decimal({},{})",
+                    self, precision, scale
+                ),
+            ));
+        }
+        Ok(value)
+    }
+
+    /// 加、减、取余结果的小数位数
+    pub fn add_scale(left: u8, right: u8) -> u8 {
+        left.max(right)
+    }
+
+    /// 乘法结果的小数位数
+    pub fn mul_scale(left: u8, right: u8) -> u8 {
+        (left + right).min(MAX_PRECISION)
+    }
+
+    /// 除法结果的小数位数
+    pub fn div_scale(left: u8, right: u8) -> u8 {
+        left.max(right).max(DIVISION_SCALE)
+    }
+
+    /// 去掉末尾的 0 之后的表示，用于哈希
+    fn normalize(&self) -> (i128, u8) {
+        let (mut value, mut scale) = (self.value, self.scale);
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        (value, scale)
+    }
+
+    /// 把两个数转换为相同的小数位数
+    fn align(self, rhs: Self) -> Result<(i128, i128, u8), ExecuteError> {
+        let scale: u8 = Self::add_scale(self.scale, rhs.scale);
+        Ok((self.rescale(scale)?.value, rhs.rescale(scale)?.value, scale))
+    }
+
+    fn overflow() -> ExecuteError {
+        ExecuteError::new(
+            ErrorCode::NumericValueOutOfRange,
+            format!("Decimal overflow, at most {} digits", MAX_PRECISION),
+        )
+    }
+
+    /// 除数为零时返回 DivisionByZero
+    fn check_divisor(rhs: Self) -> Result<(), ExecuteError> {
+        match rhs.value {
+            0 => Err(ExecuteError::new(
+                ErrorCode::DivisionByZero,
+                "division by zero",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 10 的 exp 次方，exp 不超过 MAX_PRECISION
+fn pow10(exp: u8) -> i128 {
+    10i128.pow(exp as u32)
+}
+
+/// 整数除法，结果四舍五入（远离零）
+fn round_div(value: i128, divisor: i128) -> i128 {
+    let (quotient, remainder) = (value / divisor, value % divisor);
+    if remainder.unsigned_abs() * 2 >= divisor.unsigned_abs() {
+        if (value < 0) == (divisor < 0) {
+            quotient + 1
+        } else {
+            quotient - 1
+        }
+    } else {
+        quotient
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    /// 小数位数少的一方放大后溢出时，它的绝对值一定更大
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale: u8 = Self::add_scale(self.scale, other.scale);
+        let scaled = |d: &Decimal| d.value.checked_mul(pow10(scale - d.scale));
+        match (scaled(self), scaled(other)) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            (None, _) => self.value.cmp(&0),
+            (_, None) => 0.cmp(&other.value),
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalize().hash(state)
+    }
+}
+
+impl Add for Decimal {
+    type Output = Result<Decimal, ExecuteError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (l, r, scale) = self.align(rhs)?;
+        Self::new(l.checked_add(r).ok_or_else(Self::overflow)?, scale)
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Result<Decimal, ExecuteError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (l, r, scale) = self.align(rhs)?;
+        Self::new(l.checked_sub(r).ok_or_else(Self::overflow)?, scale)
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Result<Decimal, ExecuteError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let value: i128 = self
+            .value
+            .checked_mul(rhs.value)
+            .ok_or_else(Self::overflow)?;
+        let scale: u8 = self.scale + rhs.scale;
+        let target: u8 = Self::mul_scale(self.scale, rhs.scale);
+        Self::new(round_div(value, pow10(scale - target)), target)
+    }
+}
+
+impl Div for Decimal {
+    type Output = Result<Decimal, ExecuteError>;
+
+    /// 结果保留 div_scale 位小数，四舍五入
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        let scale: u8 = Self::div_scale(self.scale, rhs.scale);
+        let value: i128 = 10i128
+            .checked_pow((scale + rhs.scale - self.scale) as u32)
+            .and_then(|p| self.value.checked_mul(p))
+            .ok_or_else(Self::overflow)?;
+        Self::new(round_div(value, rhs.value), scale)
+    }
+}
+
+impl Rem for Decimal {
+    type Output = Result<Decimal, ExecuteError>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        let (l, r, scale) = self.align(rhs)?;
+        Self::new(l % r, scale)
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            scale: self.scale,
+        }
+    }
+}
+
+impl From<Int32> for Decimal {
+    fn from(value: Int32) -> Self {
+        Self {
+            value: value.value() as i128,
+            scale: 0,
+        }
+    }
+}
+
+impl From<Int64> for Decimal {
+    fn from(value: Int64) -> Self {
+        Self {
+            value: value.value() as i128,
+            scale: 0,
+        }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign: &str = if self.value < 0 { "-" } else { "" };
+        let digits: String = self.value.unsigned_abs().to_string();
+        let scale: usize = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let digits: String = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::Decimal;
+    use crate::{
+        error::ErrorCode,
+        executor::types::{flout64::Float64, int32::Int32},
+    };
+
+    fn decimal(text: &str) -> Decimal {
+        Decimal::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(decimal("12.340").to_string(), "12.340");
+        assert_eq!(decimal("-0.05").to_string(), "-0.05");
+        assert_eq!(decimal("+7").to_string(), "7");
+        assert_eq!(decimal(".5").to_string(), "0.5");
+        assert_eq!(decimal("-0.05").scale(), 2);
+        for text in ["", "-", ".", "1.2.3", "1e5", "abc"] {
+            let error = Decimal::parse(text).unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidTextRepresentation, "{}", text);
+        }
+        assert_eq!(
+            Decimal::parse(&"9".repeat(39)).unwrap_err().code,
+            ErrorCode::NumericValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (decimal("1.25"), decimal("0.1"));
+        assert_eq!((a + b).unwrap().to_string(), "1.35");
+        assert_eq!((a - b).unwrap().to_string(), "1.15");
+        assert_eq!((a * b).unwrap().to_string(), "0.125");
+        assert_eq!((a / b).unwrap().to_string(), "12.500000");
+        assert_eq!(
+            (decimal("2") / decimal("3")).unwrap().to_string(),
+            "0.666667"
+        );
+        assert_eq!((a % b).unwrap().to_string(), "0.05");
+        assert_eq!((-a).to_string(), "-1.25");
+        assert_eq!(
+            (a / decimal("0.00")).unwrap_err().code,
+            ErrorCode::DivisionByZero
+        );
+        let max = decimal(&"9".repeat(38));
+        assert_eq!(
+            (max + decimal("1")).unwrap_err().code,
+            ErrorCode::NumericValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn conversion() {
+        assert_eq!(decimal("2.345").rescale(2).unwrap().to_string(), "2.35");
+        assert_eq!(decimal("-2.345").rescale(2).unwrap().to_string(), "-2.35");
+        assert_eq!(decimal("2.3").rescale(3).unwrap().to_string(), "2.300");
+        assert_eq!(
+            decimal("123.456").check(5, 2).unwrap().to_string(),
+            "123.46"
+        );
+        assert!(decimal("1234.5").check(5, 2).is_err());
+        assert_eq!(Decimal::from(Int32::new(-3)).to_string(), "-3");
+        assert_eq!(
+            Decimal::from_float64(Float64::new(0.125), 2)
+                .unwrap()
+                .to_string(),
+            "0.13"
+        );
+        assert!(Decimal::from_float64(Float64::new(f64::NAN), 2).is_err());
+        assert_eq!(decimal("0.5").to_float64(), Float64::new(0.5));
+    }
+
+    #[test]
+    fn ordering_and_hash() {
+        assert_eq!(decimal("1.0"), decimal("1.00"));
+        assert!(decimal("-1.5") < decimal("1"));
+        assert!(decimal("0.001") < decimal("0.01"));
+        // 放大后溢出的一方绝对值更大
+        let big = decimal(&"9".repeat(38));
+        assert!(big > decimal("0.5"));
+        assert!(-big < decimal("-0.5"));
+
+        let set: HashSet<Decimal> = ["1.0", "1.00", "1", "-0.0", "0"]
+            .into_iter()
+            .map(decimal)
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -1,8 +1,16 @@
-use std::{fmt::Display, ops::Add};
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
 
 use super::int32::Int32;
+use crate::{error::ErrorCode, executor::error::ExecuteError};
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+/// Float64 上的全序：0.0 与 -0.0 相等，所有 NaN 彼此相等且比其他数值都大
+/// 有限操作数的运算结果溢出为无穷大时返回错误，除以零时返回错误
+#[derive(Debug, Copy, Clone)]
 pub struct Float64 {
     value: f64,
 }
@@ -12,33 +20,233 @@ impl Float64 {
         Self { value }
     }
 
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
     pub fn to_i32(&self) -> Int32 {
         Int32::new(self.value as i32)
     }
 
-    pub fn to_string(&self) -> String {
-        self.value.to_string()
+    /// 规范化后的位表示，与全序中的相等关系一致
+    fn bits(&self) -> u64 {
+        if self.value.is_nan() {
+            f64::NAN.to_bits()
+        } else if self.value == 0.0 {
+            0
+        } else {
+            self.value.to_bits()
+        }
+    }
+
+    /// 计算 `self op rhs`，有限操作数的结果为无穷大时返回 NumericValueOutOfRange
+    fn checked(self, op: &str, rhs: Self, f: fn(f64, f64) -> f64) -> Result<Self, ExecuteError> {
+        let value: f64 = f(self.value, rhs.value);
+        if value.is_infinite() && self.value.is_finite() && rhs.value.is_finite() {
+            return Err(ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("float overflow in {} {} {}", self, op, rhs),
+            ));
+        }
+        Ok(Self::new(value))
+    }
+
+    /// 除数为零时返回 DivisionByZero
+    fn check_divisor(rhs: Self) -> Result<(), ExecuteError> {
+        match rhs.value == 0.0 {
+            true => Err(ExecuteError::new(
+                ErrorCode::DivisionByZero,
+                "division by zero",
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+impl PartialEq for Float64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float64 {}
+
+impl PartialOrd for Float64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.value.is_nan(), other.value.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.value.partial_cmp(&other.value).unwrap(),
+        }
+    }
+}
+
+impl Hash for Float64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state)
     }
 }
 
 impl Add for Float64 {
-    type Output = Self;
+    type Output = Result<Float64, ExecuteError>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            value: self.value + rhs.value,
-        }
+        self.checked("+", rhs, |l, r| l + r)
+    }
+}
+
+impl Sub for Float64 {
+    type Output = Result<Float64, ExecuteError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked("-", rhs, |l, r| l - r)
+    }
+}
+
+impl Mul for Float64 {
+    type Output = Result<Float64, ExecuteError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked("*", rhs, |l, r| l * r)
+    }
+}
+
+impl Div for Float64 {
+    type Output = Result<Float64, ExecuteError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        self.checked("/", rhs, |l, r| l / r)
     }
 }
 
+impl Rem for Float64 {
+    type Output = Result<Float64, ExecuteError>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        self.checked("%", rhs, |l, r| l % r)
+    }
+}
+
+impl Neg for Float64 {
+    type Output = Float64;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.value)
+    }
+}
+
+/// Int32 与 Float64 混合运算时，Int32 提升为 Float64，结果为 Float64
+macro_rules! promote {
+    ($($trait:ident $method:ident),*) => {$(
+        impl $trait<Float64> for Int32 {
+            type Output = Result<Float64, ExecuteError>;
+
+            fn $method(self, rhs: Float64) -> Self::Output {
+                self.to_float64().$method(rhs)
+            }
+        }
+
+        impl $trait<Int32> for Float64 {
+            type Output = Result<Float64, ExecuteError>;
+
+            fn $method(self, rhs: Int32) -> Self::Output {
+                self.$method(rhs.to_float64())
+            }
+        }
+    )*};
+}
+
+promote!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
 impl From<f64> for Float64 {
     fn from(value: f64) -> Self {
         Self { value }
     }
 }
 
+impl From<Int32> for Float64 {
+    fn from(value: Int32) -> Self {
+        value.to_float64()
+    }
+}
+
 impl Display for Float64 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::Float64;
+    use crate::{error::ErrorCode, executor::types::int32::Int32};
+
+    #[test]
+    fn checked_arithmetic() {
+        let (a, b) = (Float64::new(7.5), Float64::new(2.0));
+        assert_eq!((a + b).unwrap(), Float64::new(9.5));
+        assert_eq!((a - b).unwrap(), Float64::new(5.5));
+        assert_eq!((a * b).unwrap(), Float64::new(15.0));
+        assert_eq!((a / b).unwrap(), Float64::new(3.75));
+        assert_eq!((a % b).unwrap(), Float64::new(1.5));
+        assert_eq!(-a, Float64::new(-7.5));
+
+        let max = Float64::new(f64::MAX);
+        let overflow = (max * b).unwrap_err();
+        assert_eq!(overflow.code, ErrorCode::NumericValueOutOfRange);
+        assert!((max + max).is_err());
+        let infinity = Float64::new(f64::INFINITY);
+        assert_eq!((infinity + a).unwrap(), infinity);
+
+        let zero = Float64::new(-0.0);
+        assert_eq!((a / zero).unwrap_err().code, ErrorCode::DivisionByZero);
+        assert_eq!((a % zero).unwrap_err().code, ErrorCode::DivisionByZero);
+    }
+
+    #[test]
+    fn promotion() {
+        let (i, f) = (Int32::new(3), Float64::new(0.5));
+        assert_eq!((i + f).unwrap(), Float64::new(3.5));
+        assert_eq!((f - i).unwrap(), Float64::new(-2.5));
+        assert_eq!((i * f).unwrap(), Float64::new(1.5));
+        assert_eq!((f / i).unwrap(), Float64::new(0.5 / 3.0));
+        assert_eq!((i % Float64::new(2.0)).unwrap(), Float64::new(1.0));
+        assert_eq!(
+            (f / Int32::new(0)).unwrap_err().code,
+            ErrorCode::DivisionByZero
+        );
+        assert_eq!(Float64::from(i), Float64::new(3.0));
+    }
+
+    #[test]
+    fn total_order() {
+        let mut values: Vec<Float64> = [f64::NAN, 1.5, f64::NEG_INFINITY, -0.0, f64::INFINITY]
+            .into_iter()
+            .map(Float64::new)
+            .collect();
+        values.sort();
+        let sorted: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(sorted, vec!["-inf", "-0", "1.5", "inf", "NaN"]);
+
+        assert_eq!(Float64::new(f64::NAN), Float64::new(-f64::NAN));
+        assert_eq!(Float64::new(0.0), Float64::new(-0.0));
+        assert!(Float64::new(f64::NAN) > Float64::new(f64::INFINITY));
+
+        let set: HashSet<Float64> = [0.0, -0.0, f64::NAN, -f64::NAN, 1.0]
+            .into_iter()
+            .map(Float64::new)
+            .collect();
+        assert_eq!(set.len(), 3);
+    }
+}
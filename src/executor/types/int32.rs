@@ -1,8 +1,13 @@
-use std::{fmt::Display, ops::Add};
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
 
 use super::flout64::Float64;
+use crate::{error::ErrorCode, executor::error::ExecuteError};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// Int32 的算术运算都做溢出检查，溢出或除以零时返回错误
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Int32 {
     value: i32,
 }
@@ -12,22 +17,94 @@ impl Int32 {
         Self { value }
     }
 
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
     pub fn to_float64(&self) -> Float64 {
         Float64::new(self.value as f64)
     }
 
-    pub fn to_string(&self) -> String {
-        self.value.to_string()
+    /// 计算 `self op rhs`，结果溢出时返回 NumericValueOutOfRange
+    fn checked(
+        self,
+        op: &str,
+        rhs: Self,
+        f: fn(i32, i32) -> Option<i32>,
+    ) -> Result<Self, ExecuteError> {
+        f(self.value, rhs.value).map(Self::new).ok_or_else(|| {
+            ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("integer overflow in {} {} {}", self, op, rhs),
+            )
+        })
+    }
+
+    /// 除数为零时返回 DivisionByZero
+    fn check_divisor(rhs: Self) -> Result<(), ExecuteError> {
+        match rhs.value {
+            0 => Err(ExecuteError::new(
+                ErrorCode::DivisionByZero,
+                "division by zero",
+            )),
+            _ => Ok(()),
+        }
     }
 }
 
 impl Add for Int32 {
-    type Output = Int32;
+    type Output = Result<Int32, ExecuteError>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            value: self.value + rhs.value,
-        }
+        self.checked("+", rhs, i32::checked_add)
+    }
+}
+
+impl Sub for Int32 {
+    type Output = Result<Int32, ExecuteError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked("-", rhs, i32::checked_sub)
+    }
+}
+
+impl Mul for Int32 {
+    type Output = Result<Int32, ExecuteError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked("*", rhs, i32::checked_mul)
+    }
+}
+
+impl Div for Int32 {
+    type Output = Result<Int32, ExecuteError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        self.checked("/", rhs, i32::checked_div)
+    }
+}
+
+impl Rem for Int32 {
+    type Output = Result<Int32, ExecuteError>;
+
+    /// i32::MIN % -1 在数学上为 0，不视为溢出
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        Ok(Self::new(self.value.wrapping_rem(rhs.value)))
+    }
+}
+
+impl Neg for Int32 {
+    type Output = Result<Int32, ExecuteError>;
+
+    fn neg(self) -> Self::Output {
+        self.value.checked_neg().map(Self::new).ok_or_else(|| {
+            ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("integer overflow in -{}", self),
+            )
+        })
     }
 }
 
@@ -42,3 +119,41 @@ impl Display for Int32 {
         write!(f, "{}", self.value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Int32;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn checked_arithmetic() {
+        let (a, b) = (Int32::new(7), Int32::new(-2));
+        assert_eq!((a + b).unwrap(), Int32::new(5));
+        assert_eq!((a - b).unwrap(), Int32::new(9));
+        assert_eq!((a * b).unwrap(), Int32::new(-14));
+        assert_eq!((a / b).unwrap(), Int32::new(-3));
+        assert_eq!((a % b).unwrap(), Int32::new(1));
+        assert_eq!((-a).unwrap(), Int32::new(-7));
+
+        let (max, min) = (Int32::new(i32::MAX), Int32::new(i32::MIN));
+        let overflow = (max + Int32::new(1)).unwrap_err();
+        assert_eq!(overflow.code, ErrorCode::NumericValueOutOfRange);
+        assert_eq!(overflow.to_string(), "integer overflow in 2147483647 + 1");
+        assert!((min - Int32::new(1)).is_err());
+        assert!((max * Int32::new(2)).is_err());
+        assert!((min / Int32::new(-1)).is_err());
+        assert!((-min).is_err());
+        assert_eq!((min % Int32::new(-1)).unwrap(), Int32::new(0));
+
+        let zero = Int32::new(0);
+        assert_eq!((a / zero).unwrap_err().code, ErrorCode::DivisionByZero);
+        assert_eq!((a % zero).unwrap_err().code, ErrorCode::DivisionByZero);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values: Vec<Int32> = [3, -1, 2].into_iter().map(Int32::new).collect();
+        values.sort();
+        assert_eq!(values, vec![Int32::new(-1), Int32::new(2), Int32::new(3)]);
+    }
+}
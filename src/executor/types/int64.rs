@@ -0,0 +1,166 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use super::{flout64::Float64, int32::Int32};
+use crate::{error::ErrorCode, executor::error::ExecuteError};
+
+/// Int64 的算术运算都做溢出检查，溢出或除以零时返回错误
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Int64 {
+    value: i64,
+}
+
+impl Int64 {
+    pub fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn to_float64(&self) -> Float64 {
+        Float64::new(self.value as f64)
+    }
+
+    /// 转换为 Int32，超出范围时返回 NumericValueOutOfRange
+    pub fn to_int32(&self) -> Result<Int32, ExecuteError> {
+        match i32::try_from(self.value) {
+            Ok(value) => Ok(Int32::new(value)),
+            Err(_) => Err(ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("integer {} out of range for type Int32", self),
+            )),
+        }
+    }
+
+    /// 计算 `self op rhs`，结果溢出时返回 NumericValueOutOfRange
+    fn checked(
+        self,
+        op: &str,
+        rhs: Self,
+        f: fn(i64, i64) -> Option<i64>,
+    ) -> Result<Self, ExecuteError> {
+        f(self.value, rhs.value).map(Self::new).ok_or_else(|| {
+            ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("bigint overflow in {} {} {}", self, op, rhs),
+            )
+        })
+    }
+
+    /// 除数为零时返回 DivisionByZero
+    fn check_divisor(rhs: Self) -> Result<(), ExecuteError> {
+        match rhs.value {
+            0 => Err(ExecuteError::new(
+                ErrorCode::DivisionByZero,
+                "division by zero",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Add for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked("+", rhs, i64::checked_add)
+    }
+}
+
+impl Sub for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked("-", rhs, i64::checked_sub)
+    }
+}
+
+impl Mul for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked("*", rhs, i64::checked_mul)
+    }
+}
+
+impl Div for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        self.checked("/", rhs, i64::checked_div)
+    }
+}
+
+impl Rem for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    /// i64::MIN % -1 在数学上为 0，不视为溢出
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self::check_divisor(rhs)?;
+        Ok(Self::new(self.value.wrapping_rem(rhs.value)))
+    }
+}
+
+impl Neg for Int64 {
+    type Output = Result<Int64, ExecuteError>;
+
+    fn neg(self) -> Self::Output {
+        self.value.checked_neg().map(Self::new).ok_or_else(|| {
+            ExecuteError::new(
+                ErrorCode::NumericValueOutOfRange,
+                format!("bigint overflow in -{}", self),
+            )
+        })
+    }
+}
+
+impl From<i64> for Int64 {
+    fn from(value: i64) -> Self {
+        Self { value }
+    }
+}
+
+impl From<Int32> for Int64 {
+    fn from(value: Int32) -> Self {
+        Self::new(value.value() as i64)
+    }
+}
+
+impl Display for Int64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Int64;
+    use crate::{error::ErrorCode, executor::types::int32::Int32};
+
+    #[test]
+    fn checked_arithmetic() {
+        let (a, b) = (Int64::new(5_000_000_000), Int64::new(-3));
+        assert_eq!((a + b).unwrap(), Int64::new(4_999_999_997));
+        assert_eq!((a * b).unwrap(), Int64::new(-15_000_000_000));
+        assert_eq!((a / b).unwrap(), Int64::new(-1_666_666_666));
+        assert_eq!((a % b).unwrap(), Int64::new(2));
+
+        let max = Int64::new(i64::MAX);
+        let overflow = (max + Int64::new(1)).unwrap_err();
+        assert_eq!(overflow.code, ErrorCode::NumericValueOutOfRange);
+        assert!((-Int64::new(i64::MIN)).is_err());
+        assert_eq!(
+            (a / Int64::new(0)).unwrap_err().code,
+            ErrorCode::DivisionByZero
+        );
+
+        assert_eq!(Int64::from(Int32::new(-7)), Int64::new(-7));
+        assert_eq!(Int64::new(7).to_int32().unwrap(), Int32::new(7));
+        assert!(a.to_int32().is_err());
+    }
+}
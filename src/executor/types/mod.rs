@@ -1,32 +1,185 @@
 use std::fmt::Display;
 
+use crate::sql::parser::ast::create_table::ColumnType;
+
+pub mod blob;
+pub mod datetime;
+pub mod decimal;
 pub mod flout64;
 pub mod int32;
+pub mod int64;
 pub mod varchar;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum DataType {
+    Boolean,
     Int32,
+    Int64,
+    Decimal(u8, u8), // 精度（总位数）和小数位数
     Float64,
     Varchar(u16), // 不拥有数据
     String,       // 拥有数据，作为中间数据
+    Date,
+    Time,
+    Timestamp,
+    Interval,
+    Blob,
+    Null, // NULL 字面量的类型，可以作为任何类型使用
+}
+
+impl DataType {
+    /// 是否为数值类型，可以参与算术运算
+    pub fn is_numeric(&self) -> bool {
+        self.numeric_rank().is_some()
+    }
+
+    /// 数值类型的提升顺序 Int32 < Int64 < Decimal < Float64，混合运算时结果为较大的类型
+    pub fn numeric_rank(&self) -> Option<u8> {
+        match self {
+            DataType::Int32 => Some(0),
+            DataType::Int64 => Some(1),
+            DataType::Decimal(_, _) => Some(2),
+            DataType::Float64 => Some(3),
+            _ => None,
+        }
+    }
+
+    /// 数值类型的小数位数，整数为 0
+    pub fn scale(&self) -> u8 {
+        match self {
+            DataType::Decimal(_, scale) => *scale,
+            _ => 0,
+        }
+    }
+
+    /// 是否为字符串类型
+    pub fn is_string(&self) -> bool {
+        matches!(self, DataType::Varchar(_) | DataType::String)
+    }
+
+    /// 是否为 NULL 字面量的类型
+    pub fn is_null(&self) -> bool {
+        matches!(self, DataType::Null)
+    }
+}
+
+impl From<&ColumnType> for DataType {
+    fn from(column_type: &ColumnType) -> Self {
+        match column_type {
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Int32 => DataType::Int32,
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Decimal(precision, scale) => DataType::Decimal(*precision, *scale),
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Varchar(len) => DataType::Varchar(*len),
+            ColumnType::String => DataType::String,
+            ColumnType::Date => DataType::Date,
+            ColumnType::Time => DataType::Time,
+            ColumnType::Timestamp => DataType::Timestamp,
+            ColumnType::Interval => DataType::Interval,
+            ColumnType::Blob => DataType::Blob,
+        }
+    }
 }
 
 impl Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            DataType::Boolean => write!(f, "Boolean"),
             DataType::Int32 => write!(f, "Int32"),
+            DataType::Int64 => write!(f, "Int64"),
+            DataType::Decimal(precision, scale) => write!(f, "Decimal({},{})", precision, scale),
             DataType::Float64 => write!(f, "Float64"),
             DataType::Varchar(len) => write!(f, "Varchar({})", len),
             DataType::String => write!(f, "String"),
+            DataType::Date => write!(f, "Date"),
+            DataType::Time => write!(f, "Time"),
+            DataType::Timestamp => write!(f, "Timestamp"),
+            DataType::Interval => write!(f, "Interval"),
+            DataType::Blob => write!(f, "Blob"),
+            DataType::Null => write!(f, "Null"),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum OwnValue {
+    Boolean(bool),
     Int32(int32::Int32),
+    Int64(int64::Int64),
+    Decimal(decimal::Decimal),
     Float64(flout64::Float64),
     String(String),
+    Date(datetime::Date),
+    Time(datetime::Time),
+    Timestamp(datetime::Timestamp),
+    Interval(datetime::Interval),
+    Blob(Vec<u8>),
     Null,
 }
+
+impl OwnValue {
+    /// 值的类型，字符串都是 String，十进制数的精度为最大精度
+    pub fn data_type(&self) -> DataType {
+        match self {
+            OwnValue::Boolean(_) => DataType::Boolean,
+            OwnValue::Int32(_) => DataType::Int32,
+            OwnValue::Int64(_) => DataType::Int64,
+            OwnValue::Decimal(v) => DataType::Decimal(decimal::MAX_PRECISION, v.scale()),
+            OwnValue::Float64(_) => DataType::Float64,
+            OwnValue::String(_) => DataType::String,
+            OwnValue::Date(_) => DataType::Date,
+            OwnValue::Time(_) => DataType::Time,
+            OwnValue::Timestamp(_) => DataType::Timestamp,
+            OwnValue::Interval(_) => DataType::Interval,
+            OwnValue::Blob(_) => DataType::Blob,
+            OwnValue::Null => DataType::Null,
+        }
+    }
+
+    /// 整数转换为 Int64，其余类型返回 None
+    pub fn to_int64(&self) -> Option<int64::Int64> {
+        match self {
+            OwnValue::Int32(v) => Some((*v).into()),
+            OwnValue::Int64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// 整数和十进制数转换为 Decimal，其余类型返回 None
+    pub fn to_decimal(&self) -> Option<decimal::Decimal> {
+        match self {
+            OwnValue::Decimal(v) => Some(*v),
+            _ => self.to_int64().map(Into::into),
+        }
+    }
+
+    /// 数值转换为 Float64，其余类型返回 None
+    pub fn to_float64(&self) -> Option<flout64::Float64> {
+        match self {
+            OwnValue::Float64(v) => Some(*v),
+            OwnValue::Decimal(v) => Some(v.to_float64()),
+            _ => self.to_int64().map(|v| v.to_float64()),
+        }
+    }
+}
+
+/// 按 SQL 字面量的形式显示
+impl Display for OwnValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OwnValue::Boolean(v) => write!(f, "{}", if *v { "TRUE" } else { "FALSE" }),
+            OwnValue::Int32(v) => write!(f, "{}", v),
+            OwnValue::Int64(v) => write!(f, "{}", v),
+            OwnValue::Decimal(v) => write!(f, "DECIMAL '{}'", v),
+            OwnValue::Float64(v) => write!(f, "{}", v),
+            OwnValue::String(s) => write!(f, "'{}'", s),
+            OwnValue::Date(v) => write!(f, "DATE '{}'", v),
+            OwnValue::Time(v) => write!(f, "TIME '{}'", v),
+            OwnValue::Timestamp(v) => write!(f, "TIMESTAMP '{}'", v),
+            OwnValue::Interval(v) => write!(f, "INTERVAL '{}'", v),
+            OwnValue::Blob(v) => write!(f, "X'{}'", blob::to_hex(v)),
+            OwnValue::Null => write!(f, "NULL"),
+        }
+    }
+}
@@ -1,41 +1,85 @@
-use std::{fmt::Display, marker::PhantomData};
+use std::fmt::Display;
 
-/// Varchar 字符串，不持有所有权
+use crate::{error::ErrorCode, executor::error::ExecuteError};
+
+use super::DataType;
+
+/// Varchar 借用的字符串，不持有所有权
+/// 按字符串的字节序比较，与 OwnValue::String 的比较一致
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Varchar<'a> {
-    pointer: *const u8,
-    size: u16,
-    _marker: PhantomData<&'a ()>,
+    value: &'a str,
 }
 
 impl<'a> Varchar<'a> {
-    pub fn from_str(s: &'a str) -> Self {
-        Self {
-            size: s.len() as u16,
-            pointer: s.as_ptr(),
-            _marker: PhantomData,
-        }
+    /// 检查 s 不超过 VARCHAR(limit) 的字符数上限
+    pub fn new(s: &'a str, limit: u16) -> Result<Self, ExecuteError> {
+        Self::ref_string(s).check(limit)
     }
 
-    pub fn as_str(&'a self) -> &'a str {
-        unsafe {
-            std::str::from_utf8_unchecked(std::slice::from_raw_parts(
-                self.pointer,
-                self.size as usize,
-            ))
+    /// 不检查长度，用于读取已经检查过的值
+    pub fn ref_string(s: &'a str) -> Self {
+        Self { value: s }
+    }
+
+    /// 字符数超过 limit 时返回错误
+    pub fn check(self, limit: u16) -> Result<Self, ExecuteError> {
+        if self.value.chars().nth(limit as usize).is_some() {
+            return Err(ExecuteError::new(
+                ErrorCode::StringDataRightTruncation,
+                format!("value too long for type {}", DataType::Varchar(limit)),
+            ));
         }
+        Ok(self)
     }
 
-    pub fn ref_string(s: &'a String) -> Self {
-        Self::from_str(s.as_str())
+    pub fn as_str(&self) -> &'a str {
+        self.value
     }
 
-    pub fn to_string(&self) -> String {
-        self.as_str().to_string()
+    /// 字符数，不是字节数
+    pub fn char_count(&self) -> usize {
+        self.value.chars().count()
     }
 }
 
 impl<'a> Display for Varchar<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
+        write!(f, "{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Varchar;
+
+    #[test]
+    fn varchar_limit() {
+        // 按字符计数
+        assert_eq!(Varchar::new("张三", 2).unwrap().as_str(), "张三");
+        assert_eq!(
+            Varchar::new("张三", 1).unwrap_err().to_string(),
+            "value too long for type Varchar(1)"
+        );
+        assert_eq!(Varchar::new("", 0).unwrap().char_count(), 0);
+
+        // 超过 u16 字节数的字符串也不会被截断
+        let long: String = "a".repeat(70000);
+        assert_eq!(Varchar::ref_string(&long).as_str().len(), 70000);
+        assert!(Varchar::new(&long, u16::MAX).is_err());
+    }
+
+    #[test]
+    fn varchar_compare() {
+        let (a, b) = (String::from("abc"), String::from("abd"));
+        assert!(Varchar::ref_string(&a) < Varchar::ref_string(&b));
+        assert_eq!(Varchar::ref_string(&a), Varchar::ref_string("abc"));
+        let set: HashSet<Varchar> = [&a, &b, &a]
+            .into_iter()
+            .map(|s| Varchar::ref_string(s))
+            .collect();
+        assert_eq!(set.len(), 2);
     }
 }
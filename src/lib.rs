@@ -1,3 +1,9 @@
 pub mod sql;
 
 pub mod executor;
+
+/// 数据库入口，串联词法分析、语法分析、计划生成与执行
+pub mod database;
+
+/// 顶层错误类型
+pub mod error;
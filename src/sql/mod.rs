@@ -1,4 +1,4 @@
-/// sql 模块，定义词法分析 tokenizer 和 语法分析 parser
+//! sql 模块，定义词法分析 tokenizer 和 语法分析 parser
 
 /// 词法分析 tokenizer 负责将 sql 语句转换为 token 流
 pub mod tokenizer;
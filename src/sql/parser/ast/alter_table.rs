@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use super::{
+    create_table::ColumnDefinition,
+    expression::Expression,
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+};
+
+/// ALTER TABLE name operation
+#[derive(Debug, PartialEq, Clone)]
+pub struct AlterTable {
+    pub name: SingleIdentifier,
+    pub operation: AlterTableOperation,
+    pub leaf: Location, // ALTER 关键字的位置
+}
+
+impl WithLocation for AlterTable {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for AlterTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ALTER TABLE {} {}", self.name.value, self.operation)
+    }
+}
+
+/// ALTER TABLE 支持的修改
+#[derive(Debug, PartialEq, Clone)]
+pub enum AlterTableOperation {
+    /// ADD [COLUMN] column type [NOT NULL | NULL] [DEFAULT expression]
+    AddColumn {
+        column: ColumnDefinition,
+        default: Option<Expression>,
+    },
+    /// DROP [COLUMN] column
+    DropColumn(SingleIdentifier),
+    /// RENAME [COLUMN] from TO to
+    RenameColumn {
+        from: SingleIdentifier,
+        to: SingleIdentifier,
+    },
+    /// RENAME TO name
+    RenameTable(SingleIdentifier),
+}
+
+impl Display for AlterTableOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlterTableOperation::AddColumn { column, default } => {
+                write!(f, "ADD COLUMN {}", column)?;
+                if let Some(default) = default {
+                    write!(f, " DEFAULT {}", default)?;
+                }
+                Ok(())
+            }
+            AlterTableOperation::DropColumn(column) => write!(f, "DROP COLUMN {}", column.value),
+            AlterTableOperation::RenameColumn { from, to } => {
+                write!(f, "RENAME COLUMN {} TO {}", from.value, to.value)
+            }
+            AlterTableOperation::RenameTable(name) => write!(f, "RENAME TO {}", name.value),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use super::{
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+};
+
+/// CREATE TABLE name (column type [NOT NULL | NULL], ...)
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreateTable {
+    pub name: SingleIdentifier,
+    pub columns: Box<[ColumnDefinition]>,
+    pub leaf: Location, // CREATE 关键字的位置
+}
+
+impl WithLocation for CreateTable {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for CreateTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE TABLE {} (", self.name.value)?;
+        for (index, column) in self.columns.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", column)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// 列定义
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnDefinition {
+    pub name: SingleIdentifier,
+    pub column_type: ColumnType,
+    pub not_null: bool,
+}
+
+impl WithLocation for ColumnDefinition {
+    fn location(&self) -> &Location {
+        self.name.location()
+    }
+}
+
+impl Display for ColumnDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name.value, self.column_type)?;
+        if self.not_null {
+            write!(f, " NOT NULL")?;
+        }
+        Ok(())
+    }
+}
+
+/// 列类型，类型名不区分大小写
+/// BOOLEAN / BOOL -> Boolean
+/// INT / INTEGER / INT32 -> Int32
+/// BIGINT / INT64 -> Int64
+/// DECIMAL / NUMERIC [(p[, s])] -> Decimal(p, s)，默认为 (38, 0)
+/// FLOAT / DOUBLE / FLOAT64 -> Float64
+/// VARCHAR(n) -> Varchar(n)
+/// TEXT / STRING -> String
+/// DATE、TIME、TIMESTAMP、INTERVAL -> 同名类型
+/// BLOB / BYTEA -> Blob
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ColumnType {
+    Boolean,
+    Int32,
+    Int64,
+    Decimal(u8, u8),
+    Float64,
+    Varchar(u16),
+    String,
+    Date,
+    Time,
+    Timestamp,
+    Interval,
+    Blob,
+}
+
+impl ColumnType {
+    /// DECIMAL 的最大精度
+    pub const MAX_DECIMAL_PRECISION: u8 = 38;
+
+    /// 按类型名查找不带参数的类型
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "BOOLEAN" | "BOOL" => Some(ColumnType::Boolean),
+            "INT" | "INTEGER" | "INT32" => Some(ColumnType::Int32),
+            "BIGINT" | "INT64" => Some(ColumnType::Int64),
+            "DECIMAL" | "NUMERIC" => Some(ColumnType::Decimal(Self::MAX_DECIMAL_PRECISION, 0)),
+            "FLOAT" | "DOUBLE" | "FLOAT64" => Some(ColumnType::Float64),
+            "TEXT" | "STRING" => Some(ColumnType::String),
+            "DATE" => Some(ColumnType::Date),
+            "TIME" => Some(ColumnType::Time),
+            "TIMESTAMP" => Some(ColumnType::Timestamp),
+            "INTERVAL" => Some(ColumnType::Interval),
+            "BLOB" | "BYTEA" => Some(ColumnType::Blob),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ColumnType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnType::Boolean => write!(f, "BOOLEAN"),
+            ColumnType::Int32 => write!(f, "INT"),
+            ColumnType::Int64 => write!(f, "BIGINT"),
+            ColumnType::Decimal(precision, scale) => write!(f, "DECIMAL({},{})", precision, scale),
+            ColumnType::Float64 => write!(f, "DOUBLE"),
+            ColumnType::Varchar(len) => write!(f, "VARCHAR({})", len),
+            ColumnType::String => write!(f, "TEXT"),
+            ColumnType::Date => write!(f, "DATE"),
+            ColumnType::Time => write!(f, "TIME"),
+            ColumnType::Timestamp => write!(f, "TIMESTAMP"),
+            ColumnType::Interval => write!(f, "INTERVAL"),
+            ColumnType::Blob => write!(f, "BLOB"),
+        }
+    }
+}
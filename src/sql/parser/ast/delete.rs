@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use super::{
+    expression::Expression,
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+};
+
+/// DELETE FROM table [WHERE expression]
+#[derive(Debug, PartialEq, Clone)]
+pub struct Delete {
+    pub table: SingleIdentifier,
+    pub wheres: Option<Expression>,
+    pub leaf: Location, // DELETE 关键字的位置
+}
+
+impl WithLocation for Delete {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Delete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DELETE FROM {}", self.table.value)?;
+        if let Some(wheres) = &self.wheres {
+            write!(f, " WHERE {}", wheres)?;
+        }
+        Ok(())
+    }
+}
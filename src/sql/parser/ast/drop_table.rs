@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use super::{
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+};
+
+/// DROP TABLE [IF EXISTS] name
+#[derive(Debug, PartialEq, Clone)]
+pub struct DropTable {
+    pub name: SingleIdentifier,
+    pub if_exists: bool,
+    pub leaf: Location, // DROP 关键字的位置
+}
+
+impl WithLocation for DropTable {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for DropTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP TABLE ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name.value)
+    }
+}
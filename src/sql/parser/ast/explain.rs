@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use super::{
+    leaf::{Location, WithLocation},
+    Select,
+};
+
+/// EXPLAIN [ANALYZE] select
+/// ANALYZE 时执行查询，并统计每个算子的执行情况
+#[derive(Debug, PartialEq, Clone)]
+pub struct Explain {
+    pub analyze: bool,
+    pub select: Select,
+    pub leaf: Location, // EXPLAIN 关键字的位置
+}
+
+impl WithLocation for Explain {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Explain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EXPLAIN ")?;
+        if self.analyze {
+            write!(f, "ANALYZE ")?;
+        }
+        write!(f, "{}", self.select)
+    }
+}
@@ -15,6 +15,7 @@ pub enum Expression {
     UnaryExpression(UnaryExpression),   // -1
     Function(Function),                 // COUNT(*)
     SubQuery(Box<Select>),              // (SELECT * FROM tab1)
+    Placeholder(Placeholder),           // ?
 }
 
 impl WithLocation for Expression {
@@ -26,6 +27,7 @@ impl WithLocation for Expression {
             Expression::UnaryExpression(unary_expression) => unary_expression.location(),
             Expression::Function(function) => function.location(),
             Expression::SubQuery(select) => select.location(),
+            Expression::Placeholder(placeholder) => placeholder.location(),
         }
     }
 }
@@ -39,10 +41,30 @@ impl Display for Expression {
             Expression::UnaryExpression(unary_expression) => write!(f, "{}", unary_expression),
             Expression::Function(function) => write!(f, "{}", function),
             Expression::SubQuery(sub_query) => write!(f, "({})", sub_query),
+            Expression::Placeholder(placeholder) => write!(f, "{}", placeholder),
         }
     }
 }
 
+/// 参数占位符 ?，index 为在整段 SQL 中出现的序号，从 0 开始
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Placeholder {
+    pub index: usize,
+    pub leaf: Location,
+}
+
+impl WithLocation for Placeholder {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Placeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "?")
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Function {
     pub name: Identifier,
@@ -120,7 +142,11 @@ impl WithLocation for UnaryExpression {
 
 impl Display for UnaryExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.operator, self.expression)
+        if self.operator.is_postfix() {
+            write!(f, "{} {}", self.expression, self.operator)
+        } else {
+            write!(f, "{} {}", self.operator, self.expression)
+        }
     }
 }
 
@@ -130,6 +156,7 @@ pub enum BinaryOperator {
     Minus(Location),
     Multiply(Location),
     Divide(Location),
+    Modulo(Location),
     Equal(Location),
     NotEqual(Location),
     GreaterThan(Location),
@@ -147,6 +174,7 @@ impl BinaryOperator {
             BinaryOperator::Minus(_) => 1000,
             BinaryOperator::Multiply(_) => 1010,
             BinaryOperator::Divide(_) => 1010,
+            BinaryOperator::Modulo(_) => 1010,
             BinaryOperator::Equal(_) => 100,
             BinaryOperator::NotEqual(_) => 105,
             BinaryOperator::GreaterThan(_) => 110,
@@ -166,6 +194,7 @@ impl WithLocation for BinaryOperator {
             BinaryOperator::Minus(location) => location,
             BinaryOperator::Multiply(location) => location,
             BinaryOperator::Divide(location) => location,
+            BinaryOperator::Modulo(location) => location,
             BinaryOperator::Equal(location) => location,
             BinaryOperator::NotEqual(location) => location,
             BinaryOperator::GreaterThan(location) => location,
@@ -185,6 +214,7 @@ impl Display for BinaryOperator {
             BinaryOperator::Minus(_) => write!(f, "-"),
             BinaryOperator::Multiply(_) => write!(f, "*"),
             BinaryOperator::Divide(_) => write!(f, "/"),
+            BinaryOperator::Modulo(_) => write!(f, "%"),
             BinaryOperator::Equal(_) => write!(f, "="),
             BinaryOperator::NotEqual(_) => write!(f, "<>"),
             BinaryOperator::GreaterThan(_) => write!(f, ">"),
@@ -202,6 +232,27 @@ pub enum UnaryOperator {
     Plus(Location),
     Minus(Location),
     NOT(Location),
+    IsNull(Location),    // 后缀 IS NULL
+    IsNotNull(Location), // 后缀 IS NOT NULL
+}
+
+impl UnaryOperator {
+    /// 是否为写在操作数之后的运算符
+    pub fn is_postfix(&self) -> bool {
+        matches!(self, UnaryOperator::IsNull(_) | UnaryOperator::IsNotNull(_))
+    }
+
+    /// 后缀运算符的优先级，低于比较运算，高于 AND、OR
+    /// a = b IS NULL 即 (a = b) IS NULL
+    pub fn priority(&self) -> usize {
+        match self {
+            UnaryOperator::IsNull(_) | UnaryOperator::IsNotNull(_) => 50,
+            // NOT 低于比较运算，高于 AND
+            UnaryOperator::NOT(_) => 20,
+            // 正负号只作用于紧随其后的操作数
+            UnaryOperator::Plus(_) | UnaryOperator::Minus(_) => usize::MAX,
+        }
+    }
 }
 
 impl WithLocation for UnaryOperator {
@@ -210,6 +261,8 @@ impl WithLocation for UnaryOperator {
             UnaryOperator::Plus(location) => location,
             UnaryOperator::Minus(location) => location,
             UnaryOperator::NOT(location) => location,
+            UnaryOperator::IsNull(location) => location,
+            UnaryOperator::IsNotNull(location) => location,
         }
     }
 }
@@ -220,6 +273,8 @@ impl Display for UnaryOperator {
             UnaryOperator::Plus(_) => write!(f, "+"),
             UnaryOperator::Minus(_) => write!(f, "-"),
             UnaryOperator::NOT(_) => write!(f, "NOT"),
+            UnaryOperator::IsNull(_) => write!(f, "IS NULL"),
+            UnaryOperator::IsNotNull(_) => write!(f, "IS NOT NULL"),
         }
     }
 }
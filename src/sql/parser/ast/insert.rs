@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use super::{
+    expression::Expression,
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+    Select,
+};
+
+/// INSERT INTO table [(column, ...)] VALUES (expression, ...), ... | SELECT ...
+#[derive(Debug, PartialEq, Clone)]
+pub struct Insert {
+    pub table: SingleIdentifier,
+    pub columns: Box<[SingleIdentifier]>, // 为空表示按表定义的顺序插入所有列
+    pub source: InsertSource,
+    pub leaf: Location, // INSERT 关键字的位置
+}
+
+impl WithLocation for Insert {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Insert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "INSERT INTO {}", self.table.value)?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            for (index, column) in self.columns.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", column.value)?;
+            }
+            write!(f, ")")?;
+        }
+        write!(f, " {}", self.source)
+    }
+}
+
+/// 插入的数据来源
+#[derive(Debug, PartialEq, Clone)]
+pub enum InsertSource {
+    Values(Box<[Box<[Expression]>]>),
+    Select(Box<Select>),
+}
+
+impl Display for InsertSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InsertSource::Values(rows) => {
+                write!(f, "VALUES ")?;
+                for (index, row) in rows.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "(")?;
+                    for (index, expression) in row.iter().enumerate() {
+                        if index > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", expression)?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            InsertSource::Select(select) => write!(f, "{}", select),
+        }
+    }
+}
@@ -9,13 +9,7 @@ pub trait WithLocation {
     fn locate(&self, raw_sql: &str) -> String {
         let loc = self.location();
         const SKIP_BACKWARD: usize = 16;
-        let skip: usize = {
-            if loc.offset > SKIP_BACKWARD {
-                loc.offset - SKIP_BACKWARD
-            } else {
-                0
-            }
-        };
+        let skip: usize = loc.offset.saturating_sub(SKIP_BACKWARD);
         let near: String = raw_sql.chars().skip(skip).take(SKIP_BACKWARD * 2).collect();
         format!(
             "Ln {}, Col {} near \"{}\"",
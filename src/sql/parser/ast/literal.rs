@@ -14,11 +14,20 @@ impl WithLocation for Literal {
     }
 }
 
+/// 字面量的值，带类型名的字面量（如 DATE '2024-01-01'）保存原始字符串，绑定时再解析
 #[derive(Debug, PartialEq, Clone)]
 pub enum Value {
     String(Rc<str>),
     Integer(u64),
     Float(f64),
+    Boolean(bool),
+    Decimal(Rc<str>),   // DECIMAL '1.50'
+    Date(Rc<str>),      // DATE '2024-01-01'
+    Time(Rc<str>),      // TIME '12:00:00'
+    Timestamp(Rc<str>), // TIMESTAMP '2024-01-01 12:00:00'
+    Interval(Rc<str>),  // INTERVAL '1 day'
+    Blob(Rc<str>),      // X'0AFF'，十六进制串
+    Null,
 }
 
 impl Display for Literal {
@@ -27,6 +36,14 @@ impl Display for Literal {
             Value::String(s) => write!(f, "'{}'", s),
             Value::Integer(n) => write!(f, "{}", n),
             Value::Float(n) => write!(f, "{}", n),
+            Value::Boolean(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            Value::Decimal(s) => write!(f, "DECIMAL '{}'", s),
+            Value::Date(s) => write!(f, "DATE '{}'", s),
+            Value::Time(s) => write!(f, "TIME '{}'", s),
+            Value::Timestamp(s) => write!(f, "TIMESTAMP '{}'", s),
+            Value::Interval(s) => write!(f, "INTERVAL '{}'", s),
+            Value::Blob(s) => write!(f, "X'{}'", s),
+            Value::Null => write!(f, "NULL"),
         }
     }
 }
@@ -1,13 +1,27 @@
+pub mod alter_table;
+pub mod create_table;
+pub mod delete;
+pub mod drop_table;
+pub mod explain;
 pub mod expression;
 pub mod identifier;
+pub mod insert;
 pub mod leaf;
 pub mod literal;
 pub mod select;
+pub mod update;
 
 use std::fmt::Display;
 
+pub use alter_table::AlterTable;
+pub use create_table::CreateTable;
+pub use delete::Delete;
+pub use drop_table::DropTable;
+pub use explain::Explain;
+pub use insert::Insert;
 use leaf::{Location, WithLocation};
 pub use select::Select;
+pub use update::Update;
 
 /// Statements SQL 语句，一条 SQL 语句可能包含多条语句
 #[derive(Debug, PartialEq, Clone)]
@@ -20,9 +34,16 @@ pub struct Statements {
 
 /// Statement 定义各种 SQL 语句类型
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum Statement {
     Select(Select),
-    CreateTable,
+    CreateTable(CreateTable),
+    DropTable(DropTable),
+    AlterTable(AlterTable),
+    Insert(Insert),
+    Update(Update),
+    Delete(Delete),
+    Explain(Explain),
     Empty(Location),
 }
 
@@ -30,7 +51,13 @@ impl WithLocation for Statement {
     fn location(&self) -> &Location {
         match self {
             Statement::Select(select) => select.location(),
-            Statement::CreateTable => todo!(),
+            Statement::CreateTable(create_table) => create_table.location(),
+            Statement::DropTable(drop_table) => drop_table.location(),
+            Statement::AlterTable(alter_table) => alter_table.location(),
+            Statement::Insert(insert) => insert.location(),
+            Statement::Update(update) => update.location(),
+            Statement::Delete(delete) => delete.location(),
+            Statement::Explain(explain) => explain.location(),
             Statement::Empty(location) => location,
         }
     }
@@ -40,7 +67,13 @@ impl Display for Statement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Statement::Select(select) => write!(f, "{};", select),
-            Statement::CreateTable => write!(f, "CREATE TABLE"),
+            Statement::CreateTable(create_table) => write!(f, "{};", create_table),
+            Statement::DropTable(drop_table) => write!(f, "{};", drop_table),
+            Statement::AlterTable(alter_table) => write!(f, "{};", alter_table),
+            Statement::Insert(insert) => write!(f, "{};", insert),
+            Statement::Update(update) => write!(f, "{};", update),
+            Statement::Delete(delete) => write!(f, "{};", delete),
+            Statement::Explain(explain) => write!(f, "{};", explain),
             Statement::Empty(_) => write!(f, ";"),
         }
     }
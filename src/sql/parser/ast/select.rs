@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use super::expression::{Alias, Expression};
-use super::identifier::Identifier;
+use super::identifier::{Identifier, SingleIdentifier};
 use super::leaf::{Location, WithLocation};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -31,7 +31,7 @@ impl Display for Select {
             }
             write!(f, "{}", item)?;
         }
-        if self.from.len() > 0 {
+        if !self.from.is_empty() {
             write!(f, " FROM ")?;
             for (index, identifier) in self.from.iter().enumerate() {
                 if index > 0 {
@@ -43,7 +43,7 @@ impl Display for Select {
         if let Some(wheres) = &self.wheres {
             write!(f, " WHERE {}", wheres)?;
         }
-        if self.group_by.len() > 0 {
+        if !self.group_by.is_empty() {
             write!(f, " GROUP BY ")?;
             for (index, identifier) in self.group_by.iter().enumerate() {
                 if index > 0 {
@@ -55,7 +55,7 @@ impl Display for Select {
         if let Some(having) = &self.having {
             write!(f, " HAVING {}", having)?;
         }
-        if self.order_by.len() > 0 {
+        if !self.order_by.is_empty() {
             write!(f, " ORDER BY ")?;
             for (index, order_by) in self.order_by.iter().enumerate() {
                 if index > 0 {
@@ -65,10 +65,10 @@ impl Display for Select {
             }
         }
         if let Some(limit) = &self.limit {
-            write!(f, " LIMIT {}", limit)?;
+            write!(f, " {}", limit)?;
         }
         if let Some(offset) = &self.offset {
-            write!(f, " OFFSET {}", offset)?;
+            write!(f, " {}", offset)?;
         }
         Ok(())
     }
@@ -98,10 +98,13 @@ impl Display for SelectItem {
     }
 }
 
+/// FROM 子句中的一项：表或者子查询，以及之后依次连接的表
+/// a JOIN b ON ... JOIN c ON ... 为左深树 ((a JOIN b) JOIN c)
 #[derive(Debug, PartialEq, Clone)]
 pub struct FromItem {
     pub expression: Expression,
     pub alias: Option<Identifier>,
+    pub joins: Box<[Join]>,
 }
 
 impl WithLocation for FromItem {
@@ -116,14 +119,95 @@ impl Display for FromItem {
         if let Some(alias) = &self.alias {
             write!(f, " AS {}", alias)?;
         }
+        for join in self.joins.iter() {
+            write!(f, " {}", join)?;
+        }
         Ok(())
     }
 }
 
+/// [INNER | LEFT [OUTER] | RIGHT [OUTER] | FULL [OUTER] | CROSS] JOIN item [ON expression | USING (column, ...)]
+/// CROSS JOIN 没有连接条件，其余连接必须有连接条件
+/// item 为连接的表或者子查询，item.joins 为空
+#[derive(Debug, PartialEq, Clone)]
+pub struct Join {
+    pub join_type: JoinType,
+    pub item: FromItem,
+    pub constraint: Option<JoinConstraint>,
+    pub leaf: Location, // JOIN 关键字的位置
+}
+
+impl WithLocation for Join {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Join {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} JOIN {}", self.join_type, self.item)?;
+        match &self.constraint {
+            Some(JoinConstraint::On(expression)) => write!(f, " ON {}", expression),
+            Some(JoinConstraint::Using(columns)) => {
+                write!(f, " USING (")?;
+                for (index, column) in columns.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", column.value)?;
+                }
+                write!(f, ")")
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+impl Display for JoinType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinType::Inner => write!(f, "INNER"),
+            JoinType::Left => write!(f, "LEFT"),
+            JoinType::Right => write!(f, "RIGHT"),
+            JoinType::Full => write!(f, "FULL"),
+            JoinType::Cross => write!(f, "CROSS"),
+        }
+    }
+}
+
+impl JoinType {
+    /// 左表中没有匹配的行是否也要输出，此时右表的列为 NULL
+    pub fn keeps_left(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Full)
+    }
+
+    /// 右表中没有匹配的行是否也要输出，此时左表的列为 NULL
+    pub fn keeps_right(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Full)
+    }
+}
+
+/// 连接条件
+#[derive(Debug, PartialEq, Clone)]
+pub enum JoinConstraint {
+    On(Expression),
+    Using(Box<[SingleIdentifier]>),
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct OrderBy {
     pub identifier: Identifier,
     pub asc: bool,
+    pub nulls_first: Option<bool>, // NULLS FIRST / NULLS LAST，None 表示未指定
 }
 
 impl WithLocation for OrderBy {
@@ -136,16 +220,22 @@ impl Display for OrderBy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.identifier)?;
         if self.asc {
-            write!(f, " ASC")
+            write!(f, " ASC")?;
         } else {
-            write!(f, " DESC")
+            write!(f, " DESC")?;
+        }
+        match self.nulls_first {
+            Some(true) => write!(f, " NULLS FIRST"),
+            Some(false) => write!(f, " NULLS LAST"),
+            None => Ok(()),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// LIMIT 子句，expression 为常量表达式，可以包含参数占位符
+#[derive(Debug, PartialEq, Clone)]
 pub struct Limit {
-    pub limit: u64,
+    pub expression: Expression,
     pub leaf: Location,
 }
 
@@ -157,13 +247,14 @@ impl WithLocation for Limit {
 
 impl Display for Limit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "LIMIT {}", self.limit)
+        write!(f, "LIMIT {}", self.expression)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// OFFSET 子句，expression 为常量表达式，可以包含参数占位符
+#[derive(Debug, PartialEq, Clone)]
 pub struct Offset {
-    pub offset: u64,
+    pub expression: Expression,
     pub leaf: Location,
 }
 
@@ -175,6 +266,6 @@ impl WithLocation for Offset {
 
 impl Display for Offset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "OFFSET {}", self.offset)
+        write!(f, "OFFSET {}", self.expression)
     }
 }
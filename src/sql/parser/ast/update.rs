@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use super::{
+    expression::Expression,
+    identifier::SingleIdentifier,
+    leaf::{Location, WithLocation},
+};
+
+/// UPDATE table SET column = expression, ... [WHERE expression]
+#[derive(Debug, PartialEq, Clone)]
+pub struct Update {
+    pub table: SingleIdentifier,
+    pub assignments: Box<[Assignment]>,
+    pub wheres: Option<Expression>,
+    pub leaf: Location, // UPDATE 关键字的位置
+}
+
+impl WithLocation for Update {
+    fn location(&self) -> &Location {
+        &self.leaf
+    }
+}
+
+impl Display for Update {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UPDATE {} SET ", self.table.value)?;
+        for (index, assignment) in self.assignments.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", assignment)?;
+        }
+        if let Some(wheres) = &self.wheres {
+            write!(f, " WHERE {}", wheres)?;
+        }
+        Ok(())
+    }
+}
+
+/// SET 子句中的一项 column = expression
+#[derive(Debug, PartialEq, Clone)]
+pub struct Assignment {
+    pub column: SingleIdentifier,
+    pub value: Expression,
+}
+
+impl WithLocation for Assignment {
+    fn location(&self) -> &Location {
+        self.column.location()
+    }
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.column.value, self.value)
+    }
+}
@@ -1,4 +1,4 @@
-/// parser 词法分析，从 SQL tokens 序列构建抽象语法树 AST
+//! parser 词法分析，从 SQL tokens 序列构建抽象语法树 AST
 
 /// AST 抽象语法树各种节点定义，例如 语句、SELECT、表达式、标识符、字面量等
 pub mod ast;
@@ -7,4 +7,5 @@ pub mod ast;
 pub mod error;
 
 /// parser 实现词法分析
+#[allow(clippy::module_inception)]
 pub mod parser;
@@ -10,15 +10,19 @@ use crate::sql::{
 
 use super::{
     ast::{
+        alter_table::AlterTableOperation,
+        create_table::{ColumnDefinition, ColumnType},
         expression::{
-            Alias, BinaryExpression, BinaryOperator, Expression, Function, UnaryExpression,
-            UnaryOperator,
+            Alias, BinaryExpression, BinaryOperator, Expression, Function, Placeholder,
+            UnaryExpression, UnaryOperator,
         },
         identifier::{Identifier, SingleIdentifier},
+        insert::InsertSource,
         leaf::Location,
         literal::{Literal, Value},
-        select::{OrderBy, SelectItem},
-        Statement, Statements,
+        select::{Join, JoinConstraint, JoinType, Limit, Offset, OrderBy, SelectItem},
+        update::Assignment,
+        AlterTable, CreateTable, Delete, DropTable, Explain, Insert, Statement, Statements, Update,
     },
     error::ParseError,
 };
@@ -27,15 +31,19 @@ pub struct Parser<'a> {
     tokens: &'a [ParsedToken],
     index: usize,
     raw_sql: &'a str,
+    placeholders: usize, // 已解析的参数占位符数目
 }
 
 impl<'a> Parser<'a> {
-    pub fn new<'b>(tokens: &'a ParsedTokens<'b>) -> Self 
-    where 'b:'a{
+    pub fn new<'b>(tokens: &'a ParsedTokens<'b>) -> Self
+    where
+        'b: 'a,
+    {
         Self {
             tokens: tokens.tokens.as_ref(),
-            raw_sql: tokens.raw_sql.as_ref(),
+            raw_sql: tokens.raw_sql,
             index: 0,
+            placeholders: 0,
         }
     }
 
@@ -54,11 +62,20 @@ impl<'a> Parser<'a> {
     fn parse_statement(&mut self) -> Result<Statement, ParseError> {
         match self.peek().unwrap().token {
             Token::Keyword(Keyword::SELECT) => Ok(Statement::Select(self.parse_select()?)),
+            Token::Keyword(Keyword::CREATE) => {
+                Ok(Statement::CreateTable(self.parse_create_table()?))
+            }
+            Token::Keyword(Keyword::DROP) => Ok(Statement::DropTable(self.parse_drop_table()?)),
+            Token::Keyword(Keyword::ALTER) => Ok(Statement::AlterTable(self.parse_alter_table()?)),
+            Token::Keyword(Keyword::INSERT) => Ok(Statement::Insert(self.parse_insert()?)),
+            Token::Keyword(Keyword::UPDATE) => Ok(Statement::Update(self.parse_update()?)),
+            Token::Keyword(Keyword::DELETE) => Ok(Statement::Delete(self.parse_delete()?)),
+            Token::Keyword(Keyword::EXPLAIN) => Ok(Statement::Explain(self.parse_explain()?)),
             Token::Semicolon => self.parse_empty_statement(),
             _ => Err(ParseError::new(
                 "invalid statement",
-                self.location().clone(),
-                self.raw_sql.as_ref(),
+                *self.location(),
+                self.raw_sql,
             )),
         }
     }
@@ -79,18 +96,356 @@ impl<'a> Parser<'a> {
 
         let order_by: Box<[OrderBy]> = self.parse_order_by()?;
 
+        let (limit, offset) = self.parse_limit_offset()?;
+
         Ok(Select {
             items: select_items,
-            from: from,
-            wheres: wheres,
-            group_by: group_by,
-            having: having,
-            order_by: order_by,
-            limit: None,
-            offset: None,
+            from,
+            wheres,
+            group_by,
+            having,
+            order_by,
+            limit,
+            offset,
+        })
+    }
+
+    fn parse_create_table(&mut self) -> Result<CreateTable, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::CREATE));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume CREATE
+
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::TABLE)) {
+            return self.make_error(format_args!("expect keyword TABLE after CREATE"));
+        }
+        let name: SingleIdentifier = self.parse_single_identifier("table name")?;
+
+        if !self.next_if(|t| *t == Token::LeftParenthesis) {
+            return self.make_error(format_args!("expect ( after table name"));
+        }
+        let mut columns: Vec<ColumnDefinition> = vec![];
+        loop {
+            columns.push(self.parse_column_definition()?);
+            if self.next_if(|t| *t == Token::RightParenthesis) {
+                break;
+            }
+            if !self.next_if(|t| *t == Token::Comma) {
+                return self.make_error(format_args!("expect , or ) after column definition"));
+            }
+        }
+
+        Ok(CreateTable {
+            name,
+            columns: columns.into_boxed_slice(),
+            leaf,
+        })
+    }
+
+    fn parse_drop_table(&mut self) -> Result<DropTable, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::DROP));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume DROP
+
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::TABLE)) {
+            return self.make_error(format_args!("expect keyword TABLE after DROP"));
+        }
+        let if_exists: bool = self.next_if(|t| *t == Token::Keyword(Keyword::IF));
+        if if_exists && !self.next_if(|t| *t == Token::Keyword(Keyword::EXISTS)) {
+            return self.make_error(format_args!("expect keyword EXISTS after IF"));
+        }
+        Ok(DropTable {
+            name: self.parse_single_identifier("table name")?,
+            if_exists,
+            leaf,
+        })
+    }
+
+    fn parse_explain(&mut self) -> Result<Explain, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::EXPLAIN));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume EXPLAIN
+
+        let analyze: bool = self.next_if(|t| *t == Token::Keyword(Keyword::ANALYZE));
+        if self.peek().map(|t| &t.token) != Some(&Token::Keyword(Keyword::SELECT)) {
+            return self.make_error(format_args!("expect SELECT after EXPLAIN"));
+        }
+        Ok(Explain {
+            analyze,
+            select: self.parse_select()?,
+            leaf,
+        })
+    }
+
+    fn parse_alter_table(&mut self) -> Result<AlterTable, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::ALTER));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume ALTER
+
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::TABLE)) {
+            return self.make_error(format_args!("expect keyword TABLE after ALTER"));
+        }
+        let name: SingleIdentifier = self.parse_single_identifier("table name")?;
+
+        let operation: AlterTableOperation = match self.peek().map(|t| &t.token) {
+            Some(Token::Keyword(Keyword::ADD)) => {
+                self.next(); // consume ADD
+                self.next_if(|t| *t == Token::Keyword(Keyword::COLUMN));
+                let column: ColumnDefinition = self.parse_column_definition()?;
+                let default: Option<Expression> =
+                    if self.next_if(|t| *t == Token::Keyword(Keyword::DEFAULT)) {
+                        Some(self.parse_expression(0)?)
+                    } else {
+                        None
+                    };
+                AlterTableOperation::AddColumn { column, default }
+            }
+            Some(Token::Keyword(Keyword::DROP)) => {
+                self.next(); // consume DROP
+                self.next_if(|t| *t == Token::Keyword(Keyword::COLUMN));
+                AlterTableOperation::DropColumn(self.parse_single_identifier("column name")?)
+            }
+            Some(Token::Keyword(Keyword::RENAME)) => {
+                self.next(); // consume RENAME
+                if self.next_if(|t| *t == Token::Keyword(Keyword::TO)) {
+                    AlterTableOperation::RenameTable(
+                        self.parse_single_identifier("new table name")?,
+                    )
+                } else {
+                    self.next_if(|t| *t == Token::Keyword(Keyword::COLUMN));
+                    let from: SingleIdentifier = self.parse_single_identifier("column name")?;
+                    if !self.next_if(|t| *t == Token::Keyword(Keyword::TO)) {
+                        return self
+                            .make_error(format_args!("expect keyword TO after column name"));
+                    }
+                    let to: SingleIdentifier = self.parse_single_identifier("new column name")?;
+                    AlterTableOperation::RenameColumn { from, to }
+                }
+            }
+            _ => {
+                return self.make_error(format_args!("expect ADD, DROP or RENAME after table name"))
+            }
+        };
+
+        Ok(AlterTable {
+            name,
+            operation,
+            leaf,
+        })
+    }
+
+    fn parse_insert(&mut self) -> Result<Insert, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::INSERT));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume INSERT
+
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::INTO)) {
+            return self.make_error(format_args!("expect keyword INTO after INSERT"));
+        }
+        let table: SingleIdentifier = self.parse_single_identifier("table name")?;
+
+        let mut columns: Vec<SingleIdentifier> = vec![];
+        if self.next_if(|t| *t == Token::LeftParenthesis) {
+            loop {
+                columns.push(self.parse_single_identifier("column name")?);
+                if self.next_if(|t| *t == Token::RightParenthesis) {
+                    break;
+                }
+                if !self.next_if(|t| *t == Token::Comma) {
+                    return self.make_error(format_args!("expect , or ) after column name"));
+                }
+            }
+        }
+
+        let source: InsertSource = match self.peek().map(|t| &t.token) {
+            Some(Token::Keyword(Keyword::VALUES)) => {
+                self.next(); // consume VALUES
+                let mut rows: Vec<Box<[Expression]>> = vec![];
+                loop {
+                    rows.push(self.parse_values_row()?);
+                    if !self.next_if(|t| *t == Token::Comma) {
+                        break;
+                    }
+                }
+                InsertSource::Values(rows.into_boxed_slice())
+            }
+            Some(Token::Keyword(Keyword::SELECT)) => {
+                InsertSource::Select(Box::new(self.parse_select()?))
+            }
+            _ => return self.make_error(format_args!("expect keyword VALUES or SELECT")),
+        };
+
+        Ok(Insert {
+            table,
+            columns: columns.into_boxed_slice(),
+            source,
+            leaf,
+        })
+    }
+
+    /// VALUES 中的一行 (expression, ...)
+    fn parse_values_row(&mut self) -> Result<Box<[Expression]>, ParseError> {
+        if !self.next_if(|t| *t == Token::LeftParenthesis) {
+            return self.make_error(format_args!("expect ( of VALUES row"));
+        }
+        let mut row: Vec<Expression> = vec![];
+        loop {
+            row.push(self.parse_expression(0)?);
+            if self.next_if(|t| *t == Token::RightParenthesis) {
+                break;
+            }
+            if !self.next_if(|t| *t == Token::Comma) {
+                return self.make_error(format_args!("expect , or ) in VALUES row"));
+            }
+        }
+        Ok(row.into_boxed_slice())
+    }
+
+    fn parse_update(&mut self) -> Result<Update, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::UPDATE));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume UPDATE
+
+        let table: SingleIdentifier = self.parse_single_identifier("table name")?;
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::SET)) {
+            return self.make_error(format_args!("expect keyword SET after table name"));
+        }
+
+        let mut assignments: Vec<Assignment> = vec![];
+        loop {
+            let column: SingleIdentifier = self.parse_single_identifier("column name")?;
+            if !self.next_if(|t| *t == Token::Equal) {
+                return self.make_error(format_args!("expect = after column name"));
+            }
+            let value: Expression = self.parse_expression(0)?;
+            assignments.push(Assignment { column, value });
+            if !self.next_if(|t| *t == Token::Comma) {
+                break;
+            }
+        }
+
+        Ok(Update {
+            table,
+            assignments: assignments.into_boxed_slice(),
+            wheres: self.parse_where()?,
+            leaf,
+        })
+    }
+
+    fn parse_delete(&mut self) -> Result<Delete, ParseError> {
+        debug_assert_eq!(self.peek().unwrap().token, Token::Keyword(Keyword::DELETE));
+        let leaf: Location = Location::new(&self.location_and_next()); // consume DELETE
+
+        if !self.next_if(|t| *t == Token::Keyword(Keyword::FROM)) {
+            return self.make_error(format_args!("expect keyword FROM after DELETE"));
+        }
+        Ok(Delete {
+            table: self.parse_single_identifier("table name")?,
+            wheres: self.parse_where()?,
+            leaf,
+        })
+    }
+
+    /// 列定义 name type [NOT NULL | NULL]
+    fn parse_column_definition(&mut self) -> Result<ColumnDefinition, ParseError> {
+        let name: SingleIdentifier = self.parse_single_identifier("column name")?;
+        let column_type: ColumnType = self.parse_column_type()?;
+        let not_null: bool = if self.next_if(|t| *t == Token::Keyword(Keyword::NOT)) {
+            if !self.next_if(|t| *t == Token::Keyword(Keyword::NULL)) {
+                return self.make_error(format_args!("expect keyword NULL after NOT"));
+            }
+            true
+        } else {
+            self.next_if(|t| *t == Token::Keyword(Keyword::NULL));
+            false
+        };
+        Ok(ColumnDefinition {
+            name,
+            column_type,
+            not_null,
         })
     }
 
+    /// 列类型，VARCHAR 需要指定长度 VARCHAR(n)
+    fn parse_column_type(&mut self) -> Result<ColumnType, ParseError> {
+        let type_name: SingleIdentifier = self.parse_single_identifier("column type")?;
+        if type_name.value.eq_ignore_ascii_case("VARCHAR") {
+            if !self.next_if(|t| *t == Token::LeftParenthesis) {
+                return self.make_error(format_args!("expect ( after VARCHAR"));
+            }
+            let length: u16 = match self.peek().map(|t| &t.token) {
+                Some(Token::IntegerLiteral(_, number)) => {
+                    match u16::try_from(number.unwrap_or(0)) {
+                        Ok(length) if length > 0 => length,
+                        _ => {
+                            return self.make_error(format_args!(
+                                "invalid VARCHAR length, expect 1 to 65535"
+                            ))
+                        }
+                    }
+                }
+                _ => return self.make_error(format_args!("expect length of VARCHAR")),
+            };
+            self.next(); // consume length
+            if !self.next_if(|t| *t == Token::RightParenthesis) {
+                return self.make_error(format_args!("expect ) after VARCHAR length"));
+            }
+            return Ok(ColumnType::Varchar(length));
+        }
+        let decimal: bool = ["DECIMAL", "NUMERIC"]
+            .iter()
+            .any(|name| type_name.value.eq_ignore_ascii_case(name));
+        if decimal && self.next_if(|t| *t == Token::LeftParenthesis) {
+            let max: u8 = ColumnType::MAX_DECIMAL_PRECISION;
+            let precision: u8 = self.parse_type_modifier("precision", 1, max)?;
+            let scale: u8 = if self.next_if(|t| *t == Token::Comma) {
+                self.parse_type_modifier("scale", 0, precision)?
+            } else {
+                0
+            };
+            if !self.next_if(|t| *t == Token::RightParenthesis) {
+                return self.make_error(format_args!("expect ) after DECIMAL precision and scale"));
+            }
+            return Ok(ColumnType::Decimal(precision, scale));
+        }
+        match ColumnType::from_name(&type_name.value) {
+            Some(column_type) => Ok(column_type),
+            None => Err(ParseError::new(
+                format!("unknown column type {}", type_name.value),
+                self.tokens[self.index - 1].location,
+                self.raw_sql,
+            )),
+        }
+    }
+
+    /// 类型参数，例如 DECIMAL 的精度和小数位数，取值为 min 到 max
+    fn parse_type_modifier(&mut self, what: &str, min: u8, max: u8) -> Result<u8, ParseError> {
+        let value: Option<u8> = match self.peek().map(|t| &t.token) {
+            Some(Token::IntegerLiteral(_, number)) => u8::try_from(number.unwrap_or(0)).ok(),
+            _ => return self.make_error(format_args!("expect DECIMAL {what}")),
+        };
+        match value {
+            Some(value) if (min..=max).contains(&value) => {
+                self.next(); // consume number
+                Ok(value)
+            }
+            _ => self.make_error(format_args!(
+                "invalid DECIMAL {what}, expect {min} to {max}"
+            )),
+        }
+    }
+
+    /// 不带 . 的单个标识符
+    fn parse_single_identifier(&mut self, what: &str) -> Result<SingleIdentifier, ParseError> {
+        match self.peek() {
+            Some(token) => match &token.token {
+                Token::Identifier(ident) => {
+                    let identifier: SingleIdentifier = SingleIdentifier {
+                        value: ident.clone(),
+                        leaf: Location::new(&token.location),
+                    };
+                    self.next(); // consume identifier
+                    Ok(identifier)
+                }
+                _ => self.make_error(format_args!("invalid token {token}, expect {what}")),
+            },
+            None => self.make_error(format_args!("unexpected end of input, expect {what}")),
+        }
+    }
+
     fn parse_select_items(&mut self) -> Result<Box<[SelectItem]>, ParseError> {
         let mut items: Vec<SelectItem> = Vec::new();
         loop {
@@ -105,7 +460,9 @@ impl<'a> Parser<'a> {
                             | Keyword::WHERE
                             | Keyword::HAVING
                             | Keyword::GROUP
-                            | Keyword::ORDER => break,
+                            | Keyword::ORDER
+                            | Keyword::LIMIT
+                            | Keyword::OFFSET => break,
                             _ => {
                                 return self
                                     .make_error(format_args!("invalid keyword {kw}, expect FROM"))
@@ -128,17 +485,13 @@ impl<'a> Parser<'a> {
         if self.next_if(|t| *t == Token::Keyword(Keyword::FROM)) {
             let mut from: Vec<FromItem> = vec![];
             loop {
-                let expression: Expression = self.parse_expression(0)?;
-                let alias: Option<Identifier> =
-                    if self.next_if(|t| *t == Token::Keyword(Keyword::AS)) {
-                        Some(self.parse_identifier()?)
-                    } else {
-                        None
-                    };
-                from.push(FromItem {
-                    expression: expression,
-                    alias: alias,
-                });
+                let mut item: FromItem = self.parse_from_item()?;
+                let mut joins: Vec<Join> = vec![];
+                while let Some(join) = self.parse_join()? {
+                    joins.push(join);
+                }
+                item.joins = joins.into_boxed_slice();
+                from.push(item);
                 if !self.next_if(|t| *t == Token::Comma) {
                     break;
                 }
@@ -149,6 +502,84 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// 表或者子查询，以及可选的别名
+    fn parse_from_item(&mut self) -> Result<FromItem, ParseError> {
+        let expression: Expression = self.parse_expression(0)?;
+        let alias: Option<Identifier> = if self.next_if(|t| *t == Token::Keyword(Keyword::AS)) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(FromItem {
+            expression,
+            alias,
+            joins: vec![].into_boxed_slice(),
+        })
+    }
+
+    /// 解析一个 JOIN，下一个 token 不是 JOIN 相关的关键字时返回 None
+    fn parse_join(&mut self) -> Result<Option<Join>, ParseError> {
+        let join_type: JoinType = match self.peek().map(|t| &t.token) {
+            Some(Token::Keyword(Keyword::JOIN)) => JoinType::Inner,
+            Some(Token::Keyword(Keyword::INNER)) => {
+                self.next(); // consume INNER
+                JoinType::Inner
+            }
+            Some(Token::Keyword(keyword @ (Keyword::LEFT | Keyword::RIGHT | Keyword::FULL))) => {
+                let join_type: JoinType = match keyword {
+                    Keyword::LEFT => JoinType::Left,
+                    Keyword::RIGHT => JoinType::Right,
+                    _ => JoinType::Full,
+                };
+                self.next(); // consume LEFT / RIGHT / FULL
+                self.next_if(|t| *t == Token::Keyword(Keyword::OUTER));
+                join_type
+            }
+            Some(Token::Keyword(Keyword::CROSS)) => {
+                self.next(); // consume CROSS
+                JoinType::Cross
+            }
+            _ => return Ok(None),
+        };
+        let leaf: Location = match self.peek() {
+            Some(token) if token.token == Token::Keyword(Keyword::JOIN) => {
+                Location::new(&self.location_and_next()) // consume JOIN
+            }
+            _ => return self.make_error(format_args!("expect keyword JOIN after {}", join_type)),
+        };
+
+        let item: FromItem = self.parse_from_item()?;
+        let constraint: Option<JoinConstraint> = if join_type == JoinType::Cross {
+            None
+        } else if self.next_if(|t| *t == Token::Keyword(Keyword::ON)) {
+            Some(JoinConstraint::On(self.parse_expression(0)?))
+        } else if self.next_if(|t| *t == Token::Keyword(Keyword::USING)) {
+            if !self.next_if(|t| *t == Token::LeftParenthesis) {
+                return self.make_error(format_args!("expect ( after USING"));
+            }
+            let mut columns: Vec<SingleIdentifier> = vec![];
+            loop {
+                columns.push(self.parse_single_identifier("column name")?);
+                if self.next_if(|t| *t == Token::RightParenthesis) {
+                    break;
+                }
+                if !self.next_if(|t| *t == Token::Comma) {
+                    return self.make_error(format_args!("expect , or ) after column name"));
+                }
+            }
+            Some(JoinConstraint::Using(columns.into_boxed_slice()))
+        } else {
+            return self.make_error(format_args!("expect ON or USING after joined table"));
+        };
+
+        Ok(Some(Join {
+            join_type,
+            item,
+            constraint,
+            leaf,
+        }))
+    }
+
     fn parse_where(&mut self) -> Result<Option<Expression>, ParseError> {
         if self.next_if(|t| *t == Token::Keyword(Keyword::WHERE)) {
             Ok(Some(self.parse_expression(0)?))
@@ -189,18 +620,31 @@ impl<'a> Parser<'a> {
             if self.next_if(|t| *t == Token::Keyword(Keyword::BY)) {
                 loop {
                     let identifier: Identifier = self.parse_identifier()?;
-                    let asc: bool = if self.next_if(|t| *t == Token::Keyword(Keyword::ASC)) {
-                        true
-                    } else if self.next_if(|t| *t == Token::Keyword(Keyword::DESC)) {
-                        false
-                    } else {
-                        true
-                    };
+                    let asc: bool = self.next_if(|t| *t == Token::Keyword(Keyword::ASC))
+                        || !self.next_if(|t| *t == Token::Keyword(Keyword::DESC));
+                    let nulls_first: Option<bool> =
+                        if self.next_if(|t| *t == Token::Keyword(Keyword::NULLS)) {
+                            if self.next_if(|t| *t == Token::Keyword(Keyword::FIRST)) {
+                                Some(true)
+                            } else if self.next_if(|t| *t == Token::Keyword(Keyword::LAST)) {
+                                Some(false)
+                            } else {
+                                return self.make_error(format_args!(
+                                    "expect keyword FIRST or LAST after NULLS"
+                                ));
+                            }
+                        } else {
+                            None
+                        };
 
                     order_by.push(OrderBy {
-                        identifier: identifier,
-                        asc: asc,
+                        identifier,
+                        asc,
+                        nulls_first,
                     });
+                    if !self.next_if(|t| *t == Token::Comma) {
+                        break;
+                    }
                 }
             } else {
                 return self.make_error(format_args!("expect keyword BY of ORDER BY"));
@@ -209,6 +653,29 @@ impl<'a> Parser<'a> {
         Ok(order_by.into_boxed_slice())
     }
 
+    /// LIMIT 和 OFFSET 可以以任意顺序出现，各自最多一次
+    fn parse_limit_offset(&mut self) -> Result<(Option<Limit>, Option<Offset>), ParseError> {
+        let mut limit: Option<Limit> = None;
+        let mut offset: Option<Offset> = None;
+        while let Some(token) = self.peek() {
+            let leaf: Location = Location::new(&token.location);
+            if limit.is_none() && self.next_if(|t| *t == Token::Keyword(Keyword::LIMIT)) {
+                limit = Some(Limit {
+                    expression: self.parse_expression(0)?,
+                    leaf,
+                });
+            } else if offset.is_none() && self.next_if(|t| *t == Token::Keyword(Keyword::OFFSET)) {
+                offset = Some(Offset {
+                    expression: self.parse_expression(0)?,
+                    leaf,
+                });
+            } else {
+                break;
+            }
+        }
+        Ok((limit, offset))
+    }
+
     fn parse_select_item(&mut self) -> Result<SelectItem, ParseError> {
         let expr: Expression = self.parse_expression(0)?;
 
@@ -245,44 +712,59 @@ impl<'a> Parser<'a> {
         // 前缀运算符
         let prefix: Option<UnaryOperator> = self.parse_prefix_operator()?;
 
-        // 表达式左侧
-        let mut left: Expression = self.parse_expression_operand()?;
-
-        // 补上前缀
-        if let Some(op) = prefix {
-            left = Expression::UnaryExpression(UnaryExpression {
+        // 表达式左侧，NOT 作用于其后优先级更高的整个表达式，例如 NOT a > 1 即 NOT (a > 1)
+        let mut left: Expression = match prefix {
+            Some(op @ UnaryOperator::NOT(_)) => Expression::UnaryExpression(UnaryExpression {
+                expression: Box::new(self.parse_expression(op.priority())?),
                 operator: op,
-                expression: Box::new(left),
-            })
-        }
+            }),
+            Some(op) => Expression::UnaryExpression(UnaryExpression {
+                operator: op,
+                expression: Box::new(self.parse_expression_operand()?),
+            }),
+            None => self.parse_expression_operand()?,
+        };
 
         loop {
+            // 后缀运算符 IS [NOT] NULL 直接作用于 left
+            if let Some(op) = self.peek_postfix_operator()? {
+                if priority >= op.priority() {
+                    return Ok(left);
+                }
+                self.next(); // consume IS
+                if matches!(op, UnaryOperator::IsNotNull(_)) {
+                    self.next(); // consume NOT
+                }
+                self.next(); // consume NULL
+                left = Expression::UnaryExpression(UnaryExpression {
+                    operator: op,
+                    expression: Box::new(left),
+                });
+                continue;
+            }
+
             let operator: Option<BinaryOperator> = self.peek_binary_operator()?;
             match operator {
-                Some(op) => {
-                    // 根据当前优先级判断是否连接
-                    // 例一 1+2*3，首先初始优先级为 0，left 读入 1，op 读入 +
-                    // + 优先级大于 0，所以 1+ 合起来，继续读下一个表达式带上 + 的优先级
-                    // 下一次读取时 2*3 合起来了，因为 * 优先级大于 +，一起返回
-                    // 最后输出 1+(2*3)
-                    //
-                    // 例二 1*2+3，left 读入 1，op 读入 *，继续读下一个表达式带上 * 的优先级
-                    // 下一次读取 2 后不再和 + 结合，因为 * 优先级大，直接返回 2，left 变为 (1*2)
-                    // loop 第一次结束，继续循环读下一个运算符 +
-                    // 因为第一层的优先级 0 是最低的，总是形成 BinaryExpression，所以结合为 ((1*2)+3)
-                    if priority < op.priority() {
-                        self.next(); // consume operator
-                        let right: Expression = self.parse_expression(op.priority())?;
-                        left = Expression::BinaryExpression(BinaryExpression {
-                            left: Box::new(left),
-                            operator: op,
-                            right: Box::new(right),
-                        })
-                    } else {
-                        return Ok(left);
-                    }
+                // 根据当前优先级判断是否连接
+                // 例一 1+2*3，首先初始优先级为 0，left 读入 1，op 读入 +
+                // + 优先级大于 0，所以 1+ 合起来，继续读下一个表达式带上 + 的优先级
+                // 下一次读取时 2*3 合起来了，因为 * 优先级大于 +，一起返回
+                // 最后输出 1+(2*3)
+                //
+                // 例二 1*2+3，left 读入 1，op 读入 *，继续读下一个表达式带上 * 的优先级
+                // 下一次读取 2 后不再和 + 结合，因为 * 优先级大，直接返回 2，left 变为 (1*2)
+                // loop 第一次结束，继续循环读下一个运算符 +
+                // 因为第一层的优先级 0 是最低的，总是形成 BinaryExpression，所以结合为 ((1*2)+3)
+                Some(op) if priority < op.priority() => {
+                    self.next(); // consume operator
+                    let right: Expression = self.parse_expression(op.priority())?;
+                    left = Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(left),
+                        operator: op,
+                        right: Box::new(right),
+                    })
                 }
-                None => {
+                _ => {
                     return Ok(left);
                 }
             }
@@ -313,9 +795,20 @@ impl<'a> Parser<'a> {
             Some(token) => match &token.token {
                 Token::Keyword(kw) => match kw {
                     Keyword::SELECT => Ok(Expression::SubQuery(Box::new(self.parse_select()?))),
+                    Keyword::NULL => Ok(Expression::Literal(Literal {
+                        value: Value::Null,
+                        leaf: Location::new(&self.location_and_next()),
+                    })),
                     _ => self.make_error(format_args!("invalid keyword {kw} expect expression")),
                 },
-                Token::Identifier(_) => {
+                Token::Identifier(name) => {
+                    if let Some(value) = self.peek_typed_literal(name) {
+                        let leaf: Location = Location::new(&self.location_and_next());
+                        if !matches!(value, Value::Boolean(_)) {
+                            self.next(); // consume string literal
+                        }
+                        return Ok(Expression::Literal(Literal { value, leaf }));
+                    }
                     let ident: Identifier = self.parse_identifier()?;
                     // parse function starts with '(' and, loop arguments until ')'
                     if self.next_if(|t| *t == Token::LeftParenthesis) {
@@ -334,7 +827,7 @@ impl<'a> Parser<'a> {
                 }
                 Token::StringLiteral(s) => {
                     let expr: Expression = Expression::Literal(Literal {
-                        value: Value::String(Rc::clone(&s)),
+                        value: Value::String(Rc::clone(s)),
                         leaf: Location::new(&token.location),
                     });
                     self.next(); // consume string literal
@@ -343,6 +836,15 @@ impl<'a> Parser<'a> {
                 Token::IntegerLiteral(zeros, number) => {
                     Ok(Expression::Literal(self.parse_number(*zeros, *number)?))
                 }
+                Token::Placeholder => {
+                    let expr: Expression = Expression::Placeholder(Placeholder {
+                        index: self.placeholders,
+                        leaf: Location::new(&token.location),
+                    });
+                    self.placeholders += 1;
+                    self.next(); // consume ?
+                    Ok(expr)
+                }
                 Token::Multiply => {
                     let expr: Expression = Expression::Identifier(Identifier::Wildcard(
                         Location::new(&token.location),
@@ -370,6 +872,47 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// 查看以标识符开头的字面量，不消耗 token
+    /// TRUE、FALSE 是布尔值，DATE、TIME、TIMESTAMP、INTERVAL、DECIMAL、NUMERIC 后跟字符串是带类型的字面量，
+    /// X 后跟字符串是十六进制表示的 BLOB
+    fn peek_typed_literal(&self, name: &str) -> Option<Value> {
+        match name.to_ascii_uppercase().as_str() {
+            "TRUE" => return Some(Value::Boolean(true)),
+            "FALSE" => return Some(Value::Boolean(false)),
+            _ => {}
+        }
+        let Some(Token::StringLiteral(text)) = self.tokens.get(self.index + 1).map(|t| &t.token)
+        else {
+            return None;
+        };
+        let text: Rc<str> = Rc::clone(text);
+        match name.to_ascii_uppercase().as_str() {
+            "DECIMAL" | "NUMERIC" => Some(Value::Decimal(text)),
+            "DATE" => Some(Value::Date(text)),
+            "TIME" => Some(Value::Time(text)),
+            "TIMESTAMP" => Some(Value::Timestamp(text)),
+            "INTERVAL" => Some(Value::Interval(text)),
+            "X" => Some(Value::Blob(text)),
+            _ => None,
+        }
+    }
+
+    /// 查看后缀运算符 IS NULL、IS NOT NULL，不消耗 token
+    fn peek_postfix_operator(&mut self) -> Result<Option<UnaryOperator>, ParseError> {
+        if self.peek().map(|t| &t.token) != Some(&Token::Keyword(Keyword::IS)) {
+            return Ok(None);
+        }
+        let leaf: Location = Location::new(self.location());
+        let following = |offset: usize| self.tokens.get(self.index + offset).map(|t| &t.token);
+        match (following(1), following(2)) {
+            (Some(Token::Keyword(Keyword::NULL)), _) => Ok(Some(UnaryOperator::IsNull(leaf))),
+            (Some(Token::Keyword(Keyword::NOT)), Some(Token::Keyword(Keyword::NULL))) => {
+                Ok(Some(UnaryOperator::IsNotNull(leaf)))
+            }
+            _ => self.make_error(format_args!("expect NULL or NOT NULL after IS")),
+        }
+    }
+
     fn peek_binary_operator(&mut self) -> Result<Option<BinaryOperator>, ParseError> {
         match self.peek() {
             Some(token) => match token.token {
@@ -379,6 +922,7 @@ impl<'a> Parser<'a> {
                     self.location(),
                 )))),
                 Token::Divide => Ok(Some(BinaryOperator::Divide(Location::new(self.location())))),
+                Token::Modulo => Ok(Some(BinaryOperator::Modulo(Location::new(self.location())))),
                 Token::Equal => Ok(Some(BinaryOperator::Equal(Location::new(self.location())))),
                 Token::NotEqual => Ok(Some(BinaryOperator::NotEqual(Location::new(
                     self.location(),
@@ -461,8 +1005,8 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// parse identifier like single col_name or combined tab_name.col_name
-    /// TODO handle identifier with wildcard schema.table.*
+    /// parse identifier like single col_name, combined tab_name.col_name
+    /// or identifier with wildcard schema.table.*
     fn parse_identifier(&mut self) -> Result<Identifier, ParseError> {
         match self.peek() {
             Some(token) => match &token.token {
@@ -484,6 +1028,12 @@ impl<'a> Parser<'a> {
                                         });
                                         self.next(); // consume identifier
                                     }
+                                    Token::Multiply => {
+                                        self.next(); // consume *
+                                        return Ok(Identifier::WithWildcard(
+                                            identifiers.into_boxed_slice(),
+                                        ));
+                                    }
                                     _ => {
                                         return self.make_error(format_args!(
                                             "invalid token {token}, expect identifier"
@@ -519,18 +1069,14 @@ impl<'a> Parser<'a> {
             width = zeros as usize
         );
         s.parse().map_err(|e: std::num::ParseFloatError| {
-            ParseError::new(
-                e.to_string(),
-                self.location().clone(),
-                self.raw_sql.as_ref(),
-            )
+            ParseError::new(e.to_string(), *self.location(), self.raw_sql)
         })
     }
 
     fn parse_empty_statement(&mut self) -> Result<Statement, ParseError> {
         debug_assert_eq!(self.peek().unwrap().token, Token::Semicolon);
         self.next(); // consume ;
-        Ok(Statement::Empty(Location::new(&self.location())))
+        Ok(Statement::Empty(Location::new(self.location())))
     }
 
     fn peek(&self) -> Option<&ParsedToken> {
@@ -562,7 +1108,7 @@ impl<'a> Parser<'a> {
     }
 
     fn location_and_next(&mut self) -> TokenLocation {
-        let loc = self.location().clone();
+        let loc = *self.location();
         self.next();
         loc
     }
@@ -570,8 +1116,8 @@ impl<'a> Parser<'a> {
     fn make_error<T>(&self, format_args: Arguments) -> Result<T, ParseError> {
         Err(ParseError::new(
             format_args.to_string(),
-            self.location().clone(),
-            self.raw_sql.as_ref(),
+            *self.location(),
+            self.raw_sql,
         ))
     }
 }
@@ -1372,7 +1918,8 @@ mod test {
                         value: "b".into(),
                         leaf: Location::new(&tokens.tokens[3].location)
                     })),
-                    alias: None
+                    alias: None,
+                    joins: vec![].into_boxed_slice()
                 }]
                 .into_boxed_slice(),
                 wheres: None,
@@ -1411,7 +1958,8 @@ mod test {
                             value: "c".into(),
                             leaf: Location::new(&tokens.tokens[3].location)
                         })),
-                        alias: None
+                        alias: None,
+                        joins: vec![].into_boxed_slice()
                     },
                     FromItem {
                         expression: Expression::Identifier(Identifier::Single(SingleIdentifier {
@@ -1421,7 +1969,8 @@ mod test {
                         alias: Some(Identifier::Single(SingleIdentifier {
                             value: "e".into(),
                             leaf: Location::new(&tokens.tokens[7].location)
-                        }))
+                        })),
+                        joins: vec![].into_boxed_slice()
                     }
                 ]
                 .into_boxed_slice(),
@@ -1461,6 +2010,7 @@ mod test {
                         leaf: Location::new(&tokens.tokens[3].location)
                     })),
                     alias: None,
+                    joins: vec![].into_boxed_slice()
                 }]
                 .into_boxed_slice(),
                 wheres: Some(Expression::BinaryExpression(BinaryExpression {
@@ -1513,6 +2063,7 @@ mod test {
                         leaf: Location::new(&tokens.tokens[3].location)
                     })),
                     alias: None,
+                    joins: vec![].into_boxed_slice()
                 }]
                 .into_boxed_slice(),
                 wheres: Some(Expression::BinaryExpression(BinaryExpression {
@@ -1644,6 +2195,7 @@ mod test {
                                 }
                             )),
                             alias: None,
+                            joins: vec![].into_boxed_slice()
                         }]
                         .into_boxed_slice(),
                         wheres: None,
@@ -1656,7 +2208,8 @@ mod test {
                     alias: Some(Identifier::Single(SingleIdentifier {
                         value: "t".into(),
                         leaf: Location::new(&tokens.tokens[12].location)
-                    }))
+                    })),
+                    joins: vec![].into_boxed_slice()
                 }]
                 .into_boxed_slice(),
                 wheres: None,
@@ -1668,4 +2221,453 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn select_table_wildcard() {
+        let tokens: ParsedTokens = Tokenizer::new("SELECT t.*, a FROM t;").tokenize().unwrap();
+        let mut parser: Parser<'_> = Parser::new(&tokens);
+        let statements: Statements = parser.parse().unwrap();
+        assert_eq!(statements.statements.len(), 1);
+        match &statements.statements[0] {
+            Statement::Select(select) => {
+                assert_eq!(
+                    select.items[0],
+                    SelectItem::Expression(Expression::Identifier(Identifier::WithWildcard(
+                        vec![SingleIdentifier {
+                            value: "t".into(),
+                            leaf: Location::new(&tokens.tokens[1].location),
+                        }]
+                        .into_boxed_slice()
+                    )))
+                );
+                assert_eq!(select.to_string(), "SELECT t.*, a FROM t");
+            }
+            _ => panic!("expect select"),
+        }
+    }
+
+    #[test]
+    fn select_order_by() {
+        let tokens: ParsedTokens = Tokenizer::new("SELECT a FROM t ORDER BY a DESC, b, c ASC;")
+            .tokenize()
+            .unwrap();
+        let mut parser: Parser<'_> = Parser::new(&tokens);
+        let statements: Statements = parser.parse().unwrap();
+        assert_eq!(statements.statements.len(), 1);
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT a FROM t ORDER BY a DESC, b ASC, c ASC;"
+        );
+    }
+
+    #[test]
+    fn select_order_by_nulls() {
+        let tokens: ParsedTokens =
+            Tokenizer::new("SELECT a FROM t ORDER BY a DESC NULLS LAST, b nulls first")
+                .tokenize()
+                .unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT a FROM t ORDER BY a DESC NULLS LAST, b ASC NULLS FIRST;"
+        );
+
+        let tokens: ParsedTokens = Tokenizer::new("SELECT a FROM t ORDER BY a NULLS")
+            .tokenize()
+            .unwrap();
+        assert!(Parser::new(&tokens).parse().is_err());
+    }
+
+    #[test]
+    fn select_limit_offset() {
+        let tokens: ParsedTokens =
+            Tokenizer::new("SELECT a FROM t ORDER BY a LIMIT 10 OFFSET 2; SELECT 1 limit 3")
+                .tokenize()
+                .unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(statements.statements.len(), 2);
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT a FROM t ORDER BY a ASC LIMIT 10 OFFSET 2;"
+        );
+        assert_eq!(statements.statements[1].to_string(), "SELECT 1 LIMIT 3;");
+    }
+
+    #[test]
+    fn select_limit_placeholder() {
+        let tokens: ParsedTokens =
+            Tokenizer::new("SELECT a FROM t WHERE a > ? OFFSET ? LIMIT ? + 1")
+                .tokenize()
+                .unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT a FROM t WHERE (a > ?) LIMIT (? + 1) OFFSET ?;"
+        );
+        match &statements.statements[0] {
+            Statement::Select(select) => {
+                match &select.offset.as_ref().unwrap().expression {
+                    Expression::Placeholder(placeholder) => assert_eq!(placeholder.index, 1),
+                    _ => panic!("expect placeholder"),
+                }
+                match &select.limit.as_ref().unwrap().expression {
+                    Expression::BinaryExpression(binary) => {
+                        assert_eq!(
+                            *binary.left,
+                            Expression::Placeholder(Placeholder {
+                                index: 2,
+                                leaf: Location {
+                                    line_number: 1,
+                                    column_number: 44,
+                                    offset: 43,
+                                },
+                            })
+                        )
+                    }
+                    _ => panic!("expect binary expression"),
+                }
+            }
+            _ => panic!("expect select"),
+        }
+
+        // LIMIT、OFFSET 各自只能出现一次
+        let tokens: ParsedTokens = Tokenizer::new("SELECT a FROM t LIMIT 1 LIMIT 2")
+            .tokenize()
+            .unwrap();
+        assert!(Parser::new(&tokens).parse().is_err());
+    }
+
+    #[test]
+    fn create_table() {
+        let sql: &str =
+            "CREATE TABLE stu (name varchar(32) NOT NULL, age Int, score DOUBLE NULL, memo text)";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "CREATE TABLE stu (name VARCHAR(32) NOT NULL, age INT, score DOUBLE, memo TEXT);"
+        );
+        match &statements.statements[0] {
+            Statement::CreateTable(create_table) => {
+                assert_eq!(create_table.leaf.offset, 0);
+                assert_eq!(create_table.columns.len(), 4);
+                assert_eq!(create_table.columns[0].column_type, ColumnType::Varchar(32));
+                assert!(create_table.columns[0].not_null);
+                assert!(!create_table.columns[2].not_null);
+            }
+            _ => panic!("expect create table"),
+        }
+    }
+
+    #[test]
+    fn create_table_error() {
+        for (sql, message) in [
+            ("CREATE stu (a INT)", "expect keyword TABLE after CREATE"),
+            ("CREATE TABLE stu a INT", "expect ( after table name"),
+            ("CREATE TABLE stu (a BIGNUM)", "unknown column type BIGNUM"),
+            ("CREATE TABLE stu (a VARCHAR)", "expect ( after VARCHAR"),
+            ("CREATE TABLE stu (a VARCHAR(0))", "invalid VARCHAR length"),
+            (
+                "CREATE TABLE stu (a VARCHAR(70000))",
+                "invalid VARCHAR length",
+            ),
+            (
+                "CREATE TABLE stu (a DECIMAL(0))",
+                "invalid DECIMAL precision, expect 1 to 38",
+            ),
+            (
+                "CREATE TABLE stu (a DECIMAL(5, 6))",
+                "invalid DECIMAL scale, expect 0 to 5",
+            ),
+            (
+                "CREATE TABLE stu (a DECIMAL(5 2))",
+                "expect ) after DECIMAL precision and scale",
+            ),
+            (
+                "CREATE TABLE stu (a INT NOT)",
+                "expect keyword NULL after NOT",
+            ),
+            (
+                "CREATE TABLE stu (a INT b INT)",
+                "expect , or ) after column definition",
+            ),
+            (
+                "CREATE TABLE stu (a INT,",
+                "unexpected end of input, expect column name",
+            ),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn insert() {
+        let sql: &str = "insert into stu (name, age) values ('张三', 18), (?, -1)";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "INSERT INTO stu (name, age) VALUES ('张三', 18), (?, - 1);"
+        );
+        match &statements.statements[0] {
+            Statement::Insert(insert) => {
+                assert_eq!(insert.leaf.offset, 0);
+                assert_eq!(insert.columns.len(), 2);
+                match &insert.source {
+                    InsertSource::Values(rows) => assert_eq!(rows.len(), 2),
+                    _ => panic!("expect values"),
+                }
+            }
+            _ => panic!("expect insert"),
+        }
+
+        let sql: &str = "INSERT INTO tea SELECT name FROM stu WHERE age > 18";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "INSERT INTO tea SELECT name FROM stu WHERE (age > 18);"
+        );
+    }
+
+    #[test]
+    fn insert_error() {
+        for (sql, message) in [
+            ("INSERT stu VALUES (1)", "expect keyword INTO after INSERT"),
+            (
+                "INSERT INTO stu (a b) VALUES (1)",
+                "expect , or ) after column name",
+            ),
+            ("INSERT INTO stu (a)", "expect keyword VALUES or SELECT"),
+            ("INSERT INTO stu VALUES 1", "expect ( of VALUES row"),
+            (
+                "INSERT INTO stu VALUES (1 2)",
+                "expect , or ) in VALUES row",
+            ),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn update_delete() {
+        let sql: &str = "update stu set age = age + 1, name = 'a' where age > ?; DELETE FROM stu";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "UPDATE stu SET age = (age + 1), name = 'a' WHERE (age > ?);"
+        );
+        assert_eq!(statements.statements[1].to_string(), "DELETE FROM stu;");
+        match &statements.statements[1] {
+            Statement::Delete(delete) => {
+                assert_eq!(delete.leaf.offset, 56);
+                assert!(delete.wheres.is_none());
+            }
+            _ => panic!("expect delete"),
+        }
+
+        for (sql, message) in [
+            ("UPDATE stu age = 1", "expect keyword SET after table name"),
+            ("UPDATE stu SET age 1", "expect = after column name"),
+            (
+                "UPDATE stu SET",
+                "unexpected end of input, expect column name",
+            ),
+            ("DELETE stu", "expect keyword FROM after DELETE"),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn drop_alter_table() {
+        for (sql, expected) in [
+            ("drop table stu", "DROP TABLE stu;"),
+            ("DROP TABLE IF EXISTS stu", "DROP TABLE IF EXISTS stu;"),
+            (
+                "ALTER TABLE stu ADD score double not null default 1 + 1",
+                "ALTER TABLE stu ADD COLUMN score DOUBLE NOT NULL DEFAULT (1 + 1);",
+            ),
+            (
+                "ALTER TABLE stu ADD COLUMN memo TEXT",
+                "ALTER TABLE stu ADD COLUMN memo TEXT;",
+            ),
+            (
+                "ALTER TABLE stu DROP age",
+                "ALTER TABLE stu DROP COLUMN age;",
+            ),
+            (
+                "ALTER TABLE stu RENAME COLUMN age TO years",
+                "ALTER TABLE stu RENAME COLUMN age TO years;",
+            ),
+            (
+                "ALTER TABLE stu RENAME TO student",
+                "ALTER TABLE stu RENAME TO student;",
+            ),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let statements: Statements = Parser::new(&tokens).parse().unwrap();
+            assert_eq!(statements.statements[0].to_string(), expected);
+        }
+
+        for (sql, message) in [
+            ("DROP stu", "expect keyword TABLE after DROP"),
+            ("DROP TABLE IF stu", "expect keyword EXISTS after IF"),
+            ("ALTER stu ADD a INT", "expect keyword TABLE after ALTER"),
+            (
+                "ALTER TABLE stu MODIFY a INT",
+                "expect ADD, DROP or RENAME after table name",
+            ),
+            (
+                "ALTER TABLE stu RENAME a b",
+                "expect keyword TO after column name",
+            ),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn select_join() {
+        let sql: &str = "SELECT * FROM a JOIN b ON a.id = b.id left outer join c AS d using (id, x), e CROSS JOIN (SELECT x FROM g) AS f; SELECT * FROM a FULL JOIN b USING (id) RIGHT JOIN c ON 1";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT * FROM a INNER JOIN b ON (a.id = b.id) LEFT JOIN c AS d USING (id, x), e CROSS JOIN (SELECT x FROM g) AS f;"
+        );
+        assert_eq!(
+            statements.statements[1].to_string(),
+            "SELECT * FROM a FULL JOIN b USING (id) RIGHT JOIN c ON 1;"
+        );
+    }
+
+    #[test]
+    fn select_join_error() {
+        for (sql, message) in [
+            (
+                "SELECT * FROM a LEFT b ON 1",
+                "expect keyword JOIN after LEFT",
+            ),
+            (
+                "SELECT * FROM a JOIN b",
+                "expect ON or USING after joined table",
+            ),
+            ("SELECT * FROM a JOIN b USING id", "expect ( after USING"),
+            (
+                "SELECT * FROM a JOIN b USING (id x)",
+                "expect , or ) after column name",
+            ),
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn create_table_types() {
+        let sql: &str = "CREATE TABLE t (a BOOL, b BIGINT, c NUMERIC, d DECIMAL(10), e DECIMAL(10, 2), f DATE, g TIME, h TIMESTAMP, i INTERVAL, j BYTEA)";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "CREATE TABLE t (a BOOLEAN, b BIGINT, c DECIMAL(38,0), d DECIMAL(10,0), e DECIMAL(10,2), f DATE, g TIME, h TIMESTAMP, i INTERVAL, j BLOB);"
+        );
+    }
+
+    #[test]
+    fn typed_literals() {
+        let sql: &str = "SELECT TRUE, false, DECIMAL '1.50', DATE '2024-01-01', TIME '12:00', TIMESTAMP '2024-01-01 12:00:00', INTERVAL '1 day', x'0aFF', date FROM t";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT TRUE, FALSE, DECIMAL '1.50', DATE '2024-01-01', TIME '12:00', TIMESTAMP '2024-01-01 12:00:00', INTERVAL '1 day', X'0aFF', date FROM t;"
+        );
+    }
+
+    #[test]
+    fn modulo() {
+        let sql: &str = "SELECT a % 2 * 3, a + b%c FROM t";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT ((a % 2) * 3), (a + (b % c)) FROM t;"
+        );
+    }
+
+    #[test]
+    fn not_precedence() {
+        let sql: &str = "SELECT a FROM t WHERE NOT a > 1 AND NOT b = c OR NOT NOT d";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT a FROM t WHERE ((NOT (a > 1) AND NOT (b = c)) OR NOT NOT d);"
+        );
+    }
+
+    #[test]
+    fn is_null() {
+        let sql: &str = "SELECT NULL, a IS NULL, a + 1 IS NOT NULL AND NOT b IS NULL, a = b IS NULL OR c FROM t";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "SELECT NULL, a IS NULL, ((a + 1) IS NOT NULL AND NOT b IS NULL), ((a = b) IS NULL OR c) FROM t;"
+        );
+
+        for sql in ["SELECT a IS 1", "SELECT a IS NOT", "SELECT a IS"] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("expect NULL or NOT NULL after IS"),
+                "{}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn explain() {
+        let sql: &str =
+            "explain SELECT a FROM t WHERE a > 1; EXPLAIN ANALYZE SELECT * FROM t LIMIT 1";
+        let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+        let statements: Statements = Parser::new(&tokens).parse().unwrap();
+        assert_eq!(
+            statements.statements[0].to_string(),
+            "EXPLAIN SELECT a FROM t WHERE (a > 1);"
+        );
+        assert_eq!(
+            statements.statements[1].to_string(),
+            "EXPLAIN ANALYZE SELECT * FROM t LIMIT 1;"
+        );
+
+        for sql in [
+            "EXPLAIN DELETE FROM t",
+            "EXPLAIN ANALYZE",
+            "EXPLAIN EXPLAIN SELECT 1",
+        ] {
+            let tokens: ParsedTokens = Tokenizer::new(sql).tokenize().unwrap();
+            let error: ParseError = Parser::new(&tokens).parse().unwrap_err();
+            assert!(
+                error.to_string().contains("expect SELECT after EXPLAIN"),
+                "{}",
+                error
+            );
+        }
+    }
 }
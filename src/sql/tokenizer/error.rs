@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::sql::parser::ast::leaf::Location;
+
 use super::str_scanner::TokenLocation;
 
 #[derive(Debug)]
@@ -17,10 +19,19 @@ impl TokenizeError {
     ) -> Self {
         Self {
             message: message.into(),
-            location: location,
+            location,
             raw_sql: raw_sql.into(),
         }
     }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// 出错的位置
+    pub fn location(&self) -> Location {
+        Location::new(&self.location)
+    }
 }
 
 impl std::error::Error for TokenizeError {}
@@ -28,13 +39,7 @@ impl std::error::Error for TokenizeError {}
 impl Display for TokenizeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         const SKIP_BACKWARD: usize = 16;
-        let skip: usize = {
-            if self.location.offset > SKIP_BACKWARD {
-                self.location.offset - SKIP_BACKWARD
-            } else {
-                0
-            }
-        };
+        let skip: usize = self.location.offset.saturating_sub(SKIP_BACKWARD);
         let near: String = self
             .raw_sql
             .chars()
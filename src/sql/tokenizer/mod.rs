@@ -1,4 +1,4 @@
-/// tokenizer 提供 SQL 语句的词法分析
+//! tokenizer 提供 SQL 语句的词法分析
 
 /// token 定义各种 token 类型，包括关键字、标识符、字面量等
 pub mod token;
@@ -11,4 +11,5 @@ pub mod str_scanner;
 pub mod error;
 
 /// tokenizer 词法分析核心实现
+#[allow(clippy::module_inception)]
 pub mod tokenizer;
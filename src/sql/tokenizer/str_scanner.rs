@@ -8,6 +8,12 @@ pub struct TokenLocation {
     previous: char, // \n \r\n \r
 }
 
+impl Default for TokenLocation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenLocation {
     pub fn new() -> Self {
         Self {
@@ -70,12 +76,12 @@ impl<'a> Scanner<'a> {
     }
 
     pub fn location(&self) -> TokenLocation {
-        self.location.clone()
+        self.location
     }
 
     /// peek the next but not consume
     pub fn peek(&mut self) -> Option<char> {
-        self.peekable.peek().map(|c| *c)
+        self.peekable.peek().copied()
     }
 
     /// next advances the iter
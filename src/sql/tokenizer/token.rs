@@ -24,14 +24,45 @@ pub enum Keyword {
     OR,
     NOT,
     HAVING,
+    NULLS,
+    FIRST,
+    LAST,
+    INSERT,
+    INTO,
+    VALUES,
+    UPDATE,
+    SET,
+    DELETE,
+    DROP,
+    ALTER,
+    ADD,
+    COLUMN,
+    RENAME,
+    TO,
+    IF,
+    EXISTS,
+    DEFAULT,
+    JOIN,
+    INNER,
+    LEFT,
+    RIGHT,
+    FULL,
+    OUTER,
+    CROSS,
+    ON,
+    USING,
+    EXPLAIN,
+    ANALYZE,
 } // 注意同步更新 ALL_KEY_WORDS
 
 use Keyword::*;
 
 /// 关键字数组
-const ALL_KEY_WORDS: [Keyword; 19] = [
+const ALL_KEY_WORDS: [Keyword; 48] = [
     SELECT, FROM, WHERE, GROUP, BY, ORDER, LIMIT, OFFSET, AS, DESC, ASC, CREATE, TABLE, IS, NULL,
-    AND, OR, NOT, HAVING,
+    AND, OR, NOT, HAVING, NULLS, FIRST, LAST, INSERT, INTO, VALUES, UPDATE, SET, DELETE, DROP,
+    ALTER, ADD, COLUMN, RENAME, TO, IF, EXISTS, DEFAULT, JOIN, INNER, LEFT, RIGHT, FULL, OUTER,
+    CROSS, ON, USING, EXPLAIN, ANALYZE,
 ];
 
 /// 关键字映射。string -> keyword
@@ -60,13 +91,13 @@ impl Keyword {
     /// 获取关键字的映射，不应频繁调用
     pub fn map() -> &'static HashMap<&'static str, Keyword> {
         // LazyLock::force(&KEY_WORD_MAP)
-        &*KEYWORD_MAP
+        &KEYWORD_MAP
     }
 
     /// 获取关键字的最大长度，不应频繁调用
     pub fn max_length() -> usize {
         // *LazyLock::force(&KEYWORD_MAX_LENGTH)
-        *&*KEYWORD_MAX_LENGTH
+        *KEYWORD_MAX_LENGTH
     }
 
     /// 关键字转换到字符串
@@ -91,6 +122,35 @@ impl Keyword {
             OR => "OR",
             NOT => "NOT",
             HAVING => "HAVING",
+            NULLS => "NULLS",
+            FIRST => "FIRST",
+            LAST => "LAST",
+            INSERT => "INSERT",
+            INTO => "INTO",
+            VALUES => "VALUES",
+            UPDATE => "UPDATE",
+            SET => "SET",
+            DELETE => "DELETE",
+            DROP => "DROP",
+            ALTER => "ALTER",
+            ADD => "ADD",
+            COLUMN => "COLUMN",
+            RENAME => "RENAME",
+            TO => "TO",
+            IF => "IF",
+            EXISTS => "EXISTS",
+            DEFAULT => "DEFAULT",
+            JOIN => "JOIN",
+            INNER => "INNER",
+            LEFT => "LEFT",
+            RIGHT => "RIGHT",
+            FULL => "FULL",
+            OUTER => "OUTER",
+            CROSS => "CROSS",
+            ON => "ON",
+            USING => "USING",
+            EXPLAIN => "EXPLAIN",
+            ANALYZE => "ANALYZE",
         }
     }
 }
@@ -118,11 +178,13 @@ pub enum Token {
     Minus,                            // -
     Multiply,                         // *
     Divide,                           // /
+    Modulo,                           // %
     LeftParenthesis,                  // (
     RightParenthesis,                 // )
     Comma,                            // 逗号
     Semicolon,                        // 分号
     Period,                           // .
+    Placeholder,                      // ? 参数占位符
 }
 
 impl Display for Token {
@@ -145,11 +207,13 @@ impl Display for Token {
             Token::Minus => write!(f, "-"),
             Token::Multiply => write!(f, "*"),
             Token::Divide => write!(f, "/"),
+            Token::Modulo => write!(f, "%"),
             Token::LeftParenthesis => write!(f, "("),
             Token::RightParenthesis => write!(f, ")"),
             Token::Comma => write!(f, ","),
             Token::Semicolon => write!(f, ";"),
             Token::Period => write!(f, "."),
+            Token::Placeholder => write!(f, "?"),
         }
     }
 }
@@ -158,14 +222,14 @@ impl Display for Token {
 #[derive(Debug, Clone)]
 pub struct ParsedTokens<'a> {
     pub tokens: Box<[ParsedToken]>, // 解析后的 Token 序列。Box 表示对数组的只读所有
-    pub raw_sql: &'a str,          // 原始 SQL 字符串。
+    pub raw_sql: &'a str,           // 原始 SQL 字符串。
 }
 
 impl<'a> ParsedTokens<'a> {
     pub fn new(tokens: Vec<ParsedToken>, raw_sql: &'a str) -> Self {
         Self {
             tokens: tokens.into_boxed_slice(),
-            raw_sql: raw_sql,
+            raw_sql,
         }
     }
 
@@ -183,7 +247,8 @@ impl<'a> Display for ParsedTokens<'a> {
                     Token::Keyword(_)
                     | Token::Identifier(_)
                     | Token::StringLiteral(_)
-                    | Token::IntegerLiteral(_, _) => f.write_str(" ")?,
+                    | Token::IntegerLiteral(_, _)
+                    | Token::Placeholder => f.write_str(" ")?,
                     Token::Equal
                     | Token::NotEqual
                     | Token::LessThan
@@ -194,6 +259,7 @@ impl<'a> Display for ParsedTokens<'a> {
                     | Token::Minus
                     | Token::Multiply
                     | Token::Divide
+                    | Token::Modulo
                     | Token::LeftParenthesis
                     | Token::RightParenthesis
                     | Token::Period => f.write_str(" ")?,
@@ -20,7 +20,7 @@ impl<'a> Tokenizer<'a> {
         Self {
             key_words: Keyword::map(),
             ley_word_max_length: Keyword::max_length(),
-            sql: sql,
+            sql,
             scanner: Scanner::new(sql),
         }
     }
@@ -40,7 +40,7 @@ impl<'a> Tokenizer<'a> {
             }
         }
 
-        return Ok(ParsedTokens::new(tokens, self.sql));
+        Ok(ParsedTokens::new(tokens, self.sql))
     }
 
     /// read next_token from scanner
@@ -60,8 +60,10 @@ impl<'a> Tokenizer<'a> {
                     '-' => return self.token_and_next(Token::Minus),
                     '*' => return self.token_and_next(Token::Multiply),
                     '/' => return self.token_and_next(Token::Divide),
+                    '%' => return self.token_and_next(Token::Modulo),
                     '(' => return self.token_and_next(Token::LeftParenthesis),
                     ')' => return self.token_and_next(Token::RightParenthesis),
+                    '?' => return self.token_and_next(Token::Placeholder),
                     '<' => return self.next_less(),
                     '>' => return self.next_great(),
                     '!' => return self.next_bang(),
@@ -112,7 +114,7 @@ impl<'a> Tokenizer<'a> {
                                 text.push('\'');
                                 self.next_char();
                             }
-                            ';' | '=' | '>' | '<' | ',' | '.' => {
+                            ';' | '=' | '>' | '<' | '!' | ',' | '.' | ')' => {
                                 // end string
                                 break;
                             }
@@ -493,6 +495,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn text_in_parenthesis() {
+        let tokens = Tokenizer::new("('a','b')").tokenize().unwrap();
+        let tokens: Vec<_> = tokens.tokens().iter().map(|t| t.token.clone()).collect();
+        assert_eq(
+            tokens,
+            vec![
+                Token::LeftParenthesis,
+                Token::StringLiteral("a".into()),
+                Token::Comma,
+                Token::StringLiteral("b".into()),
+                Token::RightParenthesis,
+            ],
+        );
+    }
+
     #[test]
     fn select_text_escape2() {
         assert!(Tokenizer::new("SELECT '''he\nllo''';").tokenize().is_err())